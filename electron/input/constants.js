@@ -3,3 +3,34 @@ export const TRIGGER_DRAG_THRESHOLD = 0.015;
 export const TRIGGER_CLICK_CANCEL_THRESHOLD = 0.03;
 export const TRIGGER_SCROLL_MULTIPLIER = 0.5;
 export const TRIGGER_SCROLL_MAX = 140;
+
+// Mirrors the native DeviceEventType enum / ネイティブの DeviceEventType 列挙型と対応
+export const DEVICE_EVENT_DEACTIVATED = 1;
+export const DEVICE_EVENT_ROLE_CHANGED = 3;
+
+// Mirror the native TrackedControllerRole / InputHand enums
+// ネイティブの TrackedControllerRole / InputHand 列挙型と対応
+export const CONTROLLER_ROLE_LEFT_HAND = 1;
+export const CONTROLLER_ROLE_RIGHT_HAND = 2;
+export const INPUT_HAND_LEFT = 1;
+export const INPUT_HAND_RIGHT = 2;
+
+// Mirror the native GestureEventType enum / ネイティブの GestureEventType 列挙型と対応
+export const GESTURE_PRESS = 0;
+export const GESTURE_REPEAT = 2;
+export const GESTURE_SCROLL = 4;
+
+// Accepted rateHz range of the native startInputPoller / ネイティブの startInputPoller が受け付ける rateHz の範囲
+export const MIN_POLLER_RATE_HZ = 30;
+export const MAX_POLLER_RATE_HZ = 1000;
+
+// Mirror the native InputEventType enum / ネイティブの InputEventType 列挙型と対応
+export const INPUT_EVENT_HOVER_MOVE = 0;
+export const INPUT_EVENT_HOVER_END = 1;
+export const INPUT_EVENT_PRESS = 2;
+export const INPUT_EVENT_RELEASE = 3;
+export const INPUT_EVENT_SCROLL = 4;
+
+// Pixels per second the native poller scrolls at full stick deflection
+// ネイティブポーラーがスティックを最大に倒したときの毎秒スクロール量 (ピクセル)
+export const POLLER_SCROLL_SPEED = 1200;
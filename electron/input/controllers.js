@@ -2,9 +2,33 @@ import { state } from './state.js';
 import { endDrag, processGripDrag } from './drag.js';
 import { handleTriggerInput } from './trigger.js';
 
-export function computeHitFromPose(poseMatrix, overlayHandle) {
+function readAimRay(aimHand) {
+  if (!aimHand || typeof state.overlayManager.getAimRay !== 'function') {
+    return null;
+  }
+  try {
+    return state.overlayManager.getAimRay(aimHand);
+  } catch {
+    // SteamVR Input not initialized or aim actions not configured
+    // SteamVR Input が未初期化、またはエイムアクションが未設定
+    return null;
+  }
+}
+
+export function computeHitFromPose(poseMatrix, overlayHandle, aimHand = null) {
   try {
     if (!state.overlayManager) return null;
+    // Prefer the SteamVR Input aim pose (controller tip) over the grip pose
+    // グリップのポーズより SteamVR Input のエイムポーズ（コントローラー先端）を優先する
+    const aimRay = readAimRay(aimHand);
+    if (aimRay) {
+      return state.overlayManager.computeOverlayIntersection(
+        overlayHandle,
+        aimRay.origin,
+        aimRay.direction,
+      );
+    }
+
     // Extract position and forward direction / 位置と前方方向を抽出する
     // Position (Tx, Ty, Tz) / 位置（Tx, Ty, Tz）
     const px = poseMatrix[3];
@@ -29,15 +53,23 @@ export function computeHitFromPose(poseMatrix, overlayHandle) {
   }
 }
 
+/**
+ * Trigger gestures and grip drag of one controller; `trigger: false` leaves the trigger
+ * to the native poller / コントローラー1台分のトリガージェスチャーとグリップドラッグ。
+ * `trigger: false` ならトリガーはネイティブポーラーに任せる
+ */
 export function processController(
   controllerId,
   poseMatrix,
   overlayHandle,
   controllerState,
   hit,
+  { trigger = true } = {},
 ) {
   try {
-    handleTriggerInput(controllerId, controllerState, hit);
+    if (trigger) {
+      handleTriggerInput(controllerId, controllerState, hit);
+    }
     processGripDrag(
       controllerId,
       poseMatrix,
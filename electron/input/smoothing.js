@@ -78,3 +78,42 @@ export class PointerStabilizer {
     this.lastTime = 0;
   }
 }
+
+/**
+ * Same interface as PointerStabilizer, backed by the native per-controller filter
+ * PointerStabilizer と同じインターフェースで、ネイティブのコントローラー別フィルターを使う
+ */
+export class NativePointerStabilizer {
+  constructor(overlayManager, controllerId) {
+    this.overlayManager = overlayManager;
+    this.controllerId = controllerId;
+  }
+
+  update(x, y, timestamp) {
+    const [sx, sy] = this.overlayManager.stabilizePointer(
+      this.controllerId,
+      x,
+      y,
+      timestamp,
+    );
+    return { x: sx, y: sy };
+  }
+
+  reset() {
+    this.overlayManager.resetPointerStabilizer(this.controllerId);
+  }
+}
+
+/**
+ * Prefer the native filter when the loaded addon provides it
+ * 読み込んだアドオンが提供していればネイティブのフィルターを優先する
+ */
+export function createPointerStabilizer(overlayManager, controllerId) {
+  if (typeof overlayManager?.stabilizePointer === 'function') {
+    return new NativePointerStabilizer(overlayManager, controllerId);
+  }
+  // Parameters (minCutoff, beta, dcutoff) need tuning.
+  // minCutoff=0.1: Very strong smoothing at low speed
+  // beta=5.0: Quick response at high speed
+  return new PointerStabilizer(0.1, 5.0, 1.0);
+}
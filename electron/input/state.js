@@ -8,6 +8,12 @@ export const state = {
   captureSyncUnsubscribe: null,
   inputInProgress: false,
   lastCaptureFrameAt: 0,
+  // Overlay the native input poller targets, null while it is not running
+  // ネイティブ入力ポーラーの対象オーバーレイ。動作していない間は null
+  inputPollerHandle: null,
+  // Last arbitration decision per controller, applied to poller events between ticks
+  // コントローラーごとの直近の調停結果。ティックの間に届くポーラーのイベントに適用する
+  lastArbitration: {},
   lastCursorHitState: {},
   lastMouseHit: false,
   lastMouseControllerId: null,
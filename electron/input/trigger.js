@@ -1,4 +1,7 @@
 import {
+  GESTURE_PRESS,
+  GESTURE_REPEAT,
+  GESTURE_SCROLL,
   TRIGGER_CLICK_CANCEL_THRESHOLD,
   TRIGGER_DRAG_THRESHOLD,
   TRIGGER_SCROLL_MAX,
@@ -7,16 +10,66 @@ import {
 import { sendClickEvent, sendScrollEvent } from './events.js';
 import { state } from './state.js';
 
+function sendClick(u, v) {
+  sendClickEvent(u, v, 'mouseDown');
+  sendClickEvent(u, v, 'mouseUp', 1);
+}
+
+function sendScrollForDelta(deltaV) {
+  const height = state.windowSize.height > 0 ? state.windowSize.height : 700;
+  const rawDelta = deltaV * height * TRIGGER_SCROLL_MULTIPLIER;
+  const clamped = Math.max(
+    -TRIGGER_SCROLL_MAX,
+    Math.min(TRIGGER_SCROLL_MAX, rawDelta),
+  );
+  if (clamped !== 0) {
+    sendScrollEvent(clamped);
+  }
+}
+
+function hasNativeGestures(manager) {
+  return !!manager && typeof manager.updateTriggerGesture === 'function';
+}
+
+/**
+ * Act on gestures recognized by the native trigger state machine
+ * ネイティブのトリガーステートマシンが認識したジェスチャーを処理する
+ */
+function handleNativeGestures(events) {
+  for (const event of events) {
+    switch (event.eventType) {
+      case GESTURE_PRESS:
+      case GESTURE_REPEAT:
+        sendClick(event.u, event.v);
+        break;
+      case GESTURE_SCROLL:
+        sendScrollForDelta(event.scrollDelta);
+        break;
+      default:
+        break;
+    }
+  }
+}
+
 export function handleTriggerInput(controllerId, controllerState, hit) {
   if (!controllerState) return;
   const pressed = !!controllerState.triggerPressed;
+  if (hasNativeGestures(state.overlayManager)) {
+    handleNativeGestures(
+      state.overlayManager.updateTriggerGesture(
+        controllerId,
+        pressed,
+        hit ?? null,
+      ),
+    );
+    return;
+  }
   const existing = state.triggerDragState[controllerId];
 
   if (pressed) {
     if (!existing) {
       if (!hit) return;
-      sendClickEvent(hit.u, hit.v, 'mouseDown');
-      sendClickEvent(hit.u, hit.v, 'mouseUp', 1);
+      sendClick(hit.u, hit.v);
       state.triggerDragState[controllerId] = {
         startU: hit.u,
         startV: hit.v,
@@ -46,15 +99,7 @@ export function handleTriggerInput(controllerId, controllerState, hit) {
       existing.moved = true;
     }
     if (existing.dragging) {
-      const height = state.windowSize.height > 0 ? state.windowSize.height : 700;
-      const rawDelta = deltaV * height * TRIGGER_SCROLL_MULTIPLIER;
-      const clamped = Math.max(
-        -TRIGGER_SCROLL_MAX,
-        Math.min(TRIGGER_SCROLL_MAX, rawDelta),
-      );
-      if (clamped !== 0) {
-        sendScrollEvent(clamped);
-      }
+      sendScrollForDelta(deltaV);
     }
     existing.lastU = hit.u;
     existing.lastV = hit.v;
@@ -71,4 +116,7 @@ export function releaseTriggerForController(
   _clickCountOverride = null,
 ) {
   delete state.triggerDragState[controllerId];
+  if (hasNativeGestures(state.overlayManager)) {
+    state.overlayManager.resetTriggerGesture(controllerId);
+  }
 }
@@ -125,3 +125,49 @@ describe('handleTriggerInput', () => {
     expect(state.triggerDragState[7]).toBeUndefined();
   });
 });
+
+describe('handleTriggerInput with native gestures', () => {
+  beforeEach(() => {
+    vi.resetModules();
+    vi.clearAllMocks();
+  });
+
+  it('clicks on Press and Repeat and scrolls on Scroll from updateTriggerGesture', async () => {
+    const { handleTriggerInput, releaseTriggerForController } = await import('./trigger.js');
+    const { state } = await import('./state.js');
+    const { sendClickEvent, sendScrollEvent } = await import('./events.js');
+    const { GESTURE_PRESS, GESTURE_REPEAT, GESTURE_SCROLL } = await import('./constants.js');
+    state.windowSize.height = 1000;
+
+    const updateTriggerGesture = vi.fn(() => [
+      { eventType: GESTURE_PRESS, u: 0.1, v: 0.2, scrollDelta: 0 },
+      { eventType: GESTURE_REPEAT, u: 0.3, v: 0.4, scrollDelta: 0 },
+      { eventType: GESTURE_SCROLL, u: 0.3, v: 0.4, scrollDelta: 0.25 },
+      // Other gesture types are not acted on / 他のジェスチャー種別は処理しない
+      { eventType: 1, u: 0.3, v: 0.4, scrollDelta: 0 },
+    ]);
+    const resetTriggerGesture = vi.fn();
+    state.overlayManager = { updateTriggerGesture, resetTriggerGesture };
+
+    handleTriggerInput(8, { triggerPressed: true }, { u: 0.1, v: 0.2 });
+
+    expect(updateTriggerGesture).toHaveBeenCalledWith(8, true, { u: 0.1, v: 0.2 });
+    expect(sendClickEvent).toHaveBeenCalledTimes(4);
+    expect(sendClickEvent).toHaveBeenNthCalledWith(1, 0.1, 0.2, 'mouseDown');
+    expect(sendClickEvent).toHaveBeenNthCalledWith(2, 0.1, 0.2, 'mouseUp', 1);
+    expect(sendClickEvent).toHaveBeenNthCalledWith(3, 0.3, 0.4, 'mouseDown');
+    expect(sendClickEvent).toHaveBeenNthCalledWith(4, 0.3, 0.4, 'mouseUp', 1);
+    // 0.25 * 1000 px * TRIGGER_SCROLL_MULTIPLIER (0.5)
+    expect(sendScrollEvent).toHaveBeenCalledTimes(1);
+    expect(sendScrollEvent).toHaveBeenCalledWith(125);
+    expect(state.triggerDragState[8]).toBeUndefined();
+
+    updateTriggerGesture.mockReturnValue([]);
+    handleTriggerInput(8, { triggerPressed: false }, null);
+    expect(updateTriggerGesture).toHaveBeenLastCalledWith(8, false, null);
+    expect(sendClickEvent).toHaveBeenCalledTimes(4);
+
+    releaseTriggerForController(8, 0);
+    expect(resetTriggerGesture).toHaveBeenCalledWith(8);
+  });
+});
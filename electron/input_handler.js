@@ -3,20 +3,40 @@ import {
   getActiveOverlayHandle,
   getOverlayManager,
 } from './overlay.js';
-import { CURSOR_MOVE_EPSILON } from './input/constants.js';
+import {
+  CONTROLLER_ROLE_LEFT_HAND,
+  CONTROLLER_ROLE_RIGHT_HAND,
+  CURSOR_MOVE_EPSILON,
+  DEVICE_EVENT_DEACTIVATED,
+  DEVICE_EVENT_ROLE_CHANGED,
+  INPUT_EVENT_HOVER_END,
+  INPUT_EVENT_HOVER_MOVE,
+  INPUT_EVENT_PRESS,
+  INPUT_EVENT_RELEASE,
+  INPUT_EVENT_SCROLL,
+  INPUT_HAND_LEFT,
+  INPUT_HAND_RIGHT,
+  MAX_POLLER_RATE_HZ,
+  MIN_POLLER_RATE_HZ,
+  POLLER_SCROLL_SPEED,
+} from './input/constants.js';
 import {
   sendCursorEvent,
   sendCursorHideEvent,
   sendMouseEnterEvent,
   sendMouseLeaveEvent,
   sendMouseMoveEvent,
+  sendScrollEvent,
   sendTriggerStateEvent,
 } from './input/events.js';
 import { computeHitFromPose, processController } from './input/controllers.js';
 import { endDrag } from './input/drag.js';
 import { state } from './input/state.js';
-import { PointerStabilizer } from './input/smoothing.js';
-import { releaseTriggerForController } from './input/trigger.js';
+import { createPointerStabilizer } from './input/smoothing.js';
+import {
+  handleTriggerInput,
+  releaseTriggerForController,
+} from './input/trigger.js';
 
 export { updateWindowSize } from './input/mapping.js';
 
@@ -36,60 +56,216 @@ export function startInputLoop(fps = 120, webContents = null, options = {}) {
 
   stopInputLoop();
 
+  const fallbackFps = Number.isFinite(options.fallbackFps)
+    ? Math.max(1, options.fallbackFps)
+    : Math.max(1, Math.min(30, fps));
+
+  // The loop below keeps running for arbitration and the grip drag, which need
+  // SteamVR Input on this thread / 調停とグリップドラッグはこのスレッドの SteamVR Input を
+  // 必要とするため、下のループは動かし続ける
+  if (startNativePoller(fps)) {
+    console.log(
+      'Pointer, trigger and scroll events from the native input poller',
+    );
+  }
+
   const syncWithCapture = options.syncWithCapture !== false;
   if (syncWithCapture && typeof addCaptureFrameListener === 'function') {
     state.captureSyncUnsubscribe = addCaptureFrameListener(() => {
       state.lastCaptureFrameAt = Date.now();
-      if (state.inputInProgress) return;
-      state.inputInProgress = true;
-      try {
-        updateInput();
-      } finally {
-        state.inputInProgress = false;
-      }
+      runInputUpdate();
     });
     console.log('Input loop synced to capture frames');
 
-    const fallbackFps = Number.isFinite(options.fallbackFps)
-      ? Math.max(1, options.fallbackFps)
-      : Math.max(1, Math.min(30, fps));
-    const fallbackIntervalMs = Math.floor(1000 / fallbackFps);
     state.lastCaptureFrameAt = Date.now();
-    state.inputFallbackInterval = setInterval(() => {
-      const now = Date.now();
-      if (now - state.lastCaptureFrameAt < fallbackIntervalMs * 2) {
-        return;
-      }
-      if (state.inputInProgress) return;
-      state.inputInProgress = true;
-      try {
-        updateInput();
-      } finally {
-        state.inputInProgress = false;
-      }
-    }, fallbackIntervalMs);
-
+    state.inputFallbackInterval = startFallbackInterval(
+      fallbackFps,
+      () => state.lastCaptureFrameAt,
+    );
     return;
   }
 
   const intervalMs = Math.floor(1000 / fps);
   console.log(`Starting input loop at ${fps} FPS`);
 
-  state.inputInterval = setInterval(() => {
-    if (state.inputInProgress) return;
-    state.inputInProgress = true;
-    try {
-      updateInput();
-    } finally {
-      state.inputInProgress = false;
+  state.inputInterval = setInterval(runInputUpdate, intervalMs);
+}
+
+/**
+ * Run one input update unless one is already in progress
+ * 実行中の更新がなければ入力の更新を1回行う
+ */
+function runInputUpdate() {
+  if (state.inputInProgress) return;
+  state.inputInProgress = true;
+  try {
+    updateInput();
+  } finally {
+    state.inputInProgress = false;
+  }
+}
+
+/**
+ * Keep input alive at `fallbackFps` while the primary driver is silent
+ * 主な駆動源が止まっている間、`fallbackFps` で入力を動かし続ける
+ */
+function startFallbackInterval(fallbackFps, lastDrivenAt) {
+  const fallbackIntervalMs = Math.floor(1000 / fallbackFps);
+  return setInterval(() => {
+    if (Date.now() - lastDrivenAt() < fallbackIntervalMs * 2) {
+      return;
     }
-  }, intervalMs);
+    runInputUpdate();
+  }, fallbackIntervalMs);
+}
+
+/**
+ * Deliver pointer, trigger and scroll events from the native poller, which arrive off
+ * the renderer's frame rate; false when the addon has no poller or there is no overlay yet
+ * ネイティブポーラーからポインター・トリガー・スクロールのイベントを受け取る。イベントは
+ * レンダラーのフレームレートに依存しない。アドオンにポーラーが無い、またはオーバーレイがまだ無い場合は false
+ */
+function startNativePoller(fps) {
+  const manager = state.overlayManager;
+  if (typeof manager.startInputPoller !== 'function') return false;
+  const overlayHandle = getActiveOverlayHandle();
+  if (!overlayHandle) return false;
+  try {
+    manager.startInputPoller(
+      {
+        overlayHandle,
+        rateHz: Math.min(
+          MAX_POLLER_RATE_HZ,
+          Math.max(MIN_POLLER_RATE_HZ, Math.round(fps)),
+        ),
+        scrollSpeed: POLLER_SCROLL_SPEED,
+      },
+      handlePollerEvents,
+    );
+  } catch (e) {
+    console.error('Failed to start native input poller:', e);
+    return false;
+  }
+  state.inputPollerHandle = overlayHandle;
+  return true;
+}
+
+/**
+ * Dispatch one batch of native poller events. The poller already smooths the pointer;
+ * controllers hidden by the last arbitration neither point, click nor scroll.
+ * ネイティブポーラーのイベント1バッチを処理する。ポインターはポーラー側で平滑化済み。
+ * 直近の調停で非表示になったコントローラーはポイント・クリック・スクロールしない。
+ */
+function handlePollerEvents(events) {
+  if (!state.inputPollerHandle) return;
+  try {
+    let hoverChanged = false;
+    for (const event of events) {
+      const id = event.controllerIndex;
+      const decision = state.lastArbitration[id];
+      const pointerVisible = !decision || decision.pointerVisible;
+      switch (event.eventType) {
+        case INPUT_EVENT_HOVER_MOVE:
+          if (!pointerVisible) break;
+          applyPollerHover(id, event);
+          hoverChanged = true;
+          break;
+        case INPUT_EVENT_HOVER_END:
+          if (state.lastCursorHitState[id]) {
+            hideCursor(id);
+            hoverChanged = true;
+          }
+          break;
+        case INPUT_EVENT_PRESS:
+        case INPUT_EVENT_RELEASE: {
+          const pressed = event.eventType === INPUT_EVENT_PRESS;
+          // A release only follows a delivered press / 解放は配信済みの押下の後だけ送る
+          if (!!state.lastTriggerPressedState[id] === pressed) break;
+          if (pressed && !pointerVisible) break;
+          sendTriggerStateEvent(id, pressed);
+          state.lastTriggerPressedState[id] = pressed;
+          handleTriggerInput(
+            id,
+            { triggerPressed: pressed },
+            state.lastHitByController[id] ?? null,
+          );
+          break;
+        }
+        case INPUT_EVENT_SCROLL:
+          // Native deltas are positive upward, DOM deltas downward
+          // ネイティブの量は上が正、DOM の量は下が正
+          if (pointerVisible && event.scrollDelta !== 0) {
+            sendScrollEvent(-event.scrollDelta);
+          }
+          break;
+        default:
+          break;
+      }
+    }
+    if (hoverChanged) {
+      updateMouseHover(pollerHitCandidates());
+    }
+  } catch (error) {
+    // Suppress errors during shutdown
+    if (!error.message?.includes('destroyed')) {
+      console.error('Input poller event error:', error);
+    }
+  }
+}
+
+function applyPollerHover(id, event) {
+  const hit = { u: event.u, v: event.v };
+  const previous = state.lastHitByController[id];
+  if (
+    !previous ||
+    Math.abs(hit.u - previous.u) > CURSOR_MOVE_EPSILON ||
+    Math.abs(hit.v - previous.v) > CURSOR_MOVE_EPSILON
+  ) {
+    state.lastMoveAtByController[id] = event.timestampMs;
+  }
+  state.lastHitByController[id] = hit;
+  state.lastCursorHitState[id] = true;
+  sendCursorEvent(id, hit.u, hit.v);
+  // Drag-scroll while the trigger is held / トリガー押下中はドラッグでスクロールする
+  if (state.lastTriggerPressedState[id]) {
+    handleTriggerInput(id, { triggerPressed: true }, hit);
+  }
+}
+
+function pollerHitCandidates() {
+  return Object.entries(state.lastHitByController)
+    .filter(([key]) => state.lastCursorHitState[key])
+    .map(([key, hit]) => ({ controllerId: Number(key), u: hit.u, v: hit.v }));
+}
+
+/**
+ * Follow the active overlay when the poller is running / ポーラー動作中はアクティブなオーバーレイに追従する
+ */
+function retargetNativePoller(activeHandle) {
+  if (!state.inputPollerHandle || state.inputPollerHandle === activeHandle) {
+    return;
+  }
+  if (typeof state.overlayManager.setInputPollerTarget !== 'function') return;
+  try {
+    state.overlayManager.setInputPollerTarget(activeHandle);
+    state.inputPollerHandle = activeHandle;
+  } catch (e) {
+    console.error('Failed to retarget native input poller:', e);
+  }
 }
 
 /**
  * Stop the input handling loop
  */
 export function stopInputLoop() {
+  if (state.inputPollerHandle) {
+    try {
+      state.overlayManager?.stopInputPoller?.();
+    } catch (e) {
+      console.error('Failed to stop native input poller:', e);
+    }
+    state.inputPollerHandle = null;
+  }
   if (state.inputInterval) {
     clearInterval(state.inputInterval);
     state.inputInterval = null;
@@ -118,8 +294,12 @@ export function stopInputLoop() {
     if (!Number.isFinite(controllerId)) continue;
     cleanupControllerRuntimeState(controllerId);
   }
+  if (typeof state.overlayManager?.resetPointerArbitration === 'function') {
+    state.overlayManager.resetPointerArbitration();
+  }
   state.inputInProgress = false;
   state.lastCaptureFrameAt = 0;
+  state.lastArbitration = {};
   state.lastCursorHitState = {};
   state.lastHitByController = {};
   state.lastMoveAtByController = {};
@@ -140,15 +320,20 @@ function updateInput() {
   if (!state.overlayManager) return;
 
   try {
+    handleDeviceEvents();
+
     const activeHandle = getActiveOverlayHandle();
     if (!activeHandle) return;
+    retargetNativePoller(activeHandle);
 
     // 1. Get active controllers
     const controllerIds = state.overlayManager.getControllerIds();
     const hitCandidates = [];
     const observedControllerIds = new Set();
+    const aimHands = resolveAimHands();
     const now = Date.now();
-    // 2. Process each controller
+    // 2. Read each controller
+    const readings = [];
     for (const id of controllerIds) {
       if (id === 0) continue; // Skip HMD
       observedControllerIds.add(id);
@@ -165,6 +350,32 @@ function updateInput() {
         cleanupControllerRuntimeState(id);
         continue;
       }
+      readings.push({ id, poseData, controllerState });
+    }
+
+    // 3. Decide which controller may point, click and drag
+    const arbitration = arbitrateControllers(readings);
+    const pollerDriven = !!state.inputPollerHandle;
+    for (const { id, poseData, controllerState: rawState } of readings) {
+      const decision = arbitration.get(id);
+      state.lastArbitration[id] = decision ?? null;
+      const controllerState = decision
+        ? {
+            ...rawState,
+            triggerPressed: decision.triggerPressed,
+            gripPressed: decision.gripPressed,
+          }
+        : rawState;
+      if (pollerDriven) {
+        // The poller delivers the cursor and trigger; only the grip drag runs here
+        // カーソルとトリガーはポーラーが配信する。ここではグリップドラッグのみ行う
+        updatePollerController(id, poseData, activeHandle, controllerState, {
+          decision,
+          aimHand: aimHands[id],
+          hitCandidates,
+        });
+        continue;
+      }
       const pressedNow = !!controllerState.triggerPressed;
       if (state.lastTriggerPressedState[id] !== pressedNow) {
         sendTriggerStateEvent(id, pressedNow);
@@ -172,16 +383,19 @@ function updateInput() {
       }
       // Use absolute tracking pose directly with ComputeOverlayIntersection
       // ComputeOverlayIntersectionは絶対座標を受け取るため、変換不要
-      const hit = computeHitFromPose(poseData, activeHandle);
+      const hit =
+        decision && !decision.pointerVisible
+          ? null
+          : computeHitFromPose(poseData, activeHandle, aimHands[id]);
       processController(id, poseData, activeHandle, controllerState, hit);
       if (hit) {
         // --- Smoothing Logic Start ---
         if (!state.inputSmoothers[id]) {
           // Initialize smoothing filter for this controller
-          // Parameters (minCutoff, beta, dcutoff) need tuning.
-          // minCutoff=0.1: Very strong smoothing at low speed
-          // beta=5.0: Quick response at high speed
-          state.inputSmoothers[id] = new PointerStabilizer(0.1, 5.0, 1.0);
+          state.inputSmoothers[id] = createPointerStabilizer(
+            state.overlayManager,
+            id,
+          );
         }
         const smoothed = state.inputSmoothers[id].update(hit.u, hit.v, now);
         // Use smoothed coordinates for cursor events
@@ -200,71 +414,190 @@ function updateInput() {
         state.lastHitByController[id] = { u: hit.u, v: hit.v };
         state.lastCursorHitState[id] = true;
       } else if (state.lastCursorHitState[id]) {
-        sendCursorHideEvent(id);
-        state.lastCursorHitState[id] = false;
-        delete state.lastHitByController[id];
-        delete state.lastMoveAtByController[id];
-        // Reset smoother when invalid
-        if (state.inputSmoothers[id]) {
-          state.inputSmoothers[id].reset();
-        }
+        hideCursor(id);
       }
     }
     cleanupStaleControllers(observedControllerIds);
+    updateMouseHover(hitCandidates);
+  } catch (error) {
+    // Suppress errors during shutdown
+    if (!error.message?.includes('destroyed')) {
+      console.error('Input update error:', error);
+    }
+  }
+}
 
-    const multiCursor = hitCandidates.length > 1;
-    if (multiCursor) {
-      if (!state.suppressMouseHover) {
-        if (state.lastMouseHit) {
-          sendMouseLeaveEvent(state.lastMousePosition);
-        }
-        state.lastMouseHit = false;
-        state.lastMouseControllerId = null;
-        state.suppressMouseHover = true;
-      }
-      return;
+/**
+ * Grip drag and mouse candidate of a controller whose cursor and trigger come from the
+ * native poller / カーソルとトリガーをネイティブポーラーから受け取るコントローラーの
+ * グリップドラッグとマウス候補
+ */
+function updatePollerController(
+  id,
+  poseData,
+  activeHandle,
+  controllerState,
+  { decision, aimHand, hitCandidates },
+) {
+  if (decision && !decision.pointerVisible) {
+    if (state.lastCursorHitState[id]) {
+      hideCursor(id);
     }
+    processController(id, poseData, activeHandle, controllerState, null, {
+      trigger: false,
+    });
+    return;
+  }
+  const hit = controllerState.gripPressed
+    ? computeHitFromPose(poseData, activeHandle, aimHand)
+    : null;
+  processController(id, poseData, activeHandle, controllerState, hit, {
+    trigger: false,
+  });
+  const pollerHit = state.lastCursorHitState[id]
+    ? state.lastHitByController[id]
+    : null;
+  if (pollerHit) {
+    hitCandidates.push({ controllerId: id, u: pollerHit.u, v: pollerHit.v });
+  }
+}
+
+function hideCursor(id) {
+  sendCursorHideEvent(id);
+  state.lastCursorHitState[id] = false;
+  delete state.lastHitByController[id];
+  delete state.lastMoveAtByController[id];
+  // Reset smoother when invalid
+  if (state.inputSmoothers[id]) {
+    state.inputSmoothers[id].reset();
+  }
+}
 
-    if (state.suppressMouseHover) {
-      state.suppressMouseHover = false;
+/**
+ * Move the desktop mouse with the most recently moved cursor; hidden while several
+ * cursors are on the overlay / 最後に動いたカーソルでデスクトップのマウスを動かす。
+ * 複数のカーソルがオーバーレイ上にある間は隠す
+ */
+function updateMouseHover(hitCandidates) {
+  const multiCursor = hitCandidates.length > 1;
+  if (multiCursor) {
+    if (!state.suppressMouseHover) {
+      if (state.lastMouseHit) {
+        sendMouseLeaveEvent(state.lastMousePosition);
+      }
+      state.lastMouseHit = false;
+      state.lastMouseControllerId = null;
+      state.suppressMouseHover = true;
     }
+    return;
+  }
 
-    if (hitCandidates.length > 0) {
-      let primary = null;
-      let latestMoveAt = -1;
-      for (const candidate of hitCandidates) {
-        const movedAt =
-          state.lastMoveAtByController[candidate.controllerId] ?? 0;
-        if (movedAt > latestMoveAt) {
-          latestMoveAt = movedAt;
-          primary = candidate;
-        }
+  if (state.suppressMouseHover) {
+    state.suppressMouseHover = false;
+  }
+
+  if (hitCandidates.length > 0) {
+    let primary = null;
+    let latestMoveAt = -1;
+    for (const candidate of hitCandidates) {
+      const movedAt =
+        state.lastMoveAtByController[candidate.controllerId] ?? 0;
+      if (movedAt > latestMoveAt) {
+        latestMoveAt = movedAt;
+        primary = candidate;
       }
-      if (!primary) {
-        primary =
-          hitCandidates.find(
-            (candidate) =>
-              candidate.controllerId === state.lastMouseControllerId,
-          ) ?? hitCandidates[0];
+    }
+    if (!primary) {
+      primary =
+        hitCandidates.find(
+          (candidate) =>
+            candidate.controllerId === state.lastMouseControllerId,
+        ) ?? hitCandidates[0];
+    }
+    const movePosition = sendMouseMoveEvent(primary.u, primary.v);
+    if (movePosition) {
+      if (!state.lastMouseHit) {
+        sendMouseEnterEvent(movePosition);
       }
-      const movePosition = sendMouseMoveEvent(primary.u, primary.v);
-      if (movePosition) {
-        if (!state.lastMouseHit) {
-          sendMouseEnterEvent(movePosition);
-        }
-        state.lastMousePosition = movePosition;
-        state.lastMouseHit = true;
-        state.lastMouseControllerId = primary.controllerId;
+      state.lastMousePosition = movePosition;
+      state.lastMouseHit = true;
+      state.lastMouseControllerId = primary.controllerId;
+    }
+  } else if (state.lastMouseHit) {
+    sendMouseLeaveEvent(state.lastMousePosition);
+    state.lastMouseHit = false;
+    state.lastMouseControllerId = null;
+  }
+}
+
+/**
+ * Gate trigger and grip through the native focus arbitration so only one hand drags and
+ * the off hand clicks only when the policy allows it; empty on older native builds
+ * ネイティブのフォーカス調停を通してトリガーとグリップを制御する。ドラッグは片手のみで、
+ * 利き手でない方のクリックはポリシーが許す場合のみ。古いネイティブビルドでは空
+ */
+function arbitrateControllers(readings) {
+  const decisions = new Map();
+  const manager = state.overlayManager;
+  if (typeof manager.arbitrateControllers !== 'function') {
+    return decisions;
+  }
+  try {
+    const results = manager.arbitrateControllers(
+      readings.map(({ id, controllerState }) => ({
+        controllerIndex: id,
+        triggerPressed: !!controllerState.triggerPressed,
+        gripPressed: !!controllerState.gripPressed,
+      })),
+    );
+    for (const decision of results) {
+      decisions.set(decision.controllerIndex, decision);
+    }
+  } catch (e) {
+    console.error('Controller arbitration failed:', e);
+  }
+  return decisions;
+}
+
+/**
+ * Map controller index to the hand whose aim pose action it drives
+ * コントローラーインデックスを、エイムポーズアクションを担う手に対応付ける
+ */
+function resolveAimHands() {
+  const aimHands = {};
+  const manager = state.overlayManager;
+  if (typeof manager.getTrackedDeviceIndexForRole !== 'function') {
+    return aimHands;
+  }
+  for (const [role, hand] of [
+    [CONTROLLER_ROLE_LEFT_HAND, INPUT_HAND_LEFT],
+    [CONTROLLER_ROLE_RIGHT_HAND, INPUT_HAND_RIGHT],
+  ]) {
+    try {
+      const index = manager.getTrackedDeviceIndexForRole(role);
+      if (index !== null && index !== undefined) {
+        aimHands[index] = hand;
       }
-    } else if (state.lastMouseHit) {
-      sendMouseLeaveEvent(state.lastMousePosition);
-      state.lastMouseHit = false;
-      state.lastMouseControllerId = null;
+    } catch (e) {
+      console.error('Failed to resolve controller role:', e);
     }
-  } catch (error) {
-    // Suppress errors during shutdown
-    if (!error.message?.includes('destroyed')) {
-      console.error('Input update error:', error);
+  }
+  return aimHands;
+}
+
+/**
+ * Drop per-controller state as soon as SteamVR reports a disconnect or role change
+ * SteamVR が切断やロール変更を通知したら即座にコントローラーごとの状態を破棄する
+ */
+function handleDeviceEvents() {
+  if (typeof state.overlayManager.pollDeviceEvents !== 'function') return;
+  const events = state.overlayManager.pollDeviceEvents();
+  for (const event of events) {
+    if (
+      event.eventType === DEVICE_EVENT_DEACTIVATED ||
+      event.eventType === DEVICE_EVENT_ROLE_CHANGED
+    ) {
+      cleanupControllerRuntimeState(event.deviceIndex);
     }
   }
 }
@@ -306,4 +639,5 @@ function cleanupControllerRuntimeState(controllerId) {
   delete state.lastHitByController[controllerId];
   delete state.lastMoveAtByController[controllerId];
   delete state.lastTriggerPressedState[controllerId];
+  delete state.lastArbitration[controllerId];
 }
@@ -176,4 +176,193 @@ describe('input_handler cleanup behavior', () => {
 
     stopInputLoop();
   });
+
+  it('gates trigger, grip and laser through native arbitration', async () => {
+    const { startInputLoop, stopInputLoop } = await import('./input_handler.js');
+    const { computeHitFromPose, processController } = await import('./input/controllers.js');
+    const { sendTriggerStateEvent } = await import('./input/events.js');
+
+    const arbitrateControllers = vi.fn(() => [
+      {
+        controllerIndex: 1,
+        role: 0,
+        pointerVisible: true,
+        triggerPressed: true,
+        gripPressed: false,
+        tookFocus: true,
+      },
+      {
+        controllerIndex: 2,
+        role: 2,
+        pointerVisible: false,
+        triggerPressed: false,
+        gripPressed: false,
+        tookFocus: false,
+      },
+    ]);
+    Object.assign(overlayManagerMock, { arbitrateControllers });
+    overlayManagerMock.getControllerIds.mockReturnValue([1, 2]);
+    overlayManagerMock.getControllerPose.mockReturnValue([
+      1, 0, 0, 0,
+      0, 1, 0, 0,
+      0, 0, 1, 0,
+      0, 0, 0, 1,
+    ]);
+    overlayManagerMock.getControllerState.mockReturnValue({
+      triggerPressed: true,
+      gripPressed: true,
+    });
+    vi.mocked(computeHitFromPose).mockReturnValue({ u: 0.2, v: 0.3 });
+
+    startInputLoop(120, {} as Electron.WebContents);
+    captureFrameListener?.();
+
+    expect(arbitrateControllers).toHaveBeenCalledWith([
+      { controllerIndex: 1, triggerPressed: true, gripPressed: true },
+      { controllerIndex: 2, triggerPressed: true, gripPressed: true },
+    ]);
+    expect(computeHitFromPose).toHaveBeenCalledTimes(1);
+    expect(sendTriggerStateEvent).toHaveBeenCalledWith(1, true);
+    expect(sendTriggerStateEvent).toHaveBeenCalledWith(2, false);
+    expect(processController).toHaveBeenCalledWith(
+      2,
+      expect.anything(),
+      100,
+      expect.objectContaining({ triggerPressed: false, gripPressed: false }),
+      null,
+    );
+
+    stopInputLoop();
+    delete (overlayManagerMock as Record<string, unknown>).arbitrateControllers;
+  });
+
+  it('dispatches native poller events directly while the loop keeps arbitrating', async () => {
+    const { state } = await import('./input/state.js');
+    const { startInputLoop, stopInputLoop } = await import('./input_handler.js');
+    const { getActiveOverlayHandle } = await import('./overlay.js');
+    const { computeHitFromPose, processController } = await import('./input/controllers.js');
+    const {
+      sendClickEvent,
+      sendCursorEvent,
+      sendCursorHideEvent,
+      sendMouseMoveEvent,
+      sendScrollEvent,
+      sendTriggerStateEvent,
+    } = await import('./input/events.js');
+
+    let pollerCallback: ((events: unknown[]) => void) | null = null;
+    const startInputPoller = vi.fn(
+      (_options: unknown, callback: (events: unknown[]) => void) => {
+        pollerCallback = callback;
+      },
+    );
+    const setInputPollerTarget = vi.fn();
+    const stopInputPoller = vi.fn();
+    const arbitrateControllers = vi.fn(() => [
+      {
+        controllerIndex: 1,
+        role: 0,
+        pointerVisible: true,
+        triggerPressed: false,
+        gripPressed: false,
+        tookFocus: false,
+      },
+      {
+        controllerIndex: 2,
+        role: 2,
+        pointerVisible: false,
+        triggerPressed: false,
+        gripPressed: false,
+        tookFocus: false,
+      },
+    ]);
+    Object.assign(overlayManagerMock, {
+      startInputPoller,
+      setInputPollerTarget,
+      stopInputPoller,
+      arbitrateControllers,
+    });
+    overlayManagerMock.getControllerIds.mockReturnValue([1, 2]);
+    overlayManagerMock.getControllerPose.mockReturnValue([
+      1, 0, 0, 0,
+      0, 1, 0, 0,
+      0, 0, 1, 0,
+      0, 0, 0, 1,
+    ]);
+    overlayManagerMock.getControllerState.mockReturnValue({
+      triggerPressed: false,
+      gripPressed: false,
+    });
+    vi.mocked(computeHitFromPose).mockReturnValue({ u: 0.2, v: 0.3 });
+
+    startInputLoop(120, {} as Electron.WebContents);
+
+    expect(startInputPoller).toHaveBeenCalledWith(
+      { overlayHandle: 100, rateHz: 120, scrollSpeed: 1200 },
+      expect.any(Function),
+    );
+    expect(state.inputPollerHandle).toBe(100);
+    // The frame loop keeps running for arbitration and the grip drag
+    expect(captureFrameListener).not.toBeNull();
+    captureFrameListener?.();
+    expect(arbitrateControllers).toHaveBeenCalledTimes(1);
+    expect(computeHitFromPose).not.toHaveBeenCalled();
+    expect(sendCursorEvent).not.toHaveBeenCalled();
+    expect(sendTriggerStateEvent).not.toHaveBeenCalled();
+    expect(processController).toHaveBeenCalledWith(
+      1,
+      expect.anything(),
+      100,
+      expect.objectContaining({ triggerPressed: false }),
+      null,
+      { trigger: false },
+    );
+
+    const event = (eventType: number, controllerIndex: number, scrollDelta = 0) => ({
+      eventType,
+      controllerIndex,
+      u: 0.4,
+      v: 0.6,
+      scrollDelta,
+      timestampMs: 1000,
+    });
+    pollerCallback?.([
+      event(0, 1),
+      event(0, 2),
+      event(2, 1),
+      event(2, 2),
+      event(4, 1, 30),
+      event(4, 2, 30),
+    ]);
+    // Controller 2 is hidden by the last arbitration
+    expect(sendCursorEvent).toHaveBeenCalledTimes(1);
+    expect(sendCursorEvent).toHaveBeenCalledWith(1, 0.4, 0.6);
+    expect(state.lastHitByController[1]).toEqual({ u: 0.4, v: 0.6 });
+    expect(sendMouseMoveEvent).toHaveBeenCalledWith(0.4, 0.6);
+    expect(sendTriggerStateEvent).toHaveBeenCalledTimes(1);
+    expect(sendTriggerStateEvent).toHaveBeenCalledWith(1, true);
+    expect(sendClickEvent).toHaveBeenCalledTimes(2);
+    expect(sendScrollEvent).toHaveBeenCalledTimes(1);
+    expect(sendScrollEvent).toHaveBeenCalledWith(-30);
+
+    pollerCallback?.([event(3, 1), event(3, 2), event(1, 1)]);
+    expect(sendTriggerStateEvent).toHaveBeenCalledTimes(2);
+    expect(sendTriggerStateEvent).toHaveBeenLastCalledWith(1, false);
+    expect(sendCursorHideEvent).toHaveBeenCalledWith(1);
+    expect(state.lastCursorHitState[1]).toBe(false);
+
+    vi.mocked(getActiveOverlayHandle).mockReturnValue(200);
+    captureFrameListener?.();
+    expect(setInputPollerTarget).toHaveBeenCalledWith(200);
+    expect(state.inputPollerHandle).toBe(200);
+
+    stopInputLoop();
+    expect(stopInputPoller).toHaveBeenCalledTimes(1);
+    expect(state.inputPollerHandle).toBeNull();
+    vi.mocked(getActiveOverlayHandle).mockReturnValue(100);
+    delete (overlayManagerMock as Record<string, unknown>).startInputPoller;
+    delete (overlayManagerMock as Record<string, unknown>).setInputPollerTarget;
+    delete (overlayManagerMock as Record<string, unknown>).stopInputPoller;
+    delete (overlayManagerMock as Record<string, unknown>).arbitrateControllers;
+  });
 });
@@ -17,6 +17,7 @@ const OVERLAY_DEFAULT_WIDTH_M = 0.5;
 const SPLASH_WIDTH_M = 0.3;
 const SPLASH_DISTANCE_M = 1.5;
 const SPLASH_DURATION_MS = 3000;
+const DEFAULT_SWIPE_WORD_LIST = path.join('electron', 'assets', 'swipe', 'en.txt');
 
 // Helper: Normalize Pose Matrix / ポーズ行列の正規化
 function normalizePoseMatrix(pose) {
@@ -162,6 +163,21 @@ export function resetOverlayPosition() {
   }
 }
 
+/**
+ * Load the bundled English word list for swipe typing; older native builds lack it
+ * 同梱の英単語リストをスワイプ入力用に読み込む。古いネイティブビルドには無い
+ */
+function loadDefaultSwipeWordList(manager) {
+  if (typeof manager.loadSwipeWordList !== 'function') return;
+  const wordListPath = getAssetPath(DEFAULT_SWIPE_WORD_LIST);
+  try {
+    const count = manager.loadSwipeWordList(wordListPath);
+    console.log(`Loaded ${count} swipe words from ${wordListPath}`);
+  } catch (e) {
+    console.error(`Failed to load swipe word list from ${wordListPath}:`, e);
+  }
+}
+
 /**
  * Ensure overlay manager exists
  */
@@ -178,6 +194,7 @@ function ensureOverlayManager() {
       throw e;
     }
     console.log('VR System Initialized');
+    loadDefaultSwipeWordList(state.overlayManager);
 
     // Debug: Log available methods
     if (state.debug) {
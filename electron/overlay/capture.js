@@ -79,6 +79,45 @@ export function addCaptureFrameListener(listener) {
   return () => captureFrameListeners.delete(listener);
 }
 
+/**
+ * Ask the native texture throttle whether a frame would be uploaded, so throttled frames
+ * are not captured at all; true on older native builds
+ * ネイティブのテクスチャ抑制にフレームがアップロードされるかを問い合わせ、抑制される
+ * フレームはキャプチャ自体を省く。古いネイティブビルドでは true
+ */
+function shouldCaptureFrame() {
+  const manager = state.overlayManager;
+  if (typeof manager?.shouldSubmitTexture !== 'function') return true;
+  try {
+    return manager.shouldSubmitTexture();
+  } catch (e) {
+    console.warn('Texture throttle check failed:', e);
+    return true;
+  }
+}
+
+/**
+ * Paint offscreen at the minimum rate while the user is away from the headset
+ * ユーザーがヘッドセットから離れている間はオフスクリーン描画を最低レートにする
+ */
+function updateOffscreenFrameRate(webContents, activeFps) {
+  if (typeof webContents.setFrameRate !== 'function') return;
+  const manager = state.overlayManager;
+  let present = true;
+  if (typeof manager?.isUserPresent === 'function') {
+    try {
+      present = manager.isUserPresent();
+    } catch (e) {
+      console.warn('Presence check failed:', e);
+    }
+  }
+  const fps = present ? Math.round(activeFps) : MIN_CAPTURE_FPS;
+  if (state.captureFrameRate !== fps) {
+    webContents.setFrameRate(fps);
+    state.captureFrameRate = fps;
+  }
+}
+
 function updateOverlayFromImage(image) {
   if (!state.overlayManager || state.overlayHandle === null) return false;
 
@@ -199,9 +238,7 @@ export function startCapture(webContents, fps = 60) {
   }
 
   if (isOffscreen) {
-    if (typeof webContents.setFrameRate === 'function') {
-      webContents.setFrameRate(Math.round(clampedFps));
-    }
+    updateOffscreenFrameRate(webContents, clampedFps);
     if (typeof webContents.startPainting === 'function') {
       webContents.startPainting();
     }
@@ -211,7 +248,8 @@ export function startCapture(webContents, fps = 60) {
         stopCapture();
         return;
       }
-      if (state.captureInProgress) return;
+      updateOffscreenFrameRate(state.captureWebContents, clampedFps);
+      if (state.captureInProgress || !shouldCaptureFrame()) return;
       state.captureInProgress = true;
       try {
         updateOverlayFromImage(image);
@@ -241,6 +279,11 @@ export function startCapture(webContents, fps = 60) {
       scheduleNext(1);
       return;
     }
+    if (!shouldCaptureFrame()) {
+      nextCaptureTime = Date.now() + intervalMs;
+      scheduleNext(intervalMs);
+      return;
+    }
     state.captureInProgress = true;
     try {
       // Capture the page / ページをキャプチャ
@@ -328,6 +371,7 @@ export function stopCapture() {
   state.renderGoneHandler = null;
   state.captureWebContents = null;
   state.captureInProgress = false;
+  state.captureFrameRate = 0;
   state.lastFrameBuffer = null;
   state.lastFrameImage = null;
   state.frameRetention.length = 0;
@@ -85,6 +85,7 @@ export function isSteamVrRunning() {
 
 // Load native module with DLL handling / DLL処理付きでネイティブモジュールを読み込み
 let OverlayManager;
+let nativeModule = null;
 try {
   const nativePath = getNativeModulePath();
   const nativeDir = path.dirname(nativePath);
@@ -94,7 +95,8 @@ try {
   try {
     process.chdir(nativeDir);
 
-    ({ OverlayManager } = require(nativePath));
+    nativeModule = require(nativePath);
+    ({ OverlayManager } = nativeModule);
     console.log('Native module loaded from:', nativePath);
   } finally {
     // Restore CWD / CWDを復元
@@ -104,6 +106,22 @@ try {
   console.error('Failed to load native module:', error);
 }
 
+/**
+ * Action manifest generator/validator from the native module, or null when it is unavailable
+ * ネイティブモジュールのアクションマニフェスト生成・検証。利用できない場合は null
+ */
+export function getNativeInputManifestTools() {
+  if (typeof nativeModule?.validateActionManifest !== 'function') {
+    return null;
+  }
+  return {
+    generateActionManifest: nativeModule.generateActionManifest,
+    generateDefaultBindings: nativeModule.generateDefaultBindings,
+    defaultBindingControllerTypes: nativeModule.defaultBindingControllerTypes,
+    validateActionManifest: nativeModule.validateActionManifest,
+  };
+}
+
 export function createOverlayManager() {
   if (!OverlayManager) {
     throw new Error('OverlayManager is not available (native module failed to load)');
@@ -14,6 +14,7 @@ export const state = {
   paintHandler: null,
   captureWebContents: null,
   captureInProgress: false,
+  captureFrameRate: 0, // Offscreen paint rate last requested / 最後に要求したオフスクリーン描画レート
   destroyedHandler: null,
   renderGoneHandler: null,
   lastSizeMismatchTime: 0,
@@ -2,7 +2,10 @@ import fs from 'fs';
 import path from 'path';
 import { execFileSync } from 'child_process';
 import { app } from 'electron';
-import { getAssetPath } from '../overlay/native.js';
+import {
+  getAssetPath,
+  getNativeInputManifestTools,
+} from '../overlay/native.js';
 
 const MANIFEST_FILE_NAME = 'vrchat-osc-keyboard.vrmanifest';
 const ACTIONS_FILE_NAME = 'actions.json';
@@ -142,9 +145,38 @@ export function ensureSteamVrInputFiles() {
     });
   }
 
+  const issues = validateSteamVrInputFiles({ actionsPath, bindingsDir }) || [];
+  for (const issue of issues) {
+    console.warn(`[SteamVR Input] ${issue.file}: ${issue.message}`);
+  }
+
   return { appKey, actionsPath, bindingsDir };
 }
 
+/**
+ * Check actions.json and the binding files against the native built-in actions.
+ * Returns the issues found, or null when the native validator is unavailable.
+ * actions.json とバインディングファイルをネイティブの組み込みアクションと照合する。
+ * 見つかった問題を返し、ネイティブの検証が利用できない場合は null を返す。
+ */
+export function validateSteamVrInputFiles({ actionsPath, bindingsDir }) {
+  const tools = getNativeInputManifestTools();
+  if (!tools) {
+    return null;
+  }
+  const readFile = (filePath) => ({
+    name: path.basename(filePath),
+    contents: fs.readFileSync(filePath, 'utf-8'),
+  });
+  const bindingFiles = CONTROLLER_TYPES.map((controllerType) =>
+    path.join(bindingsDir, `${controllerType}.json`),
+  ).filter((filePath) => fs.existsSync(filePath));
+  return tools.validateActionManifest(
+    readFile(actionsPath),
+    bindingFiles.map(readFile),
+  );
+}
+
 function buildManifestContent() {
   const { appKey, actionsPath, bindingsDir } = ensureSteamVrInputFiles();
   const bindings = CONTROLLER_TYPES.map((controllerType) => ({
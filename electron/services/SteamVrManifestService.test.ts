@@ -0,0 +1,56 @@
+import fs from 'fs';
+import os from 'os';
+import path from 'path';
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+
+const { tools } = vi.hoisted(() => ({
+  tools: { validateActionManifest: vi.fn(() => [] as unknown[]) },
+}));
+
+vi.mock('electron', () => ({
+  app: { getPath: vi.fn(() => '') },
+}));
+
+vi.mock('../overlay/native.js', () => ({
+  getAssetPath: vi.fn(),
+  getNativeInputManifestTools: vi.fn(() => tools),
+}));
+
+import { validateSteamVrInputFiles } from './SteamVrManifestService.js';
+
+describe('validateSteamVrInputFiles', () => {
+  let dir: string;
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'vrkb-steamvr-'));
+    fs.mkdirSync(path.join(dir, 'bindings'));
+    fs.writeFileSync(path.join(dir, 'actions.json'), '{"actions":[]}');
+    fs.writeFileSync(path.join(dir, 'bindings', 'knuckles.json'), '{}');
+  });
+
+  afterEach(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('passes the written files to the native validator', () => {
+    const issue = {
+      kind: 1,
+      file: 'knuckles.json',
+      path: '/actions/vrkb2/in/scroll',
+      message: 'knuckles does not bind /actions/vrkb2/in/scroll',
+    };
+    tools.validateActionManifest.mockReturnValueOnce([issue]);
+
+    const issues = validateSteamVrInputFiles({
+      actionsPath: path.join(dir, 'actions.json'),
+      bindingsDir: path.join(dir, 'bindings'),
+    });
+
+    expect(issues).toEqual([issue]);
+    expect(tools.validateActionManifest).toHaveBeenCalledWith(
+      { name: 'actions.json', contents: '{"actions":[]}' },
+      [{ name: 'knuckles.json', contents: '{}' }],
+    );
+  });
+});
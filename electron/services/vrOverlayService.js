@@ -1,6 +1,12 @@
 import fs from 'fs';
+import path from 'path';
 
-import { getOverlayManager, toggleOverlayAll } from '../overlay.js';
+import {
+  getOverlayManager,
+  hideOverlayAll,
+  showOverlayAll,
+  toggleOverlayAll,
+} from '../overlay.js';
 import {
   ensureSteamVrInputFiles,
   ensureSteamVrManifestRegistered,
@@ -8,6 +14,12 @@ import {
 } from './SteamVrManifestService.js';
 
 const DEFAULT_POLL_HZ = 60;
+// ToggleGestureEvent values from the native module
+const TOGGLE_GESTURE_TOGGLE = 1;
+const TOGGLE_GESTURE_SHOW = 2;
+const TOGGLE_GESTURE_HIDE = 3;
+// Optional per-controller-type overrides for the legacy button layout
+const LEGACY_BUTTON_MAPS_FILE = 'legacy_button_maps.json';
 export const STEAMVR_APP_KEY = getSteamVrAppKey();
 
 const state = {
@@ -24,6 +36,47 @@ function getManifestPath() {
   return ensureSteamVrInputFiles().actionsPath;
 }
 
+// Older native builds only expose pollToggleClicked
+function pollToggle(manager) {
+  if (typeof manager.pollToggleGesture !== 'function') {
+    if (manager.pollToggleClicked()) {
+      toggleOverlayAll();
+    }
+    return;
+  }
+  switch (manager.pollToggleGesture()) {
+    case TOGGLE_GESTURE_TOGGLE:
+      toggleOverlayAll();
+      break;
+    case TOGGLE_GESTURE_SHOW:
+      showOverlayAll();
+      break;
+    case TOGGLE_GESTURE_HIDE:
+      hideOverlayAll();
+      break;
+    default:
+      break;
+  }
+}
+
+function loadLegacyButtonMaps(manager, manifestPath) {
+  if (typeof manager.loadLegacyButtonMaps !== 'function') {
+    return;
+  }
+  const configPath = path.join(
+    path.dirname(manifestPath),
+    LEGACY_BUTTON_MAPS_FILE,
+  );
+  if (!fs.existsSync(configPath)) {
+    return;
+  }
+  try {
+    manager.loadLegacyButtonMaps(configPath);
+  } catch (error) {
+    console.warn('[SteamVR Input] legacy button maps ignored:', error);
+  }
+}
+
 function registerManifestForBindings() {
   const result = ensureSteamVrManifestRegistered();
   if (!result?.success) {
@@ -56,6 +109,7 @@ export function init() {
   try {
     registerManifestForBindings();
     manager.initInput(manifestPath);
+    loadLegacyButtonMaps(manager, manifestPath);
     state.initialized = true;
     return true;
   } catch (error) {
@@ -81,9 +135,7 @@ export function startPolling(hz = DEFAULT_POLL_HZ) {
   const intervalMs = Math.max(1, Math.floor(1000 / hz));
   state.timer = setInterval(() => {
     try {
-      if (manager.pollToggleClicked()) {
-        toggleOverlayAll();
-      }
+      pollToggle(manager);
     } catch (error) {
       console.warn('[SteamVR Input] poll failed, stopping:', error);
       stop();
@@ -108,6 +160,7 @@ export function getCurrentBindings() {
       gripBindings: [],
       triggerBound: false,
       gripBound: false,
+      actions: [],
     };
   }
 
@@ -120,6 +173,7 @@ export function getCurrentBindings() {
       gripBindings: [],
       triggerBound: false,
       gripBound: false,
+      actions: [],
     };
   }
 
@@ -10,6 +10,8 @@ const manager = {
 vi.mock('../overlay.js', () => ({
   getOverlayManager: vi.fn(() => manager),
   toggleOverlayAll: vi.fn(),
+  showOverlayAll: vi.fn(),
+  hideOverlayAll: vi.fn(),
   resetOverlayPosition: vi.fn(),
 }));
 
@@ -58,6 +60,31 @@ describe('vrOverlayService', () => {
     expect(overlay.toggleOverlayAll).toHaveBeenCalledTimes(1);
   });
 
+  it('shows and hides overlay from native toggle gesture events', async () => {
+    const overlay = await import('../overlay.js');
+    const service = await import('./vrOverlayService.js');
+    const pollToggleGesture = vi
+      .fn()
+      .mockReturnValueOnce(2)
+      .mockReturnValueOnce(0)
+      .mockReturnValue(3);
+    Object.assign(manager, { pollToggleGesture });
+
+    try {
+      service.init();
+      service.startPolling(60);
+
+      vi.advanceTimersByTime(16 * 3);
+
+      expect(overlay.showOverlayAll).toHaveBeenCalledTimes(1);
+      expect(overlay.hideOverlayAll).toHaveBeenCalled();
+      expect(overlay.toggleOverlayAll).not.toHaveBeenCalled();
+      expect(manager.pollToggleClicked).not.toHaveBeenCalled();
+    } finally {
+      delete (manager as { pollToggleGesture?: unknown }).pollToggleGesture;
+    }
+  });
+
   it('returns binding info from native manager', async () => {
     const service = await import('./vrOverlayService.js');
     manager.getCurrentBindings.mockReturnValue({
@@ -108,6 +135,7 @@ describe('vrOverlayService', () => {
       gripBindings: [],
       triggerBound: false,
       gripBound: false,
+      actions: [],
     });
   });
 });
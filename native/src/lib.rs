@@ -1,7 +1,78 @@
 mod overlay;
 
+pub use overlay::default_binding_controller_types;
+pub use overlay::generate_action_manifest;
+pub use overlay::generate_default_bindings;
+pub use overlay::validate_action_manifest;
+pub use overlay::ActionBindings;
+pub use overlay::ActionOrigin;
+pub use overlay::AimRay;
+pub use overlay::AnalogActionState;
+pub use overlay::BindingInfo;
+pub use overlay::ControllerButtons;
 pub use overlay::ControllerState;
+pub use overlay::ControllerType;
 pub use overlay::CurrentBindings;
+pub use overlay::DeviceActivityLevel;
+pub use overlay::DeviceEvent;
+pub use overlay::DeviceEventType;
+pub use overlay::DeviceInfo;
+pub use overlay::DevicePose;
+pub use overlay::DigitalActionState;
+pub use overlay::DrumstickParams;
+pub use overlay::FingertipPokeParams;
+pub use overlay::GazeDwellParams;
+pub use overlay::GazeDwellState;
+pub use overlay::GestureEvent;
+pub use overlay::GestureEventType;
+pub use overlay::InputActionDefinition;
+pub use overlay::InputActionSetConfig;
+pub use overlay::InputActionType;
+pub use overlay::InputEvent;
+pub use overlay::InputEventType;
+pub use overlay::InputHand;
+pub use overlay::InputPollerOptions;
 pub use overlay::IntersectionResult;
+pub use overlay::KeyBounds;
+pub use overlay::KeyNavigationParams;
+pub use overlay::KeyNavigationState;
+pub use overlay::LegacyButtonMapInfo;
+pub use overlay::LegacyButtonMapSource;
+pub use overlay::ManifestFile;
+pub use overlay::ManifestIssue;
+pub use overlay::ManifestIssueKind;
 pub use overlay::OverlayManager;
+pub use overlay::OverlayParentTransform;
+pub use overlay::OverlayPoint;
 pub use overlay::OverlayRelativeTransform;
+pub use overlay::PointerArbitration;
+pub use overlay::PointerArbitrationPolicy;
+pub use overlay::PointerRole;
+pub use overlay::PointerStabilizerParams;
+pub use overlay::PoseActionState;
+pub use overlay::PosePredictionMode;
+pub use overlay::PresenceState;
+pub use overlay::SecondaryPointerPolicy;
+pub use overlay::SwipeCandidate;
+pub use overlay::SwipeTypingParams;
+pub use overlay::SwipeTypingState;
+pub use overlay::SwitchScanConfig;
+pub use overlay::SwitchScanEvent;
+pub use overlay::SwitchScanEventType;
+pub use overlay::TextureThrottleOptions;
+pub use overlay::ToggleGestureConfig;
+pub use overlay::ToggleGestureEvent;
+pub use overlay::ToggleGestureMode;
+pub use overlay::TouchEvent;
+pub use overlay::TouchEventType;
+pub use overlay::TouchKeyOffset;
+pub use overlay::TouchModelParams;
+pub use overlay::TouchSample;
+pub use overlay::TrackedControllerRole;
+pub use overlay::TrackedDeviceClass;
+pub use overlay::TrackedDeviceSelector;
+pub use overlay::TrackingResult;
+pub use overlay::TrackingUniverse;
+pub use overlay::TriggerGestureParams;
+pub use overlay::VrEvent;
+pub use overlay::VrEventBatch;
@@ -0,0 +1,384 @@
+use napi_derive::napi;
+use openvr_sys as vr;
+
+use super::constants::{AIM_LEFT_ACTION, AIM_RIGHT_ACTION};
+use super::errors::require_fn;
+use super::input_ops::input_error_if_needed;
+use super::manager::{create_poses_cache, InputActionCache, OverlayManager};
+use super::math::{hmd_matrix34_to_vec, invert_hmd_matrix34, mul_hmd_matrix34, pose_ray};
+use super::types::{
+    AimRay, AnalogActionState, DigitalActionState, InputActionType, InputHand, PoseActionState,
+    TrackingResult,
+};
+
+/// Handle and restrict-to-device source for one read / 1回の読み取りに使うハンドルとデバイス制限ソース
+#[derive(Clone, Copy, Debug)]
+pub(super) struct ResolvedAction {
+    pub handle: vr::VRActionHandle_t,
+    pub source: vr::VRInputValueHandle_t,
+}
+
+fn type_name(action_type: InputActionType) -> &'static str {
+    match action_type {
+        InputActionType::Boolean => "boolean",
+        InputActionType::Vector1 => "vector1",
+        InputActionType::Vector2 => "vector2",
+        InputActionType::Pose => "pose",
+        InputActionType::Vibration => "vibration",
+        InputActionType::Skeleton => "skeleton",
+    }
+}
+
+impl InputActionCache {
+    /// Look up `name`, check its type and pick the hand to read from. A requested hand
+    /// may narrow an unrestricted action but not contradict the definition's restriction.
+    /// `name` を検索して型を確認し、読み取る手を決める。要求した手で無制限のアクションを
+    /// 絞り込めるが、定義側の制限と矛盾してはならない。
+    pub(super) fn resolve(
+        &self,
+        name: &str,
+        expected: &[InputActionType],
+        hand: Option<InputHand>,
+    ) -> napi::Result<ResolvedAction> {
+        if !self.initialized {
+            return Err(napi::Error::from_reason("SteamVR input is not initialized"));
+        }
+        let action = self
+            .action(name)
+            .ok_or_else(|| napi::Error::from_reason(format!("unknown action: {name}")))?;
+        if !expected.contains(&action.action_type) {
+            return Err(napi::Error::from_reason(format!(
+                "action {name} is a {} action",
+                type_name(action.action_type)
+            )));
+        }
+        let hand = match (action.hand, hand) {
+            (InputHand::Any, requested) => requested.unwrap_or(InputHand::Any),
+            (restricted, None) => restricted,
+            (restricted, Some(requested)) if requested == restricted => restricted,
+            (restricted, Some(_)) => {
+                return Err(napi::Error::from_reason(format!(
+                    "action {name} is restricted to the {:?} hand",
+                    restricted
+                )))
+            }
+        };
+        Ok(ResolvedAction {
+            handle: action.handle,
+            source: self.hand_source(hand),
+        })
+    }
+}
+
+impl OverlayManager {
+    /// Refresh SteamVR Input state for the configured action set
+    /// 設定されたアクションセットの SteamVR Input の状態を更新する
+    pub(super) fn update_action_state(&self, cache: &InputActionCache) -> napi::Result<()> {
+        let input = self.input()?;
+        let update_action_state_fn = require_fn(input.UpdateActionState, "UpdateActionState")?;
+        let mut active_set = vr::VRActiveActionSet_t {
+            ulActionSet: cache.action_set_handle,
+            ulRestrictedToDevice: vr::k_ulInvalidInputValueHandle,
+            ulSecondaryActionSet: vr::k_ulInvalidActionSetHandle,
+            unPadding: 0,
+            nPriority: 0,
+        };
+        unsafe {
+            input_error_if_needed(
+                "UpdateActionState",
+                update_action_state_fn(
+                    &mut active_set,
+                    std::mem::size_of::<vr::VRActiveActionSet_t>() as u32,
+                    1,
+                ),
+            )
+        }
+    }
+
+    pub(super) fn read_digital_action(
+        &self,
+        action: ResolvedAction,
+    ) -> napi::Result<vr::InputDigitalActionData_t> {
+        let input = self.input()?;
+        let get_digital_fn = require_fn(input.GetDigitalActionData, "GetDigitalActionData")?;
+        let mut data: vr::InputDigitalActionData_t = unsafe { std::mem::zeroed() };
+        unsafe {
+            input_error_if_needed(
+                "GetDigitalActionData",
+                get_digital_fn(
+                    action.handle,
+                    &mut data,
+                    std::mem::size_of::<vr::InputDigitalActionData_t>() as u32,
+                    action.source,
+                ),
+            )?;
+        }
+        Ok(data)
+    }
+
+    pub(super) fn read_analog_action(
+        &self,
+        action: ResolvedAction,
+    ) -> napi::Result<vr::InputAnalogActionData_t> {
+        let input = self.input()?;
+        let get_analog_fn = require_fn(input.GetAnalogActionData, "GetAnalogActionData")?;
+        let mut data: vr::InputAnalogActionData_t = unsafe { std::mem::zeroed() };
+        unsafe {
+            input_error_if_needed(
+                "GetAnalogActionData",
+                get_analog_fn(
+                    action.handle,
+                    &mut data,
+                    std::mem::size_of::<vr::InputAnalogActionData_t>() as u32,
+                    action.source,
+                ),
+            )?;
+        }
+        Ok(data)
+    }
+
+    /// Pose for the next frame in the configured universe / 設定ユニバースでの次フレームのポーズ
+    pub(super) fn read_pose_action(
+        &self,
+        action: ResolvedAction,
+    ) -> napi::Result<vr::InputPoseActionData_t> {
+        let input = self.input()?;
+        let get_pose_fn = require_fn(
+            input.GetPoseActionDataForNextFrame,
+            "GetPoseActionDataForNextFrame",
+        )?;
+        let mut data: vr::InputPoseActionData_t = unsafe { std::mem::zeroed() };
+        unsafe {
+            input_error_if_needed(
+                "GetPoseActionDataForNextFrame",
+                get_pose_fn(
+                    action.handle,
+                    self.tracking_universe(),
+                    &mut data,
+                    std::mem::size_of::<vr::InputPoseActionData_t>() as u32,
+                    action.source,
+                ),
+            )?;
+        }
+        Ok(data)
+    }
+
+    fn aim_action(&self, hand: InputHand) -> napi::Result<ResolvedAction> {
+        let name = match hand {
+            InputHand::Left => AIM_LEFT_ACTION,
+            InputHand::Right => AIM_RIGHT_ACTION,
+            InputHand::Any => {
+                return Err(napi::Error::from_reason("aim hand must be Left or Right"))
+            }
+        };
+        self.borrow_input_cache()?
+            .resolve(name, &[InputActionType::Pose], Some(hand))
+    }
+
+    /// Valid pose of a hand's aim action / 手のエイムアクションの有効なポーズ
+    pub(super) fn aim_pose(
+        &self,
+        hand: InputHand,
+    ) -> napi::Result<Option<vr::TrackedDevicePose_t>> {
+        let data = self.read_pose_action(self.aim_action(hand)?)?;
+        Ok((data.bActive && data.pose.bPoseIsValid).then_some(data.pose))
+    }
+
+    /// Aim pose of a hand relative to the device pose of the controller driving it, with
+    /// that controller's index. The input poller applies it to the device pose, as it
+    /// cannot read SteamVR Input on its own thread. Both poses are sampled for now.
+    /// 手のエイムポーズを、それを動かすコントローラーのデバイスポーズに対する相対値として、
+    /// そのコントローラーのインデックスと共に返す。入力ポーラーは自スレッドで SteamVR Input を
+    /// 読めないため、これをデバイスポーズに適用する。両ポーズとも現在時刻で取得する。
+    pub(super) fn aim_offset(&self, hand: InputHand) -> napi::Result<Option<(u32, [[f32; 4]; 3])>> {
+        let action = self.aim_action(hand)?;
+        let input = self.input()?;
+        let get_pose_fn = require_fn(
+            input.GetPoseActionDataRelativeToNow,
+            "GetPoseActionDataRelativeToNow",
+        )?;
+        let get_origin_info_fn = require_fn(
+            input.GetOriginTrackedDeviceInfo,
+            "GetOriginTrackedDeviceInfo",
+        )?;
+        let mut data: vr::InputPoseActionData_t = unsafe { std::mem::zeroed() };
+        unsafe {
+            input_error_if_needed(
+                "GetPoseActionDataRelativeToNow",
+                get_pose_fn(
+                    action.handle,
+                    self.tracking_universe(),
+                    0.0,
+                    &mut data,
+                    std::mem::size_of::<vr::InputPoseActionData_t>() as u32,
+                    action.source,
+                ),
+            )?;
+        }
+        if !data.bActive || !data.pose.bPoseIsValid {
+            return Ok(None);
+        }
+
+        let mut info: vr::InputOriginInfo_t = unsafe { std::mem::zeroed() };
+        let err = unsafe {
+            get_origin_info_fn(
+                data.activeOrigin,
+                &mut info,
+                std::mem::size_of::<vr::InputOriginInfo_t>() as u32,
+            )
+        };
+        let index = info.trackedDeviceIndex;
+        if err != vr::EVRInputError_VRInputError_None || index >= vr::k_unMaxTrackedDeviceCount {
+            return Ok(None);
+        }
+
+        let mut poses = create_poses_cache();
+        self.fetch_poses(0.0, &mut poses)?;
+        let device = &poses[index as usize];
+        if !device.bPoseIsValid {
+            return Ok(None);
+        }
+        Ok(
+            invert_hmd_matrix34(&device.mDeviceToAbsoluteTracking).map(|inverse| {
+                let offset = mul_hmd_matrix34(&inverse, &data.pose.mDeviceToAbsoluteTracking);
+                (index, offset.m)
+            }),
+        )
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Refresh action state; getDigital / getAnalog / getPose read the latest update
+    /// アクション状態を更新する。getDigital / getAnalog / getPose は最新の更新結果を読む
+    #[napi]
+    pub fn update_input_state(&self) -> napi::Result<()> {
+        let cache = self.borrow_input_cache()?;
+        if !cache.initialized {
+            return Err(napi::Error::from_reason("SteamVR input is not initialized"));
+        }
+        self.update_action_state(&cache)
+    }
+
+    #[napi]
+    pub fn get_digital(
+        &self,
+        name: String,
+        hand: Option<InputHand>,
+    ) -> napi::Result<DigitalActionState> {
+        let action =
+            self.borrow_input_cache()?
+                .resolve(&name, &[InputActionType::Boolean], hand)?;
+        let data = self.read_digital_action(action)?;
+        Ok(DigitalActionState {
+            active: data.bActive,
+            state: data.bState,
+            changed: data.bChanged,
+            updateTime: data.fUpdateTime as f64,
+        })
+    }
+
+    #[napi]
+    pub fn get_analog(
+        &self,
+        name: String,
+        hand: Option<InputHand>,
+    ) -> napi::Result<AnalogActionState> {
+        let action = self.borrow_input_cache()?.resolve(
+            &name,
+            &[InputActionType::Vector1, InputActionType::Vector2],
+            hand,
+        )?;
+        let data = self.read_analog_action(action)?;
+        Ok(AnalogActionState {
+            active: data.bActive,
+            x: data.x as f64,
+            y: data.y as f64,
+            z: data.z as f64,
+            deltaX: data.deltaX as f64,
+            deltaY: data.deltaY as f64,
+            deltaZ: data.deltaZ as f64,
+        })
+    }
+
+    #[napi]
+    pub fn get_pose(&self, name: String, hand: Option<InputHand>) -> napi::Result<PoseActionState> {
+        let action = self.borrow_input_cache()?.resolve(
+            &name,
+            &[InputActionType::Pose, InputActionType::Skeleton],
+            hand,
+        )?;
+        let data = self.read_pose_action(action)?;
+        let pose = &data.pose;
+        Ok(PoseActionState {
+            active: data.bActive,
+            poseIsValid: pose.bPoseIsValid,
+            trackingResult: TrackingResult::from_vr(pose.eTrackingResult),
+            matrix: hmd_matrix34_to_vec(&pose.mDeviceToAbsoluteTracking.m),
+            velocity: pose.vVelocity.v.iter().map(|&v| v as f64).collect(),
+            angularVelocity: pose.vAngularVelocity.v.iter().map(|&v| v as f64).collect(),
+        })
+    }
+
+    /// Aim ray for a hand from its `aim_left` / `aim_right` pose action, which follows the
+    /// controller tip rather than the grip. None while the action is inactive or untracked.
+    /// `aim_left` / `aim_right` ポーズアクションから手のエイムレイを得る。グリップではなく
+    /// コントローラー先端に追従する。アクションが無効またはトラッキング外なら None。
+    #[napi]
+    pub fn get_aim_ray(&self, hand: InputHand) -> napi::Result<Option<AimRay>> {
+        let Some(pose) = self.aim_pose(hand)? else {
+            return Ok(None);
+        };
+        let (origin, direction) = pose_ray(&pose.mDeviceToAbsoluteTracking.m);
+        // Keep the poller's ray on the same aim pose / ポーラーのレイも同じエイムポーズに揃える
+        if self.input_poller_running()? {
+            if let Some((index, offset)) = self.aim_offset(hand)? {
+                self.update_input_poller(|config| {
+                    config.aim_offsets.insert(index, offset);
+                })?;
+            }
+        }
+        Ok(Some(AimRay {
+            origin,
+            direction,
+            trackingResult: TrackingResult::from_vr(pose.eTrackingResult),
+        }))
+    }
+
+    #[napi]
+    pub fn trigger_vibration(
+        &self,
+        name: String,
+        hand: Option<InputHand>,
+        duration_seconds: f64,
+        frequency: f64,
+        amplitude: f64,
+    ) -> napi::Result<()> {
+        if !(duration_seconds.is_finite() && frequency.is_finite() && amplitude.is_finite()) {
+            return Err(napi::Error::from_reason(
+                "vibration parameters must be finite",
+            ));
+        }
+        let action =
+            self.borrow_input_cache()?
+                .resolve(&name, &[InputActionType::Vibration], hand)?;
+        let input = self.input()?;
+        let vibrate_fn = require_fn(
+            input.TriggerHapticVibrationAction,
+            "TriggerHapticVibrationAction",
+        )?;
+        unsafe {
+            input_error_if_needed(
+                "TriggerHapticVibrationAction",
+                vibrate_fn(
+                    action.handle,
+                    0.0,
+                    duration_seconds as f32,
+                    frequency as f32,
+                    amplitude.clamp(0.0, 1.0) as f32,
+                    action.source,
+                ),
+            )
+        }
+    }
+}
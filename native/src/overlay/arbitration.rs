@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Hand {
+    Left,
+    Right,
+    /// No hand role (tracker, stylus, role not assigned yet) / 手のロールが無い (トラッカー、スタイラス、未割り当て)
+    Other,
+}
+
+/// What happens to the laser of the hand that is not the primary pointer
+/// 主ポインターではない方の手のレーザーの扱い
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum SecondaryPolicy {
+    /// Hidden; its trigger only takes over as primary / 非表示。トリガーは主ポインターの引き継ぎにのみ使う
+    Suppress,
+    /// Visible and clicking, for two-handed typing / 両手打ち用に表示しクリックも有効
+    TwoHanded,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct ArbitrationPolicy {
+    pub dominant_hand: Hand,
+    pub secondary: SecondaryPolicy,
+}
+
+impl ArbitrationPolicy {
+    /// Both lasers stay usable as before; only the drag lock changes behavior
+    /// 従来どおり両方のレーザーが使える。変わるのはドラッグのロックのみ
+    pub(super) const DEFAULT: Self = Self {
+        dominant_hand: Hand::Right,
+        secondary: SecondaryPolicy::TwoHanded,
+    };
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        if self.dominant_hand == Hand::Other {
+            return Err(napi::Error::from_reason(
+                "dominantHand must be the left or right hand",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// One controller's raw buttons for this tick / このティックのコントローラー1台分の生のボタン
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct ArbitrationInput {
+    pub index: u32,
+    pub hand: Hand,
+    pub trigger: bool,
+    pub grip: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum PointerRole {
+    Primary,
+    /// Visible and usable alongside the primary / 主ポインターと並んで表示・使用できる
+    Secondary,
+    Suppressed,
+}
+
+/// What the input loop may do with one controller this tick. `trigger` and `grip` are
+/// the gated button states to feed into click and drag handling.
+/// このティックで入力ループがコントローラー1台に対して行ってよいこと。`trigger` と
+/// `grip` はクリックとドラッグの処理に渡す、制御済みのボタン状態。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct ArbitrationDecision {
+    pub index: u32,
+    pub role: PointerRole,
+    pub pointer_visible: bool,
+    pub trigger: bool,
+    pub grip: bool,
+    /// This controller became the primary pointer this tick / このティックで主ポインターになった
+    pub took_focus: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct ControllerButtons {
+    trigger: bool,
+    grip: bool,
+    /// The current trigger press is passed through / 現在のトリガー押下を通している
+    press_delivered: bool,
+}
+
+/// Decides which controller owns pointer focus and the overlay drag.
+///
+/// Focus rules:
+/// 1. Until someone pulls a trigger, the dominant-hand controller is the provisional primary;
+///    without one, every laser is shown.
+/// 2. The first trigger press claims the primary pointer; on the same tick the dominant hand
+///    wins, then the lowest device index. Under `Suppress` a press from a hidden laser only
+///    takes focus.
+/// 3. A trigger press on another controller hands focus over only while the primary is idle
+///    (trigger up and not dragging). Under `Suppress` that press is swallowed so switching
+///    hands never types a key; under `TwoHanded` it also clicks.
+/// 4. While the primary is busy, another controller's press clicks under `TwoHanded` and is
+///    swallowed under `Suppress`, without taking focus.
+/// 5. A press keeps its pass/swallow decision until release, so press and release stay paired.
+/// 6. The first grip owns the drag until it is released; other grips are ignored meanwhile.
+/// 7. A controller that disappears releases focus and the drag.
+///
+/// ポインターのフォーカスとオーバーレイのドラッグを所有するコントローラーを決める。
+///
+/// フォーカスの規則:
+/// 1. 誰かがトリガーを引くまでは利き手のコントローラーが仮の主ポインター。利き手が無ければ
+///    すべてのレーザーを表示する。
+/// 2. 最初のトリガー押下が主ポインターを取る。同じティックなら利き手、次にデバイス
+///    インデックスの小さい方が優先。`Suppress` では非表示のレーザーの押下はフォーカスを取るだけ。
+/// 3. 他のコントローラーのトリガー押下は、主ポインターが待機中 (トリガーが離れていて
+///    ドラッグ中でない) の場合にのみフォーカスを引き継ぐ。`Suppress` ではその押下を
+///    握りつぶして持ち替えでキーが入力されないようにし、`TwoHanded` ではクリックも通す。
+/// 4. 主ポインターが操作中の間、他のコントローラーの押下は `TwoHanded` ではクリックになり、
+///    `Suppress` では握りつぶされる。どちらもフォーカスは移らない。
+/// 5. 押下ごとの通す/握りつぶす判断は解放まで保つため、押下と解放は必ず対になる。
+/// 6. 最初のグリップが解放されるまでドラッグを所有し、その間の他のグリップは無視する。
+/// 7. 見えなくなったコントローラーはフォーカスとドラッグを手放す。
+#[derive(Debug, Default)]
+pub(super) struct PointerArbiter {
+    primary: Option<u32>,
+    drag_owner: Option<u32>,
+    buttons: HashMap<u32, ControllerButtons>,
+}
+
+impl PointerArbiter {
+    pub(super) fn primary(&self) -> Option<u32> {
+        self.primary
+    }
+
+    pub(super) fn drag_owner(&self) -> Option<u32> {
+        self.drag_owner
+    }
+
+    fn primary_busy(&self, primary: u32) -> bool {
+        self.drag_owner == Some(primary)
+            || self
+                .buttons
+                .get(&primary)
+                .is_some_and(|buttons| buttons.trigger)
+    }
+
+    fn dominant_controller(policy: &ArbitrationPolicy, inputs: &[ArbitrationInput]) -> Option<u32> {
+        inputs
+            .iter()
+            .filter(|input| input.hand == policy.dominant_hand)
+            .map(|input| input.index)
+            .min()
+    }
+
+    /// Arbitrate every connected controller for one tick; controllers left out are treated
+    /// as gone. Decisions come back in the order of `inputs`.
+    /// 接続中の全コントローラーを1ティック分調停する。含まれないコントローラーは消えたものとして
+    /// 扱う。判定は `inputs` と同じ順で返す。
+    pub(super) fn update(
+        &mut self,
+        policy: &ArbitrationPolicy,
+        inputs: &[ArbitrationInput],
+    ) -> Vec<ArbitrationDecision> {
+        self.buttons
+            .retain(|index, _| inputs.iter().any(|input| input.index == *index));
+        if self
+            .primary
+            .is_some_and(|index| !self.buttons.contains_key(&index))
+        {
+            self.primary = None;
+        }
+        if self
+            .drag_owner
+            .is_some_and(|index| !self.buttons.contains_key(&index))
+        {
+            self.drag_owner = None;
+        }
+
+        // Dominant hand first, then by index / 利き手を先に、次にインデックス順
+        let mut order: Vec<&ArbitrationInput> = inputs.iter().collect();
+        order.sort_by_key(|input| (input.hand != policy.dominant_hand, input.index));
+
+        // Judged on the previous tick so the processing order does not matter; a press
+        // that claims focus this tick makes the new primary busy
+        // 処理順に左右されないよう前ティックの状態で判定する。このティックでフォーカスを取った
+        // 押下は新しい主ポインターを操作中にする
+        let mut primary_busy = self
+            .primary
+            .is_some_and(|primary| self.primary_busy(primary));
+        let provisional = Self::dominant_controller(policy, inputs);
+        let mut took_focus = None;
+        for input in order {
+            let previous = self.buttons.get(&input.index).copied().unwrap_or_default();
+            let mut buttons = ControllerButtons {
+                trigger: input.trigger,
+                grip: input.grip,
+                press_delivered: input.trigger && previous.press_delivered,
+            };
+
+            if input.trigger && !previous.trigger {
+                buttons.press_delivered = match self.primary {
+                    None => {
+                        self.primary = Some(input.index);
+                        took_focus = Some(input.index);
+                        primary_busy = true;
+                        // A hidden laser only takes focus / 非表示のレーザーはフォーカスを取るだけ
+                        provisional.is_none_or(|index| index == input.index)
+                            || policy.secondary == SecondaryPolicy::TwoHanded
+                    }
+                    Some(primary) if primary == input.index => true,
+                    Some(_) => {
+                        if !primary_busy {
+                            self.primary = Some(input.index);
+                            took_focus = Some(input.index);
+                            primary_busy = true;
+                        }
+                        policy.secondary == SecondaryPolicy::TwoHanded
+                    }
+                };
+            }
+
+            if input.grip && !previous.grip && self.drag_owner.is_none() {
+                self.drag_owner = Some(input.index);
+            } else if !input.grip && self.drag_owner == Some(input.index) {
+                self.drag_owner = None;
+            }
+            self.buttons.insert(input.index, buttons);
+        }
+
+        let provisional = self
+            .primary
+            .or_else(|| Self::dominant_controller(policy, inputs));
+        inputs
+            .iter()
+            .map(|input| {
+                let role = match provisional {
+                    Some(primary) if primary == input.index => PointerRole::Primary,
+                    Some(_) if policy.secondary == SecondaryPolicy::Suppress => {
+                        PointerRole::Suppressed
+                    }
+                    _ => PointerRole::Secondary,
+                };
+                let buttons = self.buttons[&input.index];
+                ArbitrationDecision {
+                    index: input.index,
+                    role,
+                    pointer_visible: role != PointerRole::Suppressed,
+                    trigger: buttons.press_delivered,
+                    grip: self.drag_owner == Some(input.index),
+                    took_focus: took_focus == Some(input.index),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEFT: u32 = 1;
+    const RIGHT: u32 = 2;
+
+    fn policy(secondary: SecondaryPolicy) -> ArbitrationPolicy {
+        ArbitrationPolicy {
+            dominant_hand: Hand::Right,
+            secondary,
+        }
+    }
+
+    fn inputs(left: (bool, bool), right: (bool, bool)) -> [ArbitrationInput; 2] {
+        [
+            ArbitrationInput {
+                index: LEFT,
+                hand: Hand::Left,
+                trigger: left.0,
+                grip: left.1,
+            },
+            ArbitrationInput {
+                index: RIGHT,
+                hand: Hand::Right,
+                trigger: right.0,
+                grip: right.1,
+            },
+        ]
+    }
+
+    fn decision(decisions: &[ArbitrationDecision], index: u32) -> ArbitrationDecision {
+        *decisions.iter().find(|d| d.index == index).unwrap()
+    }
+
+    const IDLE: (bool, bool) = (false, false);
+    const TRIGGER: (bool, bool) = (true, false);
+    const GRIP: (bool, bool) = (false, true);
+
+    #[test]
+    fn dominant_hand_is_provisional_primary_until_first_trigger() {
+        let mut arbiter = PointerArbiter::default();
+        let policy = policy(SecondaryPolicy::Suppress);
+        let decisions = arbiter.update(&policy, &inputs(IDLE, IDLE));
+        assert_eq!(decision(&decisions, RIGHT).role, PointerRole::Primary);
+        assert!(!decision(&decisions, LEFT).pointer_visible);
+        assert_eq!(arbiter.primary(), None);
+
+        // The hidden off hand takes focus with its first press without clicking
+        // 非表示の利き手でない方は最初の押下でクリックせずにフォーカスを取る
+        let decisions = arbiter.update(&policy, &inputs(TRIGGER, IDLE));
+        let left = decision(&decisions, LEFT);
+        assert!(left.took_focus && !left.trigger);
+        assert_eq!(left.role, PointerRole::Primary);
+        assert!(!decision(&decisions, RIGHT).pointer_visible);
+    }
+
+    #[test]
+    fn simultaneous_first_presses_go_to_the_dominant_hand() {
+        let mut arbiter = PointerArbiter::default();
+        let decisions = arbiter.update(
+            &policy(SecondaryPolicy::Suppress),
+            &inputs(TRIGGER, TRIGGER),
+        );
+        assert_eq!(arbiter.primary(), Some(RIGHT));
+        assert!(decision(&decisions, RIGHT).trigger);
+        assert!(!decision(&decisions, LEFT).trigger);
+    }
+
+    #[test]
+    fn suppress_swallows_the_handoff_press_until_release() {
+        let mut arbiter = PointerArbiter::default();
+        let policy = policy(SecondaryPolicy::Suppress);
+        arbiter.update(&policy, &inputs(IDLE, TRIGGER));
+        arbiter.update(&policy, &inputs(IDLE, IDLE));
+
+        let decisions = arbiter.update(&policy, &inputs(TRIGGER, IDLE));
+        let left = decision(&decisions, LEFT);
+        assert!(left.took_focus);
+        assert!(!left.trigger, "switching hands must not click");
+        assert!(!arbiter.update(&policy, &inputs(TRIGGER, IDLE))[0].trigger);
+
+        arbiter.update(&policy, &inputs(IDLE, IDLE));
+        assert!(arbiter.update(&policy, &inputs(TRIGGER, IDLE))[0].trigger);
+    }
+
+    #[test]
+    fn busy_primary_keeps_focus() {
+        let mut arbiter = PointerArbiter::default();
+        let suppress = policy(SecondaryPolicy::Suppress);
+        arbiter.update(&suppress, &inputs(IDLE, TRIGGER));
+        let decisions = arbiter.update(&suppress, &inputs(TRIGGER, TRIGGER));
+        assert_eq!(arbiter.primary(), Some(RIGHT));
+        assert!(!decision(&decisions, LEFT).trigger);
+
+        let mut arbiter = PointerArbiter::default();
+        let two_handed = policy(SecondaryPolicy::TwoHanded);
+        arbiter.update(&two_handed, &inputs(IDLE, TRIGGER));
+        let decisions = arbiter.update(&two_handed, &inputs(TRIGGER, TRIGGER));
+        assert_eq!(arbiter.primary(), Some(RIGHT));
+        let left = decision(&decisions, LEFT);
+        assert!(left.trigger && !left.took_focus);
+        assert_eq!(left.role, PointerRole::Secondary);
+        assert!(left.pointer_visible);
+    }
+
+    #[test]
+    fn grip_cannot_steal_an_active_drag() {
+        let mut arbiter = PointerArbiter::default();
+        let policy = policy(SecondaryPolicy::TwoHanded);
+        arbiter.update(&policy, &inputs(GRIP, IDLE));
+        let decisions = arbiter.update(&policy, &inputs(GRIP, GRIP));
+        assert!(decision(&decisions, LEFT).grip);
+        assert!(!decision(&decisions, RIGHT).grip);
+
+        // Still ignored after the owner lets go, until it is pressed again
+        // 所有者が離した後も、押し直すまでは無視される
+        let decisions = arbiter.update(&policy, &inputs(IDLE, GRIP));
+        assert!(!decision(&decisions, RIGHT).grip);
+        arbiter.update(&policy, &inputs(IDLE, IDLE));
+        let decisions = arbiter.update(&policy, &inputs(IDLE, GRIP));
+        assert!(decision(&decisions, RIGHT).grip);
+    }
+
+    #[test]
+    fn disconnect_releases_focus_and_drag() {
+        let mut arbiter = PointerArbiter::default();
+        let policy = policy(SecondaryPolicy::Suppress);
+        arbiter.update(&policy, &inputs((true, true), IDLE));
+        assert_eq!(arbiter.primary(), Some(LEFT));
+        assert_eq!(arbiter.drag_owner(), Some(LEFT));
+
+        let right_only = [inputs(IDLE, IDLE)[1]];
+        let decisions = arbiter.update(&policy, &right_only);
+        assert_eq!(arbiter.primary(), None);
+        assert_eq!(arbiter.drag_owner(), None);
+        assert_eq!(decisions[0].role, PointerRole::Primary);
+    }
+}
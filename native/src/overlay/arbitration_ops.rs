@@ -0,0 +1,136 @@
+use napi_derive::napi;
+
+use super::arbitration::{
+    ArbitrationDecision, ArbitrationInput, ArbitrationPolicy, Hand, PointerRole as Role,
+    SecondaryPolicy,
+};
+use super::manager::OverlayManager;
+use super::types::{
+    ControllerButtons, InputHand, PointerArbitration, PointerArbitrationPolicy, PointerRole,
+    SecondaryPointerPolicy, TrackedControllerRole,
+};
+
+impl ArbitrationPolicy {
+    fn from_napi(policy: &PointerArbitrationPolicy) -> napi::Result<Self> {
+        let policy = Self {
+            dominant_hand: match policy.dominantHand {
+                InputHand::Left => Hand::Left,
+                InputHand::Right => Hand::Right,
+                InputHand::Any => Hand::Other,
+            },
+            secondary: match policy.secondaryPointer {
+                SecondaryPointerPolicy::Suppress => SecondaryPolicy::Suppress,
+                SecondaryPointerPolicy::TwoHanded => SecondaryPolicy::TwoHanded,
+            },
+        };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    fn to_napi(self) -> PointerArbitrationPolicy {
+        PointerArbitrationPolicy {
+            dominantHand: match self.dominant_hand {
+                Hand::Left => InputHand::Left,
+                Hand::Right => InputHand::Right,
+                Hand::Other => InputHand::Any,
+            },
+            secondaryPointer: match self.secondary {
+                SecondaryPolicy::Suppress => SecondaryPointerPolicy::Suppress,
+                SecondaryPolicy::TwoHanded => SecondaryPointerPolicy::TwoHanded,
+            },
+        }
+    }
+}
+
+impl ArbitrationDecision {
+    fn to_napi(self) -> PointerArbitration {
+        PointerArbitration {
+            controllerIndex: self.index,
+            role: match self.role {
+                Role::Primary => PointerRole::Primary,
+                Role::Secondary => PointerRole::Secondary,
+                Role::Suppressed => PointerRole::Suppressed,
+            },
+            pointerVisible: self.pointer_visible,
+            triggerPressed: self.trigger,
+            gripPressed: self.grip,
+            tookFocus: self.took_focus,
+        }
+    }
+}
+
+impl OverlayManager {
+    fn controller_hand(&self, index: u32) -> napi::Result<Hand> {
+        Ok(match self.device_role(index)? {
+            TrackedControllerRole::LeftHand => Hand::Left,
+            TrackedControllerRole::RightHand => Hand::Right,
+            _ => Hand::Other,
+        })
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Decide pointer focus and drag ownership for one input tick. Pass every controller
+    /// that is still connected; controllers left out release focus and the drag.
+    /// 1入力ティック分のポインターのフォーカスとドラッグの所有者を決める。接続中の全コントローラーを
+    /// 渡すこと。含まれないコントローラーはフォーカスとドラッグを手放す。
+    #[napi]
+    pub fn arbitrate_controllers(
+        &self,
+        controllers: Vec<ControllerButtons>,
+    ) -> napi::Result<Vec<PointerArbitration>> {
+        let inputs = controllers
+            .iter()
+            .map(|controller| {
+                Ok(ArbitrationInput {
+                    index: controller.controllerIndex,
+                    hand: self.controller_hand(controller.controllerIndex)?,
+                    trigger: controller.triggerPressed,
+                    grip: controller.gripPressed,
+                })
+            })
+            .collect::<napi::Result<Vec<_>>>()?;
+        let policy = self.arbitration_policy();
+        Ok(self
+            .borrow_pointer_arbiter_mut()?
+            .update(&policy, &inputs)
+            .into_iter()
+            .map(ArbitrationDecision::to_napi)
+            .collect())
+    }
+
+    /// Controller that currently holds pointer focus, if any / 現在ポインターのフォーカスを持つコントローラー
+    #[napi]
+    pub fn get_primary_controller(&self) -> napi::Result<Option<u32>> {
+        Ok(self.borrow_pointer_arbiter()?.primary())
+    }
+
+    /// Controller that owns the overlay drag, if any / オーバーレイのドラッグを所有するコントローラー
+    #[napi]
+    pub fn get_drag_controller(&self) -> napi::Result<Option<u32>> {
+        Ok(self.borrow_pointer_arbiter()?.drag_owner())
+    }
+
+    /// Forget focus and drag ownership; the next trigger press claims focus again
+    /// フォーカスとドラッグの所有を忘れる。次のトリガー押下が再びフォーカスを取る
+    #[napi]
+    pub fn reset_pointer_arbitration(&self) -> napi::Result<()> {
+        *self.borrow_pointer_arbiter_mut()? = Default::default();
+        Ok(())
+    }
+
+    #[napi]
+    pub fn set_pointer_arbitration_policy(
+        &self,
+        policy: PointerArbitrationPolicy,
+    ) -> napi::Result<()> {
+        self.set_arbitration_policy(ArbitrationPolicy::from_napi(&policy)?);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_pointer_arbitration_policy(&self) -> PointerArbitrationPolicy {
+        self.arbitration_policy().to_napi()
+    }
+}
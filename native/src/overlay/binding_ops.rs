@@ -0,0 +1,275 @@
+use napi_derive::napi;
+use openvr_sys as vr;
+use std::collections::BTreeSet;
+use std::ffi::c_char;
+
+use super::constants::{GRIP_ACTION, TOGGLE_ACTION, TRIGGER_ACTION};
+use super::errors::{input_error, read_c_buffer, require_fn};
+use super::input_ops::to_cstring;
+use super::manager::{InputAction, InputActionCache, OverlayManager};
+use super::types::{ActionBindings, ActionOrigin, BindingInfo, CurrentBindings};
+
+const MAX_BINDING_INFO: usize = 16;
+const LOCALIZED_NAME_CAPACITY: usize = 256;
+
+fn is_non_fatal_binding_info_error(err: vr::EVRInputError) -> bool {
+    err == vr::EVRInputError_VRInputError_NoData
+        || err == vr::EVRInputError_VRInputError_NoActiveActionSet
+}
+
+/// Display label for a binding: its localized name, else the raw paths joined
+/// バインディングの表示ラベル。ローカライズ名、なければ生のパスを連結したもの
+fn binding_label(binding: &BindingInfo) -> String {
+    if let Some(name) = binding
+        .localizedName
+        .as_deref()
+        .filter(|name| !name.is_empty())
+    {
+        return name.to_string();
+    }
+    [
+        binding.devicePath.as_str(),
+        binding.inputPath.as_str(),
+        binding.mode.as_str(),
+        binding.slot.as_str(),
+        binding.sourceType.as_str(),
+    ]
+    .into_iter()
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+impl OverlayManager {
+    /// Localized name of an input origin, None when SteamVR cannot name it
+    /// 入力オリジンのローカライズ名。SteamVR が名前を返せない場合は None
+    fn origin_localized_name(&self, origin: vr::VRInputValueHandle_t) -> Option<String> {
+        let input = self.input().ok()?;
+        let get_name_fn = input.GetOriginLocalizedName?;
+        let mut buffer: [c_char; LOCALIZED_NAME_CAPACITY] = [0; LOCALIZED_NAME_CAPACITY];
+        let err = unsafe {
+            get_name_fn(
+                origin,
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+                vr::EVRInputStringBits_VRInputString_All,
+            )
+        };
+        let name = read_c_buffer(&buffer);
+        (err == vr::EVRInputError_VRInputError_None && !name.is_empty()).then_some(name)
+    }
+
+    /// Localized name of a bound `device + input` path such as `/user/hand/right/input/trigger`
+    /// `/user/hand/right/input/trigger` のようなバインド先 `デバイス + 入力` パスのローカライズ名
+    fn binding_localized_name(&self, device_path: &str, input_path: &str) -> Option<String> {
+        let input = self.input().ok()?;
+        let get_input_source_fn = input.GetInputSourceHandle?;
+        let path = to_cstring(&format!("{device_path}{input_path}"), "input source path").ok()?;
+        let mut origin: vr::VRInputValueHandle_t = vr::k_ulInvalidInputValueHandle;
+        let err = unsafe { get_input_source_fn(path.as_ptr() as *mut c_char, &mut origin) };
+        if err != vr::EVRInputError_VRInputError_None || origin == vr::k_ulInvalidInputValueHandle {
+            return None;
+        }
+        self.origin_localized_name(origin)
+    }
+
+    fn action_binding_info(&self, action: InputAction) -> napi::Result<Vec<BindingInfo>> {
+        let input = self.input()?;
+        let get_action_binding_info_fn =
+            require_fn(input.GetActionBindingInfo, "GetActionBindingInfo")?;
+        let mut bindings: [vr::InputBindingInfo_t; MAX_BINDING_INFO] =
+            unsafe { std::mem::zeroed() };
+        let mut returned_count: u32 = 0;
+
+        unsafe {
+            let err = get_action_binding_info_fn(
+                action.handle,
+                bindings.as_mut_ptr(),
+                std::mem::size_of::<vr::InputBindingInfo_t>() as u32,
+                bindings.len() as u32,
+                &mut returned_count,
+            );
+            if err != vr::EVRInputError_VRInputError_None
+                && err != vr::EVRInputError_VRInputError_BufferTooSmall
+            {
+                if is_non_fatal_binding_info_error(err) {
+                    return Ok(vec![]);
+                }
+                return Err(input_error("GetActionBindingInfo", err));
+            }
+        }
+
+        Ok(bindings
+            .iter()
+            .take(returned_count as usize)
+            .map(|info| {
+                let device_path = read_c_buffer(&info.rchDevicePathName);
+                let input_path = read_c_buffer(&info.rchInputPathName);
+                let localized_name = self.binding_localized_name(&device_path, &input_path);
+                BindingInfo {
+                    devicePath: device_path,
+                    inputPath: input_path,
+                    mode: read_c_buffer(&info.rchModeName),
+                    slot: read_c_buffer(&info.rchSlotName),
+                    sourceType: read_c_buffer(&info.rchInputSourceType),
+                    localizedName: localized_name,
+                }
+            })
+            .collect())
+    }
+
+    fn action_origins(
+        &self,
+        cache: &InputActionCache,
+        action: InputAction,
+    ) -> napi::Result<Vec<ActionOrigin>> {
+        let input = self.input()?;
+        let get_origins_fn = require_fn(input.GetActionOrigins, "GetActionOrigins")?;
+        let get_origin_info_fn = require_fn(
+            input.GetOriginTrackedDeviceInfo,
+            "GetOriginTrackedDeviceInfo",
+        )?;
+        let mut origins = [vr::k_ulInvalidInputValueHandle; vr::k_unMaxActionOriginCount as usize];
+
+        let err = unsafe {
+            get_origins_fn(
+                cache.action_set_handle,
+                action.handle,
+                origins.as_mut_ptr(),
+                origins.len() as u32,
+            )
+        };
+        if err != vr::EVRInputError_VRInputError_None {
+            if is_non_fatal_binding_info_error(err) {
+                return Ok(vec![]);
+            }
+            return Err(input_error("GetActionOrigins", err));
+        }
+
+        Ok(origins
+            .into_iter()
+            .filter(|&origin| origin != vr::k_ulInvalidInputValueHandle)
+            .map(|origin| {
+                let mut info: vr::InputOriginInfo_t = unsafe { std::mem::zeroed() };
+                let err = unsafe {
+                    get_origin_info_fn(
+                        origin,
+                        &mut info,
+                        std::mem::size_of::<vr::InputOriginInfo_t>() as u32,
+                    )
+                };
+                let known = err == vr::EVRInputError_VRInputError_None;
+                ActionOrigin {
+                    localizedName: self.origin_localized_name(origin).unwrap_or_default(),
+                    trackedDeviceIndex: (known
+                        && info.trackedDeviceIndex != vr::k_unTrackedDeviceIndexInvalid)
+                        .then_some(info.trackedDeviceIndex),
+                    renderModelComponent: if known {
+                        read_c_buffer(&info.rchRenderModelComponentName)
+                    } else {
+                        String::new()
+                    },
+                }
+            })
+            .collect())
+    }
+
+    fn action_bindings(
+        &self,
+        cache: &InputActionCache,
+        name: &str,
+        action: InputAction,
+    ) -> napi::Result<ActionBindings> {
+        Ok(ActionBindings {
+            name: name.to_string(),
+            actionType: action.action_type,
+            bindings: self.action_binding_info(action)?,
+            origins: self.action_origins(cache, action)?,
+        })
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Bindings and active origins of one action by name / 名前で指定したアクションのバインディングと有効なオリジン
+    #[napi]
+    pub fn get_action_bindings(&self, name: String) -> napi::Result<ActionBindings> {
+        let cache = self.borrow_input_cache()?;
+        if !cache.initialized {
+            return Err(napi::Error::from_reason("SteamVR input is not initialized"));
+        }
+        let action = cache
+            .action(&name)
+            .ok_or_else(|| napi::Error::from_reason(format!("unknown action: {name}")))?;
+        self.action_bindings(&cache, &name, action)
+    }
+
+    #[napi]
+    pub fn get_current_bindings(&self) -> napi::Result<CurrentBindings> {
+        let cache = self.borrow_input_cache()?;
+        if !cache.initialized {
+            return Ok(CurrentBindings {
+                initialized: false,
+                toggleOverlay: vec![],
+                triggerBindings: vec![],
+                gripBindings: vec![],
+                triggerBound: false,
+                gripBound: false,
+                actions: vec![],
+            });
+        }
+
+        let mut names: Vec<&String> = cache.actions.keys().collect();
+        names.sort();
+        // One unreadable action (e.g. not bound on this controller) must not hide the rest,
+        // nor the legacy summary fields, so it reports no bindings or origins instead
+        // 読めないアクションが1つあっても (このコントローラーで未バインドなど) 他のアクションや
+        // レガシーの要約フィールドを隠さないよう、バインディングとオリジンを空にして返す
+        let actions: Vec<_> = names
+            .into_iter()
+            .map(|name| {
+                let action = cache.actions[name];
+                self.action_bindings(&cache, name, action)
+                    .unwrap_or_else(|_| ActionBindings {
+                        name: name.to_string(),
+                        actionType: action.action_type,
+                        bindings: vec![],
+                        origins: vec![],
+                    })
+            })
+            .collect();
+
+        let labels = |name: &str| -> Vec<String> {
+            actions
+                .iter()
+                .find(|action| action.name == name)
+                .map(|action| {
+                    action
+                        .bindings
+                        .iter()
+                        .map(binding_label)
+                        .filter(|label| !label.is_empty())
+                        .collect::<BTreeSet<_>>()
+                        .into_iter()
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let toggle_labels = labels(TOGGLE_ACTION);
+        let trigger_labels = labels(TRIGGER_ACTION);
+        let grip_labels = labels(GRIP_ACTION);
+
+        let trigger_bound = !trigger_labels.is_empty();
+        let grip_bound = !grip_labels.is_empty();
+
+        Ok(CurrentBindings {
+            initialized: true,
+            toggleOverlay: toggle_labels,
+            triggerBindings: trigger_labels,
+            gripBindings: grip_labels,
+            triggerBound: trigger_bound,
+            gripBound: grip_bound,
+            actions,
+        })
+    }
+}
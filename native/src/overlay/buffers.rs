@@ -1,10 +1,10 @@
 use super::constants::BYTES_PER_PIXEL;
 
 pub(super) fn expected_rgba_size(width: u32, height: u32) -> napi::Result<usize> {
-    let width = usize::try_from(width)
-        .map_err(|_| napi::Error::from_reason("width is too large"))?;
-    let height = usize::try_from(height)
-        .map_err(|_| napi::Error::from_reason("height is too large"))?;
+    let width =
+        usize::try_from(width).map_err(|_| napi::Error::from_reason("width is too large"))?;
+    let height =
+        usize::try_from(height).map_err(|_| napi::Error::from_reason("height is too large"))?;
     width
         .checked_mul(height)
         .and_then(|v| v.checked_mul(BYTES_PER_PIXEL as usize))
@@ -0,0 +1,321 @@
+use std::collections::BTreeMap;
+
+use super::constants::{
+    BUTTON_APPLICATION_MENU, BUTTON_AXIS0, BUTTON_AXIS1, BUTTON_AXIS2, BUTTON_AXIS3, BUTTON_GRIP,
+    LEGACY_AXIS_COUNT,
+};
+use super::json::Json;
+
+/// Table name used when the controller type has no table of its own
+/// コントローラー種別専用のテーブルが無いときに使うテーブル名
+pub(super) const GENERIC_BUTTON_MAP: &str = "generic";
+
+/// Where one controller type reports each control in the legacy `VRControllerState_t`.
+/// Buttons are `EVRButtonId` bit positions and axes are `rAxis` indices; `None` means the
+/// controller has no such control.
+/// コントローラー種別ごとに、レガシーの `VRControllerState_t` のどこに各操作が現れるか。
+/// ボタンは `EVRButtonId` のビット位置、軸は `rAxis` のインデックス。`None` はその操作が無いことを表す。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct LegacyButtonMap {
+    pub trigger_button: Option<u32>,
+    pub trigger_axis: Option<usize>,
+    pub grip_button: Option<u32>,
+    pub menu_button: Option<u32>,
+    pub touchpad_button: Option<u32>,
+    pub touchpad_axis: Option<usize>,
+    pub joystick_button: Option<u32>,
+    pub joystick_axis: Option<usize>,
+}
+
+impl LegacyButtonMap {
+    /// The fixed layout used before per-type tables: touchpad on axis 0, joystick on axis 2
+    /// 種別ごとのテーブル導入前の固定レイアウト: タッチパッドが軸0、ジョイスティックが軸2
+    pub(super) const GENERIC: Self = Self {
+        trigger_button: Some(BUTTON_AXIS1),
+        trigger_axis: Some(1),
+        grip_button: Some(BUTTON_GRIP),
+        menu_button: Some(BUTTON_APPLICATION_MENU),
+        touchpad_button: Some(BUTTON_AXIS0),
+        touchpad_axis: Some(0),
+        joystick_button: Some(BUTTON_AXIS2),
+        joystick_axis: Some(2),
+    };
+
+    /// Trackpad only; axis 2 does not exist / トラックパッドのみ。軸2は存在しない
+    const VIVE: Self = Self {
+        joystick_button: None,
+        joystick_axis: None,
+        ..Self::GENERIC
+    };
+
+    /// Trackpad on axis 0, grip force on axis 2, thumbstick on axis 3
+    /// トラックパッドが軸0、グリップの握力が軸2、サムスティックが軸3
+    const KNUCKLES: Self = Self {
+        joystick_button: Some(BUTTON_AXIS3),
+        joystick_axis: Some(3),
+        ..Self::GENERIC
+    };
+
+    /// Thumbstick reported in the touchpad slot, grip on axis 2
+    /// サムスティックがタッチパッドの枠で報告され、グリップは軸2
+    const THUMBSTICK_ON_AXIS0: Self = Self {
+        touchpad_button: None,
+        touchpad_axis: None,
+        joystick_button: Some(BUTTON_AXIS0),
+        joystick_axis: Some(0),
+        ..Self::GENERIC
+    };
+
+    /// Whether `button` is set in a `ulButtonPressed` mask / `ulButtonPressed` のマスクで `button` が立っているか
+    fn pressed(button: Option<u32>, buttons: u64) -> bool {
+        button.is_some_and(|bit| buttons & (1u64 << bit) != 0)
+    }
+
+    fn axis(axis: Option<usize>, axes: &[(f64, f64)]) -> (f64, f64) {
+        axis.and_then(|index| axes.get(index).copied())
+            .unwrap_or((0.0, 0.0))
+    }
+
+    pub(super) fn read(&self, buttons: u64, axes: &[(f64, f64)]) -> LegacyReading {
+        LegacyReading {
+            trigger_pressed: Self::pressed(self.trigger_button, buttons),
+            trigger_value: Self::axis(self.trigger_axis, axes).0,
+            grip_pressed: Self::pressed(self.grip_button, buttons),
+            menu_pressed: Self::pressed(self.menu_button, buttons),
+            touchpad_pressed: Self::pressed(self.touchpad_button, buttons),
+            touchpad: Self::axis(self.touchpad_axis, axes),
+            joystick_pressed: Self::pressed(self.joystick_button, buttons),
+            joystick: Self::axis(self.joystick_axis, axes),
+        }
+    }
+}
+
+/// One legacy controller reading decoded through a `LegacyButtonMap`
+/// `LegacyButtonMap` で解釈したレガシーのコントローラー読み取り値
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(super) struct LegacyReading {
+    pub trigger_pressed: bool,
+    pub trigger_value: f64,
+    pub grip_pressed: bool,
+    pub menu_pressed: bool,
+    pub touchpad_pressed: bool,
+    pub touchpad: (f64, f64),
+    pub joystick_pressed: bool,
+    pub joystick: (f64, f64),
+}
+
+/// Built-in tables keyed by `Prop_ControllerType_String` / `Prop_ControllerType_String` をキーにした組み込みテーブル
+const BUILTIN_BUTTON_MAPS: [(&str, LegacyButtonMap); 6] = [
+    ("vive_controller", LegacyButtonMap::VIVE),
+    ("knuckles", LegacyButtonMap::KNUCKLES),
+    ("holographic_controller", LegacyButtonMap::GENERIC),
+    (
+        "vive_cosmos_controller",
+        LegacyButtonMap::THUMBSTICK_ON_AXIS0,
+    ),
+    ("oculus_touch", LegacyButtonMap::THUMBSTICK_ON_AXIS0),
+    ("pico_controller", LegacyButtonMap::THUMBSTICK_ON_AXIS0),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ButtonMapSource {
+    Generic,
+    BuiltIn,
+    Config,
+}
+
+/// The table chosen for one controller / コントローラー1台に選ばれたテーブル
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct ResolvedButtonMap {
+    pub name: String,
+    pub source: ButtonMapSource,
+    pub map: LegacyButtonMap,
+}
+
+/// Built-in tables plus the overrides loaded from a config file
+/// 組み込みテーブルと設定ファイルから読み込んだ上書き
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct LegacyButtonMaps {
+    overrides: BTreeMap<String, LegacyButtonMap>,
+}
+
+impl LegacyButtonMaps {
+    pub(super) fn builtin(controller_type: &str) -> Option<LegacyButtonMap> {
+        BUILTIN_BUTTON_MAPS
+            .iter()
+            .find(|(name, _)| *name == controller_type)
+            .map(|(_, map)| *map)
+    }
+
+    pub(super) fn resolve(&self, controller_type: Option<&str>) -> ResolvedButtonMap {
+        let Some(controller_type) = controller_type else {
+            return ResolvedButtonMap {
+                name: GENERIC_BUTTON_MAP.to_string(),
+                source: ButtonMapSource::Generic,
+                map: LegacyButtonMap::GENERIC,
+            };
+        };
+        let (source, map) = if let Some(map) = self.overrides.get(controller_type) {
+            (ButtonMapSource::Config, *map)
+        } else if let Some(map) = Self::builtin(controller_type) {
+            (ButtonMapSource::BuiltIn, map)
+        } else {
+            return self.resolve(None);
+        };
+        ResolvedButtonMap {
+            name: controller_type.to_string(),
+            source,
+            map,
+        }
+    }
+
+    pub(super) fn overridden_types(&self) -> Vec<String> {
+        self.overrides.keys().cloned().collect()
+    }
+
+    /// Parse a config file of the form `{ "<controller type>": { "joystickAxis": 3, ... } }`.
+    /// Fields left out keep the built-in (or generic) value and `null` removes a control.
+    /// `{ "<コントローラー種別>": { "joystickAxis": 3, ... } }` 形式の設定ファイルを解析する。
+    /// 省略したフィールドは組み込み (または汎用) の値を保ち、`null` はその操作を無効にする。
+    pub(super) fn parse(text: &str) -> Result<Self, String> {
+        let root = Json::parse(text)?;
+        let Json::Object(entries) = root else {
+            return Err("legacy button map config must be an object".to_string());
+        };
+        let mut overrides = BTreeMap::new();
+        for (controller_type, fields) in &entries {
+            let Json::Object(fields) = fields else {
+                return Err(format!("{controller_type}: expected an object"));
+            };
+            let mut map = Self::builtin(controller_type).unwrap_or(LegacyButtonMap::GENERIC);
+            for (field, value) in fields {
+                let context = || format!("{controller_type}.{field}");
+                match field.as_str() {
+                    "triggerButton" => map.trigger_button = parse_button(value, context)?,
+                    "triggerAxis" => map.trigger_axis = parse_axis(value, context)?,
+                    "gripButton" => map.grip_button = parse_button(value, context)?,
+                    "menuButton" => map.menu_button = parse_button(value, context)?,
+                    "touchpadButton" => map.touchpad_button = parse_button(value, context)?,
+                    "touchpadAxis" => map.touchpad_axis = parse_axis(value, context)?,
+                    "joystickButton" => map.joystick_button = parse_button(value, context)?,
+                    "joystickAxis" => map.joystick_axis = parse_axis(value, context)?,
+                    _ => return Err(format!("{}: unknown field", context())),
+                }
+            }
+            overrides.insert(controller_type.clone(), map);
+        }
+        Ok(Self { overrides })
+    }
+}
+
+fn parse_index(
+    value: &Json,
+    limit: usize,
+    context: impl Fn() -> String,
+) -> Result<Option<usize>, String> {
+    match value {
+        Json::Null => Ok(None),
+        Json::Number(n) if n.fract() == 0.0 && *n >= 0.0 && *n < limit as f64 => {
+            Ok(Some(*n as usize))
+        }
+        _ => Err(format!(
+            "{}: expected null or an integer within 0..{limit}",
+            context()
+        )),
+    }
+}
+
+fn parse_button(value: &Json, context: impl Fn() -> String) -> Result<Option<u32>, String> {
+    Ok(parse_index(value, u64::BITS as usize, context)?.map(|bit| bit as u32))
+}
+
+fn parse_axis(value: &Json, context: impl Fn() -> String) -> Result<Option<usize>, String> {
+    parse_index(value, LEGACY_AXIS_COUNT, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AXES: [(f64, f64); LEGACY_AXIS_COUNT] =
+        [(0.1, 0.2), (0.9, 0.0), (0.5, 0.0), (-0.3, 0.4), (0.0, 0.0)];
+
+    #[test]
+    fn knuckles_reads_thumbstick_from_axis3_not_grip_force() {
+        let maps = LegacyButtonMaps::default();
+        let resolved = maps.resolve(Some("knuckles"));
+        assert_eq!(resolved.source, ButtonMapSource::BuiltIn);
+        let reading = resolved.map.read(1 << BUTTON_AXIS3, &AXES);
+        assert!(reading.joystick_pressed);
+        assert_eq!(reading.joystick, (-0.3, 0.4));
+        assert_eq!(reading.touchpad, (0.1, 0.2));
+        assert_eq!(reading.trigger_value, 0.9);
+    }
+
+    #[test]
+    fn thumbstick_controllers_report_no_touchpad() {
+        let maps = LegacyButtonMaps::default();
+        let reading = maps
+            .resolve(Some("oculus_touch"))
+            .map
+            .read(1 << BUTTON_AXIS0, &AXES);
+        assert!(reading.joystick_pressed);
+        assert!(!reading.touchpad_pressed);
+        assert_eq!(reading.joystick, (0.1, 0.2));
+        assert_eq!(reading.touchpad, (0.0, 0.0));
+    }
+
+    #[test]
+    fn unknown_types_fall_back_to_generic() {
+        let maps = LegacyButtonMaps::default();
+        for controller_type in [None, Some("vive_tracker_handed")] {
+            let resolved = maps.resolve(controller_type);
+            assert_eq!(resolved.name, GENERIC_BUTTON_MAP);
+            assert_eq!(resolved.source, ButtonMapSource::Generic);
+            assert_eq!(resolved.map, LegacyButtonMap::GENERIC);
+        }
+    }
+
+    #[test]
+    fn config_overrides_merge_onto_builtin_tables() {
+        let maps = LegacyButtonMaps::parse(
+            r#"{ "holographic_controller": { "joystickButton": null, "joystickAxis": 3 },
+                 "gamepad": { "menuButton": 0 } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            maps.overridden_types(),
+            ["gamepad", "holographic_controller"]
+        );
+
+        let resolved = maps.resolve(Some("holographic_controller"));
+        assert_eq!(resolved.source, ButtonMapSource::Config);
+        assert_eq!(
+            resolved.map,
+            LegacyButtonMap {
+                joystick_button: None,
+                joystick_axis: Some(3),
+                ..LegacyButtonMap::GENERIC
+            }
+        );
+        assert_eq!(
+            maps.resolve(Some("gamepad")).map.menu_button,
+            Some(0),
+            "types without a built-in table start from the generic layout"
+        );
+    }
+
+    #[test]
+    fn config_rejects_bad_fields() {
+        for text in [
+            "[]",
+            r#"{ "knuckles": 1 }"#,
+            r#"{ "knuckles": { "thumbAxis": 0 } }"#,
+            r#"{ "knuckles": { "joystickAxis": 5 } }"#,
+            r#"{ "knuckles": { "gripButton": 64 } }"#,
+            r#"{ "knuckles": { "gripButton": 1.5 } }"#,
+        ] {
+            assert!(LegacyButtonMaps::parse(text).is_err(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use napi_derive::napi;
+use openvr_sys as vr;
+
+use super::button_map::{ButtonMapSource, LegacyButtonMaps, ResolvedButtonMap};
+use super::constants::LEGACY_AXIS_COUNT;
+use super::manager::OverlayManager;
+use super::types::{LegacyButtonMapInfo, LegacyButtonMapSource};
+
+/// `rAxis` as (x, y) pairs / `rAxis` を (x, y) の組にしたもの
+pub(super) fn legacy_axes(state: &vr::VRControllerState_t) -> [(f64, f64); LEGACY_AXIS_COUNT] {
+    std::array::from_fn(|i| (state.rAxis[i].x as f64, state.rAxis[i].y as f64))
+}
+
+impl ButtonMapSource {
+    pub(super) fn to_napi(self) -> LegacyButtonMapSource {
+        match self {
+            ButtonMapSource::Generic => LegacyButtonMapSource::Generic,
+            ButtonMapSource::BuiltIn => LegacyButtonMapSource::BuiltIn,
+            ButtonMapSource::Config => LegacyButtonMapSource::Config,
+        }
+    }
+}
+
+impl ResolvedButtonMap {
+    fn into_napi(self) -> LegacyButtonMapInfo {
+        let axis = |axis: Option<usize>| axis.map(|index| index as u32);
+        LegacyButtonMapInfo {
+            name: self.name,
+            source: self.source.to_napi(),
+            triggerButton: self.map.trigger_button,
+            triggerAxis: axis(self.map.trigger_axis),
+            gripButton: self.map.grip_button,
+            menuButton: self.map.menu_button,
+            touchpadButton: self.map.touchpad_button,
+            touchpadAxis: axis(self.map.touchpad_axis),
+            joystickButton: self.map.joystick_button,
+            joystickAxis: axis(self.map.joystick_axis),
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Table for the device's `Prop_ControllerType_String` / デバイスの `Prop_ControllerType_String` に対応するテーブル
+    pub(super) fn controller_button_map(&self, index: u32) -> napi::Result<ResolvedButtonMap> {
+        let controller_type = self
+            .string_device_property(index, vr::ETrackedDeviceProperty_Prop_ControllerType_String)?;
+        Ok(self
+            .borrow_legacy_button_maps()?
+            .resolve(controller_type.as_deref()))
+    }
+
+    fn replace_legacy_button_maps(&self, maps: LegacyButtonMaps) -> napi::Result<()> {
+        self.update_input_poller(|config| config.button_maps = maps.clone())?;
+        *self.borrow_legacy_button_maps_mut()? = maps;
+        Ok(())
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Override the built-in legacy button maps from a JSON config file. Returns the
+    /// controller types it overrides; a parse error leaves the current maps in place.
+    /// JSON の設定ファイルで組み込みのレガシーボタンマップを上書きする。上書きした
+    /// コントローラー種別を返す。解析エラー時は現在のマップをそのまま保つ。
+    #[napi]
+    pub fn load_legacy_button_maps(&self, path: String) -> napi::Result<Vec<String>> {
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| napi::Error::from_reason(format!("Failed to read {path}: {e}")))?;
+        let maps = LegacyButtonMaps::parse(&text)
+            .map_err(|e| napi::Error::from_reason(format!("{path}: {e}")))?;
+        let types = maps.overridden_types();
+        self.replace_legacy_button_maps(maps)?;
+        Ok(types)
+    }
+
+    /// Drop config overrides and use the built-in tables only / 設定の上書きを捨てて組み込みテーブルのみを使う
+    #[napi]
+    pub fn clear_legacy_button_maps(&self) -> napi::Result<()> {
+        self.replace_legacy_button_maps(LegacyButtonMaps::default())
+    }
+
+    /// Map that applies to a `Prop_ControllerType_String` value / `Prop_ControllerType_String` の値に適用されるマップ
+    #[napi]
+    pub fn get_legacy_button_map(
+        &self,
+        controller_type: String,
+    ) -> napi::Result<LegacyButtonMapInfo> {
+        Ok(self
+            .borrow_legacy_button_maps()?
+            .resolve(Some(&controller_type))
+            .into_napi())
+    }
+
+    /// Map applied to a connected controller / 接続中のコントローラーに適用されるマップ
+    #[napi]
+    pub fn get_controller_button_map(&self, index: u32) -> napi::Result<LegacyButtonMapInfo> {
+        if index >= vr::k_unMaxTrackedDeviceCount {
+            return Err(napi::Error::from_reason("Invalid device index"));
+        }
+        Ok(self.controller_button_map(index)?.into_napi())
+    }
+}
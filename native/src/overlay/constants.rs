@@ -6,14 +6,29 @@ pub(super) const DEFAULT_SYSTEM_INTERFACE: &str = "FnTable:IVRSystem_023";
 pub(super) const DEFAULT_INPUT_INTERFACE: &str = "FnTable:IVRInput_010";
 pub(super) const BYTES_PER_PIXEL: u32 = 4;
 pub(super) const HMD_DEVICE_INDEX: u32 = 0;
+pub(super) const MAX_PREDICTED_SECONDS: f64 = 0.1;
+pub(super) const TOGGLE_RELEASE_STREAK_TO_UNLOCK: u8 = 3;
 
-// Controller button bitmasks / コントローラーボタンのビットマスク
-pub(super) const BUTTON_TRIGGER: u64 = 1u64 << 33; // k_EButton_SteamVR_Trigger
-pub(super) const BUTTON_GRIP: u64 = 1u64 << 2; // k_EButton_Grip
-pub(super) const BUTTON_TOUCHPAD: u64 = 1u64 << 32; // k_EButton_SteamVR_Touchpad
-pub(super) const BUTTON_JOYSTICK: u64 = 1u64 << 34; // k_EButton_Axis2 (often joystick click) / k_EButton_Axis2 (多くの場合ジョイスティックのクリック)
+// Built-in SteamVR Input actions / 組み込みの SteamVR Input アクション
+pub(super) const DEFAULT_ACTION_SET_PATH: &str = "/actions/vrkb2";
+pub(super) const TOGGLE_ACTION: &str = "toggle_overlay";
+pub(super) const TRIGGER_ACTION: &str = "trigger_click";
+pub(super) const GRIP_ACTION: &str = "grip_click";
+pub(super) const TRIGGER_PULL_ACTION: &str = "trigger_pull";
+pub(super) const SCROLL_ACTION: &str = "scroll";
+pub(super) const JOYSTICK_ACTION: &str = "joystick";
+pub(super) const AIM_LEFT_ACTION: &str = "aim_left";
+pub(super) const AIM_RIGHT_ACTION: &str = "aim_right";
+pub(super) const SKELETON_LEFT_ACTION: &str = "skeleton_left";
+pub(super) const SKELETON_RIGHT_ACTION: &str = "skeleton_right";
 
-// Controller axis indices / コントローラー軸のインデックス
-pub(super) const AXIS_TRIGGER: usize = 1;
-pub(super) const AXIS_TOUCHPAD: usize = 0;
-pub(super) const AXIS_JOYSTICK: usize = 2;
+// Legacy `EVRButtonId` bit positions in `ulButtonPressed` / `ulButtonPressed` のレガシー `EVRButtonId` ビット位置
+pub(super) const BUTTON_APPLICATION_MENU: u32 = 1; // k_EButton_ApplicationMenu
+pub(super) const BUTTON_GRIP: u32 = 2; // k_EButton_Grip
+pub(super) const BUTTON_AXIS0: u32 = 32; // k_EButton_Axis0 (k_EButton_SteamVR_Touchpad)
+pub(super) const BUTTON_AXIS1: u32 = 33; // k_EButton_Axis1 (k_EButton_SteamVR_Trigger)
+pub(super) const BUTTON_AXIS2: u32 = 34; // k_EButton_Axis2
+pub(super) const BUTTON_AXIS3: u32 = 35; // k_EButton_Axis3
+
+/// Length of `VRControllerState_t::rAxis` / `VRControllerState_t::rAxis` の長さ
+pub(super) const LEGACY_AXIS_COUNT: usize = 5;
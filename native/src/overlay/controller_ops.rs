@@ -1,16 +1,18 @@
 use napi_derive::napi;
 use openvr_sys as vr;
 
+use super::action_ops::ResolvedAction;
+use super::button_map::ResolvedButtonMap;
+use super::button_map_ops::legacy_axes;
 use super::constants::{
-    AXIS_JOYSTICK, AXIS_TOUCHPAD, AXIS_TRIGGER, BUTTON_GRIP, BUTTON_JOYSTICK, BUTTON_TOUCHPAD,
-    BUTTON_TRIGGER,
+    GRIP_ACTION, JOYSTICK_ACTION, SCROLL_ACTION, TRIGGER_ACTION, TRIGGER_PULL_ACTION,
 };
 use super::errors::require_fn;
-use super::manager::OverlayManager;
+use super::manager::{InputActionCache, OverlayManager};
 use super::math::hmd_matrix34_to_vec;
-use super::types::ControllerState;
+use super::types::{ControllerState, InputActionType};
 
-fn empty_controller_state() -> ControllerState {
+fn empty_controller_state(button_map: &ResolvedButtonMap) -> ControllerState {
     ControllerState {
         triggerPressed: false,
         triggerValue: 0.0,
@@ -21,6 +23,41 @@ fn empty_controller_state() -> ControllerState {
         joystickPressed: false,
         joystickX: 0.0,
         joystickY: 0.0,
+        // Only SteamVR Input reports scroll / スクロールは SteamVR Input のみが報告する
+        scrollX: 0.0,
+        scrollY: 0.0,
+        buttonMap: button_map.name.clone(),
+        buttonMapSource: button_map.source.to_napi(),
+    }
+}
+
+impl OverlayManager {
+    /// First active reading of an analog action across `sources`; None if it is not
+    /// configured, not analog, or unbound on this controller.
+    /// `sources` のうち最初に有効なアナログアクションの値。未設定・非アナログ・
+    /// このコントローラーで未バインドなら None。
+    fn read_bound_analog(
+        &self,
+        cache: &InputActionCache,
+        name: &str,
+        sources: &[vr::VRInputValueHandle_t],
+    ) -> Option<vr::InputAnalogActionData_t> {
+        let action = cache.action(name)?;
+        if !matches!(
+            action.action_type,
+            InputActionType::Vector1 | InputActionType::Vector2
+        ) {
+            return None;
+        }
+        sources.iter().find_map(|&source| {
+            match self.read_analog_action(ResolvedAction {
+                handle: action.handle,
+                source,
+            }) {
+                Ok(data) if data.bActive => Some(data),
+                _ => None,
+            }
+        })
     }
 }
 
@@ -49,39 +86,13 @@ impl OverlayManager {
             return Err(napi::Error::from_reason("Invalid device index"));
         }
 
-        // Check if cached poses are still fresh (within TTL) / キャッシュされたポーズがまだ新鮮か確認 (TTL以内)
-        let cache_hit = self.poses_cache_valid();
-        let mut poses = self.borrow_poses_cache()?;
-        let pose_count = vr::k_unMaxTrackedDeviceCount as usize;
-        debug_assert_eq!(poses.len(), pose_count);
-        if poses.len() != pose_count {
-            poses.resize_with(pose_count, || unsafe { std::mem::zeroed() });
+        let poses = self.fresh_poses()?;
+        let pose = &poses[index as usize];
+        if !pose.bPoseIsValid || !pose.bDeviceIsConnected {
+            return Ok(vec![]); // Valid but not tracking/connected / 有効だが未トラッキング or 未接続
         }
 
-        unsafe {
-            if !cache_hit {
-                // Cache miss: fetch all poses from OpenVR API / キャッシュミス: OpenVR APIから全ポーズを取得
-                let system = self.system()?;
-                let get_pose_fn = require_fn(
-                    system.GetDeviceToAbsoluteTrackingPose,
-                    "GetDeviceToAbsoluteTrackingPose",
-                )?;
-                get_pose_fn(
-                    vr::ETrackingUniverseOrigin_TrackingUniverseStanding,
-                    0.0,
-                    poses.as_mut_ptr(),
-                    vr::k_unMaxTrackedDeviceCount,
-                );
-                self.mark_poses_cache();
-            }
-
-            let pose = &poses[index as usize];
-            if !pose.bPoseIsValid || !pose.bDeviceIsConnected {
-                return Ok(vec![]); // Valid but not tracking/connected / 有効だが未トラッキング or 未接続
-            }
-
-            Ok(hmd_matrix34_to_vec(&pose.mDeviceToAbsoluteTracking.m))
-        }
+        Ok(hmd_matrix34_to_vec(&pose.mDeviceToAbsoluteTracking.m))
     }
 
     #[napi]
@@ -93,6 +104,9 @@ impl OverlayManager {
         if controller_index >= vr::k_unMaxTrackedDeviceCount {
             return Err(napi::Error::from_reason("Invalid device index"));
         }
+        // Raw button bits and axis slots differ per controller type
+        // 生のボタンビットと軸スロットはコントローラー種別ごとに異なる
+        let button_map = self.controller_button_map(controller_index)?;
 
         unsafe {
             let mut state: vr::VRControllerState_t = std::mem::zeroed();
@@ -102,59 +116,64 @@ impl OverlayManager {
                 std::mem::size_of::<vr::VRControllerState_t>() as u32,
             );
 
-            let mut result = if !success {
-                empty_controller_state()
-            } else {
-                ControllerState {
-                    triggerPressed: (state.ulButtonPressed & BUTTON_TRIGGER) != 0,
-                    triggerValue: state.rAxis[AXIS_TRIGGER].x as f64,
-                    gripPressed: (state.ulButtonPressed & BUTTON_GRIP) != 0,
-                    touchpadPressed: (state.ulButtonPressed & BUTTON_TOUCHPAD) != 0,
-                    touchpadX: state.rAxis[AXIS_TOUCHPAD].x as f64,
-                    touchpadY: state.rAxis[AXIS_TOUCHPAD].y as f64,
-                    joystickPressed: (state.ulButtonPressed & BUTTON_JOYSTICK) != 0,
-                    joystickX: state.rAxis[AXIS_JOYSTICK].x as f64,
-                    joystickY: state.rAxis[AXIS_JOYSTICK].y as f64,
-                }
-            };
+            let mut result = empty_controller_state(&button_map);
+            if success {
+                let reading = button_map
+                    .map
+                    .read(state.ulButtonPressed, &legacy_axes(&state));
+                result.triggerPressed = reading.trigger_pressed;
+                result.triggerValue = reading.trigger_value;
+                result.gripPressed = reading.grip_pressed;
+                result.touchpadPressed = reading.touchpad_pressed;
+                (result.touchpadX, result.touchpadY) = reading.touchpad;
+                result.joystickPressed = reading.joystick_pressed;
+                (result.joystickX, result.joystickY) = reading.joystick;
+            }
 
             // SteamVR Input integration:
-            // Read action data from the most recent UpdateActionState (called by poll_toggle_clicked).
+            // Read action data from the most recent UpdateActionState (called by
+            // poll_toggle_gesture, update_switch_scan or update_input_state).
             // SteamVR Input 統合:
-            // poll_toggle_clicked で呼ばれた最新の UpdateActionState の結果からアクションデータを読み取る。
+            // poll_toggle_gesture・update_switch_scan・update_input_state で呼ばれた
+            // 最新の UpdateActionState の結果からアクションデータを読み取る。
             if let Ok(input) = self.input() {
                 let cache = self.borrow_input_cache()?;
                 if cache.initialized {
-                    if let Some(get_digital_action_data_fn) = input.GetDigitalActionData {
-                        let preferred_source = if let Some(get_role_fn) = get_role_fn {
-                            match get_role_fn(controller_index) {
-                                vr::ETrackedControllerRole_TrackedControllerRole_LeftHand => {
-                                    cache.left_hand_source
-                                }
-                                vr::ETrackedControllerRole_TrackedControllerRole_RightHand => {
-                                    cache.right_hand_source
-                                }
-                                _ => vr::k_ulInvalidInputValueHandle,
+                    let preferred_source = if let Some(get_role_fn) = get_role_fn {
+                        match get_role_fn(controller_index) {
+                            vr::ETrackedControllerRole_TrackedControllerRole_LeftHand => {
+                                cache.left_hand_source
+                            }
+                            vr::ETrackedControllerRole_TrackedControllerRole_RightHand => {
+                                cache.right_hand_source
                             }
-                        } else {
-                            vr::k_ulInvalidInputValueHandle
-                        };
-
-                        let sources = [preferred_source, vr::k_ulInvalidInputValueHandle];
-                        let source_count = if preferred_source == vr::k_ulInvalidInputValueHandle {
-                            1
-                        } else {
-                            2
-                        };
-
-                        let mut trigger_overridden = false;
-                        let mut grip_overridden = false;
-                        for source in sources.iter().take(source_count).copied() {
-                            if !trigger_overridden {
+                            _ => vr::k_ulInvalidInputValueHandle,
+                        }
+                    } else {
+                        vr::k_ulInvalidInputValueHandle
+                    };
+
+                    let sources = [preferred_source, vr::k_ulInvalidInputValueHandle];
+                    let source_count = if preferred_source == vr::k_ulInvalidInputValueHandle {
+                        1
+                    } else {
+                        2
+                    };
+                    let sources = &sources[..source_count];
+
+                    if let Some(get_digital_action_data_fn) = input.GetDigitalActionData {
+                        // Actions left out of the configured set keep the legacy value
+                        // 設定されたアクションセットに無いアクションはレガシーの値のまま
+                        let trigger_action = cache.action(TRIGGER_ACTION);
+                        let grip_action = cache.action(GRIP_ACTION);
+                        let mut trigger_overridden = trigger_action.is_none();
+                        let mut grip_overridden = grip_action.is_none();
+                        for source in sources.iter().copied() {
+                            if let (false, Some(action)) = (trigger_overridden, trigger_action) {
                                 let mut trigger_data: vr::InputDigitalActionData_t =
                                     std::mem::zeroed();
                                 let trigger_err = get_digital_action_data_fn(
-                                    cache.trigger_action_handle,
+                                    action.handle,
                                     &mut trigger_data,
                                     std::mem::size_of::<vr::InputDigitalActionData_t>() as u32,
                                     source,
@@ -167,11 +186,11 @@ impl OverlayManager {
                                 }
                             }
 
-                            if !grip_overridden {
+                            if let (false, Some(action)) = (grip_overridden, grip_action) {
                                 let mut grip_data: vr::InputDigitalActionData_t =
                                     std::mem::zeroed();
                                 let grip_err = get_digital_action_data_fn(
-                                    cache.grip_action_handle,
+                                    action.handle,
                                     &mut grip_data,
                                     std::mem::size_of::<vr::InputDigitalActionData_t>() as u32,
                                     source,
@@ -189,6 +208,21 @@ impl OverlayManager {
                             }
                         }
                     }
+
+                    // Analog actions replace the fixed rAxis slots, which differ per controller type
+                    // アナログアクションはコントローラー種別ごとに異なる固定の rAxis スロットを置き換える
+                    if let Some(data) = self.read_bound_analog(&cache, TRIGGER_PULL_ACTION, sources)
+                    {
+                        result.triggerValue = data.x as f64;
+                    }
+                    if let Some(data) = self.read_bound_analog(&cache, JOYSTICK_ACTION, sources) {
+                        result.joystickX = data.x as f64;
+                        result.joystickY = data.y as f64;
+                    }
+                    if let Some(data) = self.read_bound_analog(&cache, SCROLL_ACTION, sources) {
+                        result.scrollX = data.x as f64;
+                        result.scrollY = data.y as f64;
+                    }
                 }
             }
 
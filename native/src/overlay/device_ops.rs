@@ -0,0 +1,411 @@
+use napi_derive::napi;
+use openvr_sys as vr;
+use std::ffi::c_char;
+
+use super::errors::{read_c_buffer, require_fn};
+use super::manager::OverlayManager;
+use super::types::{
+    ControllerType, DeviceInfo, TrackedControllerRole, TrackedDeviceClass, TrackedDeviceSelector,
+};
+
+/// Initial buffer for string properties; grown on TrackedProp_BufferTooSmall
+/// 文字列プロパティの初期バッファ。TrackedProp_BufferTooSmall の場合は拡張する
+const DEVICE_STRING_PROPERTY_BUFFER: usize = 256;
+
+/// Resolved form of `TrackedDeviceSelector` kept by overlay attachments
+/// オーバーレイのアタッチメントが保持する `TrackedDeviceSelector` の解決済み形式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum DeviceSelector {
+    Index(u32),
+    Role(TrackedControllerRole),
+    Class {
+        class: TrackedDeviceClass,
+        ordinal: u32,
+    },
+}
+
+impl DeviceSelector {
+    pub(super) fn from_napi(selector: &TrackedDeviceSelector) -> napi::Result<Self> {
+        let ordinal = selector.ordinal.unwrap_or(0);
+        match (selector.deviceIndex, selector.role, selector.deviceClass) {
+            (Some(index), None, None) => {
+                if index >= vr::k_unMaxTrackedDeviceCount {
+                    return Err(napi::Error::from_reason("Invalid device index"));
+                }
+                Ok(Self::Index(index))
+            }
+            (None, Some(role), None) => Ok(Self::Role(role)),
+            (None, None, Some(class)) => Ok(Self::Class { class, ordinal }),
+            _ => Err(napi::Error::from_reason(
+                "Device selector must set exactly one of deviceIndex, role or deviceClass",
+            )),
+        }
+    }
+}
+
+impl TrackedDeviceClass {
+    pub(super) fn to_vr(self) -> vr::ETrackedDeviceClass {
+        match self {
+            Self::Invalid => vr::ETrackedDeviceClass_TrackedDeviceClass_Invalid,
+            Self::Hmd => vr::ETrackedDeviceClass_TrackedDeviceClass_HMD,
+            Self::Controller => vr::ETrackedDeviceClass_TrackedDeviceClass_Controller,
+            Self::GenericTracker => vr::ETrackedDeviceClass_TrackedDeviceClass_GenericTracker,
+            Self::TrackingReference => vr::ETrackedDeviceClass_TrackedDeviceClass_TrackingReference,
+            Self::DisplayRedirect => vr::ETrackedDeviceClass_TrackedDeviceClass_DisplayRedirect,
+        }
+    }
+}
+
+impl TrackedDeviceClass {
+    pub(super) fn from_vr(device_class: vr::ETrackedDeviceClass) -> Self {
+        match device_class {
+            vr::ETrackedDeviceClass_TrackedDeviceClass_HMD => Self::Hmd,
+            vr::ETrackedDeviceClass_TrackedDeviceClass_Controller => Self::Controller,
+            vr::ETrackedDeviceClass_TrackedDeviceClass_GenericTracker => Self::GenericTracker,
+            vr::ETrackedDeviceClass_TrackedDeviceClass_TrackingReference => Self::TrackingReference,
+            vr::ETrackedDeviceClass_TrackedDeviceClass_DisplayRedirect => Self::DisplayRedirect,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl TrackedControllerRole {
+    pub(super) fn from_vr(role: vr::ETrackedControllerRole) -> Self {
+        match role {
+            vr::ETrackedControllerRole_TrackedControllerRole_LeftHand => Self::LeftHand,
+            vr::ETrackedControllerRole_TrackedControllerRole_RightHand => Self::RightHand,
+            vr::ETrackedControllerRole_TrackedControllerRole_OptOut => Self::OptOut,
+            vr::ETrackedControllerRole_TrackedControllerRole_Treadmill => Self::Treadmill,
+            vr::ETrackedControllerRole_TrackedControllerRole_Stylus => Self::Stylus,
+            _ => Self::Invalid,
+        }
+    }
+
+    pub(super) fn to_vr(self) -> vr::ETrackedControllerRole {
+        match self {
+            Self::Invalid => vr::ETrackedControllerRole_TrackedControllerRole_Invalid,
+            Self::LeftHand => vr::ETrackedControllerRole_TrackedControllerRole_LeftHand,
+            Self::RightHand => vr::ETrackedControllerRole_TrackedControllerRole_RightHand,
+            Self::OptOut => vr::ETrackedControllerRole_TrackedControllerRole_OptOut,
+            Self::Treadmill => vr::ETrackedControllerRole_TrackedControllerRole_Treadmill,
+            Self::Stylus => vr::ETrackedControllerRole_TrackedControllerRole_Stylus,
+        }
+    }
+}
+
+impl ControllerType {
+    pub(super) fn from_prop(controller_type: &str) -> Self {
+        match controller_type {
+            "vive_controller" => Self::ViveController,
+            "knuckles" => Self::Knuckles,
+            "oculus_touch" => Self::OculusTouch,
+            "holographic_controller" => Self::HolographicController,
+            "vive_cosmos_controller" => Self::ViveCosmosController,
+            "pico_controller" => Self::PicoController,
+            "gamepad" => Self::Gamepad,
+            name if name.starts_with("vive_tracker") => Self::ViveTracker,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// String device property, `None` if missing or empty. Takes the function table so the
+/// input poller thread can share it with the manager.
+/// 文字列プロパティ。無いか空なら `None`。入力ポーラーのスレッドとマネージャーで共有できる
+/// よう関数テーブルを受け取る。
+pub(super) fn read_string_device_property(
+    system: &vr::VR_IVRSystem_FnTable,
+    index: u32,
+    prop: vr::ETrackedDeviceProperty,
+) -> napi::Result<Option<String>> {
+    let get_string_fn = require_fn(
+        system.GetStringTrackedDeviceProperty,
+        "GetStringTrackedDeviceProperty",
+    )?;
+    let mut buffer: Vec<c_char> = vec![0; DEVICE_STRING_PROPERTY_BUFFER];
+    let mut err = vr::ETrackedPropertyError_TrackedProp_Success;
+    unsafe {
+        let required = get_string_fn(
+            index,
+            prop,
+            buffer.as_mut_ptr(),
+            buffer.len() as u32,
+            &mut err,
+        );
+        if err == vr::ETrackedPropertyError_TrackedProp_BufferTooSmall {
+            let required = (required as usize).min(vr::k_unMaxPropertyStringSize as usize);
+            buffer.resize(required, 0);
+            err = vr::ETrackedPropertyError_TrackedProp_Success;
+            get_string_fn(
+                index,
+                prop,
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+                &mut err,
+            );
+        }
+    }
+    if err != vr::ETrackedPropertyError_TrackedProp_Success {
+        return Ok(None);
+    }
+    let value = read_c_buffer(&buffer);
+    Ok((!value.is_empty()).then_some(value))
+}
+
+/// Float device property, `None` if the device does not report it. Takes the function
+/// table like `read_string_device_property`.
+/// float 型のデバイスプロパティ。デバイスが報告しなければ `None`。
+/// `read_string_device_property` と同様に関数テーブルを受け取る。
+pub(super) fn read_float_device_property(
+    system: &vr::VR_IVRSystem_FnTable,
+    index: u32,
+    prop: vr::ETrackedDeviceProperty,
+) -> napi::Result<Option<f32>> {
+    let get_float_fn = require_fn(
+        system.GetFloatTrackedDeviceProperty,
+        "GetFloatTrackedDeviceProperty",
+    )?;
+    let mut err = vr::ETrackedPropertyError_TrackedProp_Success;
+    let value = unsafe { get_float_fn(index, prop, &mut err) };
+    Ok((err == vr::ETrackedPropertyError_TrackedProp_Success).then_some(value))
+}
+
+impl OverlayManager {
+    /// Connected device indices of a class in index order / 指定クラスの接続済みデバイスをインデックス順に返す
+    pub(super) fn connected_devices_of_class(
+        &self,
+        device_class: vr::ETrackedDeviceClass,
+    ) -> napi::Result<Vec<u32>> {
+        let system = self.system()?;
+        let get_class_fn = require_fn(system.GetTrackedDeviceClass, "GetTrackedDeviceClass")?;
+        let is_connected_fn =
+            require_fn(system.IsTrackedDeviceConnected, "IsTrackedDeviceConnected")?;
+        let mut devices = Vec::new();
+        unsafe {
+            for i in 0..vr::k_unMaxTrackedDeviceCount {
+                if get_class_fn(i) == device_class && is_connected_fn(i) {
+                    devices.push(i);
+                }
+            }
+        }
+        Ok(devices)
+    }
+
+    /// Float device property, `None` if the device does not report it
+    /// float 型のデバイスプロパティ。デバイスが報告しなければ `None`
+    pub(super) fn float_device_property(
+        &self,
+        index: u32,
+        prop: vr::ETrackedDeviceProperty,
+    ) -> napi::Result<Option<f32>> {
+        read_float_device_property(self.system()?, index, prop)
+    }
+
+    pub(super) fn int32_device_property(
+        &self,
+        index: u32,
+        prop: vr::ETrackedDeviceProperty,
+    ) -> napi::Result<Option<i32>> {
+        let system = self.system()?;
+        let get_int32_fn = require_fn(
+            system.GetInt32TrackedDeviceProperty,
+            "GetInt32TrackedDeviceProperty",
+        )?;
+        let mut err = vr::ETrackedPropertyError_TrackedProp_Success;
+        let value = unsafe { get_int32_fn(index, prop, &mut err) };
+        Ok((err == vr::ETrackedPropertyError_TrackedProp_Success).then_some(value))
+    }
+
+    pub(super) fn bool_device_property(
+        &self,
+        index: u32,
+        prop: vr::ETrackedDeviceProperty,
+    ) -> napi::Result<Option<bool>> {
+        let system = self.system()?;
+        let get_bool_fn = require_fn(
+            system.GetBoolTrackedDeviceProperty,
+            "GetBoolTrackedDeviceProperty",
+        )?;
+        let mut err = vr::ETrackedPropertyError_TrackedProp_Success;
+        let value = unsafe { get_bool_fn(index, prop, &mut err) };
+        Ok((err == vr::ETrackedPropertyError_TrackedProp_Success).then_some(value))
+    }
+
+    /// String device property, `None` if missing or empty / 文字列プロパティ。無いか空なら `None`
+    pub(super) fn string_device_property(
+        &self,
+        index: u32,
+        prop: vr::ETrackedDeviceProperty,
+    ) -> napi::Result<Option<String>> {
+        read_string_device_property(self.system()?, index, prop)
+    }
+
+    pub(super) fn device_role(&self, index: u32) -> napi::Result<TrackedControllerRole> {
+        let system = self.system()?;
+        if let Some(get_role_fn) = system.GetControllerRoleForTrackedDeviceIndex {
+            let role = TrackedControllerRole::from_vr(unsafe { get_role_fn(index) });
+            if role != TrackedControllerRole::Invalid {
+                return Ok(role);
+            }
+        }
+        // Trackers only carry a role hint / トラッカーはロールヒントのみを持つ
+        Ok(self
+            .int32_device_property(
+                index,
+                vr::ETrackedDeviceProperty_Prop_ControllerRoleHint_Int32,
+            )?
+            .map(|hint| TrackedControllerRole::from_vr(hint as vr::ETrackedControllerRole))
+            .unwrap_or(TrackedControllerRole::Invalid))
+    }
+
+    pub(super) fn device_info(&self, index: u32) -> napi::Result<DeviceInfo> {
+        let system = self.system()?;
+        let get_class_fn = require_fn(system.GetTrackedDeviceClass, "GetTrackedDeviceClass")?;
+        let device_class = TrackedDeviceClass::from_vr(unsafe { get_class_fn(index) });
+
+        let controller_type_name = self
+            .string_device_property(index, vr::ETrackedDeviceProperty_Prop_ControllerType_String)?;
+        let provides_battery_status = self
+            .bool_device_property(
+                index,
+                vr::ETrackedDeviceProperty_Prop_DeviceProvidesBatteryStatus_Bool,
+            )?
+            .unwrap_or(false);
+        let (battery_percentage, is_charging) = if provides_battery_status {
+            (
+                self.float_device_property(
+                    index,
+                    vr::ETrackedDeviceProperty_Prop_DeviceBatteryPercentage_Float,
+                )?
+                .map(|value| value as f64),
+                self.bool_device_property(
+                    index,
+                    vr::ETrackedDeviceProperty_Prop_DeviceIsCharging_Bool,
+                )?,
+            )
+        } else {
+            (None, None)
+        };
+
+        Ok(DeviceInfo {
+            deviceIndex: index,
+            deviceClass: device_class,
+            role: self.device_role(index)?,
+            controllerType: controller_type_name
+                .as_deref()
+                .map(ControllerType::from_prop)
+                .unwrap_or(ControllerType::Unknown),
+            controllerTypeName: controller_type_name,
+            modelNumber: self.string_device_property(
+                index,
+                vr::ETrackedDeviceProperty_Prop_ModelNumber_String,
+            )?,
+            serialNumber: self.string_device_property(
+                index,
+                vr::ETrackedDeviceProperty_Prop_SerialNumber_String,
+            )?,
+            manufacturerName: self.string_device_property(
+                index,
+                vr::ETrackedDeviceProperty_Prop_ManufacturerName_String,
+            )?,
+            renderModelName: self.string_device_property(
+                index,
+                vr::ETrackedDeviceProperty_Prop_RenderModelName_String,
+            )?,
+            providesBatteryStatus: provides_battery_status,
+            batteryPercentage: battery_percentage,
+            isCharging: is_charging,
+        })
+    }
+
+    pub(super) fn device_index_for_role(
+        &self,
+        role: vr::ETrackedControllerRole,
+    ) -> napi::Result<Option<u32>> {
+        let system = self.system()?;
+        let get_index_fn = require_fn(
+            system.GetTrackedDeviceIndexForControllerRole,
+            "GetTrackedDeviceIndexForControllerRole",
+        )?;
+        let index = unsafe { get_index_fn(role) };
+        if index == vr::k_unTrackedDeviceIndexInvalid || index >= vr::k_unMaxTrackedDeviceCount {
+            return Ok(None);
+        }
+        Ok(Some(index))
+    }
+
+    /// Resolve a selector to the current device index, `None` if the device is absent
+    /// セレクタを現在のデバイスインデックスに解決する。デバイスが無ければ `None`
+    pub(super) fn resolve_device_selector(
+        &self,
+        selector: DeviceSelector,
+    ) -> napi::Result<Option<u32>> {
+        match selector {
+            DeviceSelector::Index(index) => Ok(Some(index)),
+            DeviceSelector::Role(role) => self.device_index_for_role(role.to_vr()),
+            DeviceSelector::Class { class, ordinal } => Ok(self
+                .connected_devices_of_class(class.to_vr())?
+                .get(ordinal as usize)
+                .copied()),
+        }
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    #[napi]
+    pub fn get_tracked_device_indices(
+        &self,
+        device_class: TrackedDeviceClass,
+    ) -> napi::Result<Vec<u32>> {
+        self.connected_devices_of_class(device_class.to_vr())
+    }
+
+    #[napi]
+    pub fn get_tracked_device_index_for_role(
+        &self,
+        role: TrackedControllerRole,
+    ) -> napi::Result<Option<u32>> {
+        self.device_index_for_role(role.to_vr())
+    }
+
+    #[napi]
+    pub fn get_device_info(&self, index: u32) -> napi::Result<Option<DeviceInfo>> {
+        if index >= vr::k_unMaxTrackedDeviceCount {
+            return Err(napi::Error::from_reason("Invalid device index"));
+        }
+        let system = self.system()?;
+        let is_connected_fn =
+            require_fn(system.IsTrackedDeviceConnected, "IsTrackedDeviceConnected")?;
+        if !unsafe { is_connected_fn(index) } {
+            return Ok(None);
+        }
+        self.device_info(index).map(Some)
+    }
+
+    /// Every connected HMD, controller and tracker / 接続中のすべての HMD・コントローラー・トラッカー
+    #[napi]
+    pub fn get_device_list(&self) -> napi::Result<Vec<DeviceInfo>> {
+        let mut indices = Vec::new();
+        for device_class in [
+            TrackedDeviceClass::Hmd,
+            TrackedDeviceClass::Controller,
+            TrackedDeviceClass::GenericTracker,
+        ] {
+            indices.extend(self.connected_devices_of_class(device_class.to_vr())?);
+        }
+        indices.sort_unstable();
+        indices
+            .into_iter()
+            .map(|index| self.device_info(index))
+            .collect()
+    }
+
+    #[napi]
+    pub fn find_tracked_device(
+        &self,
+        selector: TrackedDeviceSelector,
+    ) -> napi::Result<Option<u32>> {
+        self.resolve_device_selector(DeviceSelector::from_napi(&selector)?)
+    }
+}
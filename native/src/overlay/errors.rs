@@ -29,6 +29,22 @@ pub(super) fn cstr_to_string(ptr: *const c_char) -> Option<String> {
     Some(String::from_utf8_lossy(&bytes[..len]).into_owned())
 }
 
+/// Caller-supplied `timestampMs` as seconds / 呼び出し側が渡した `timestampMs` を秒に変換する
+pub(super) fn timestamp_seconds(timestamp_ms: f64) -> napi::Result<f64> {
+    if timestamp_ms.is_finite() {
+        Ok(timestamp_ms / 1000.0)
+    } else {
+        Err(napi::Error::from_reason("timestampMs must be finite"))
+    }
+}
+
+pub(super) fn read_c_buffer(buf: &[c_char]) -> String {
+    // Safely scan for NUL within buffer bounds / バッファ境界内で安全にNULをスキャン
+    let bytes = unsafe { std::slice::from_raw_parts(buf.as_ptr() as *const u8, buf.len()) };
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&bytes[..len]).trim().to_string()
+}
+
 pub(super) fn init_error_message(err: vr::EVRInitError) -> String {
     let symbol = unsafe { cstr_to_string(vr::VR_GetVRInitErrorAsSymbol(err)) };
     let description = unsafe { cstr_to_string(vr::VR_GetVRInitErrorAsEnglishDescription(err)) };
@@ -0,0 +1,163 @@
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::Status;
+use napi_derive::napi;
+use openvr_sys as vr;
+
+use super::errors::require_fn;
+use super::manager::OverlayManager;
+use super::types::{
+    DeviceEvent, DeviceEventType, TrackedControllerRole, TrackedDeviceClass, VrEvent, VrEventBatch,
+};
+
+/// Upper bound per poll so a flooding queue cannot stall the caller
+/// キューが溢れていても呼び出し側を止めないための1回あたりの上限
+const MAX_EVENTS_PER_POLL: usize = 256;
+
+/// JS callback receiving the events `poll_device_events` does not return
+/// `poll_device_events` が返さないイベントを受け取る JS コールバック
+pub(super) type VrEventCallback = ThreadsafeFunction<Vec<VrEvent>, (), Vec<VrEvent>, Status, false>;
+
+impl DeviceEventType {
+    fn from_vr(event_type: u32) -> Option<Self> {
+        match event_type {
+            vr::EVREventType_VREvent_TrackedDeviceActivated => Some(Self::Activated),
+            vr::EVREventType_VREvent_TrackedDeviceDeactivated => Some(Self::Deactivated),
+            vr::EVREventType_VREvent_TrackedDeviceUpdated => Some(Self::Updated),
+            vr::EVREventType_VREvent_TrackedDeviceRoleChanged => Some(Self::RoleChanged),
+            vr::EVREventType_VREvent_TrackedDeviceUserInteractionStarted => {
+                Some(Self::UserInteractionStarted)
+            }
+            vr::EVREventType_VREvent_TrackedDeviceUserInteractionEnded => {
+                Some(Self::UserInteractionEnded)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Drain the IVRSystem event queue / IVRSystem のイベントキューを空にする
+    fn drain_system_events(&self) -> napi::Result<Vec<vr::VREvent_t>> {
+        let system = self.system()?;
+        let poll_fn = require_fn(system.PollNextEvent, "PollNextEvent")?;
+        let mut events = Vec::new();
+        while events.len() < MAX_EVENTS_PER_POLL {
+            let mut event: vr::VREvent_t = unsafe { std::mem::zeroed() };
+            let has_event =
+                unsafe { poll_fn(&mut event, std::mem::size_of::<vr::VREvent_t>() as u32) };
+            if !has_event {
+                break;
+            }
+            events.push(event);
+        }
+        Ok(events)
+    }
+
+    fn device_event_from_vr(
+        &self,
+        event_type: DeviceEventType,
+        event: &vr::VREvent_t,
+    ) -> napi::Result<DeviceEvent> {
+        let index = event.trackedDeviceIndex;
+        let (device_class, role) = if index < vr::k_unMaxTrackedDeviceCount {
+            let system = self.system()?;
+            let get_class_fn = require_fn(system.GetTrackedDeviceClass, "GetTrackedDeviceClass")?;
+            (
+                TrackedDeviceClass::from_vr(unsafe { get_class_fn(index) }),
+                self.device_role(index)?,
+            )
+        } else {
+            (TrackedDeviceClass::Invalid, TrackedControllerRole::Invalid)
+        };
+        Ok(DeviceEvent {
+            eventType: event_type,
+            deviceIndex: index,
+            deviceClass: device_class,
+            role,
+            eventAgeSeconds: event.eventAgeSeconds as f64,
+        })
+    }
+
+    /// Drain pending IVRSystem events in order, splitting device events from the rest.
+    /// Attachments, seated overlays and presence are updated before returning.
+    /// 保留中の IVRSystem イベントを順に取り出し、デバイスイベントとそれ以外に分ける。
+    /// 返す前にアタッチメント・シーテッド配置のオーバーレイ・在席状態を更新する。
+    fn process_system_events(&self) -> napi::Result<VrEventBatch> {
+        let mut device_events = Vec::new();
+        let mut other_events = Vec::new();
+        let mut devices_changed = false;
+        let mut seated_reset = false;
+
+        for event in self.drain_system_events()? {
+            self.note_presence_event(&event);
+            seated_reset |= event.eventType == vr::EVREventType_VREvent_SeatedZeroPoseReset;
+            let Some(event_type) = DeviceEventType::from_vr(event.eventType) else {
+                other_events.push(VrEvent {
+                    eventType: event.eventType,
+                    deviceIndex: event.trackedDeviceIndex,
+                    eventAgeSeconds: event.eventAgeSeconds as f64,
+                });
+                continue;
+            };
+            devices_changed |= matches!(
+                event_type,
+                DeviceEventType::Activated
+                    | DeviceEventType::Deactivated
+                    | DeviceEventType::RoleChanged
+            );
+            device_events.push(self.device_event_from_vr(event_type, &event)?);
+        }
+
+        if devices_changed {
+            self.invalidate_poses_cache();
+            self.refresh_overlay_attachments()?;
+        }
+        if seated_reset {
+            self.refresh_seated_zero_pose()?;
+        }
+        Ok(VrEventBatch {
+            deviceEvents: device_events,
+            otherEvents: other_events,
+        })
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Drain pending IVRSystem events and return the device-related ones in order; the
+    /// other events go to the listener set by `setVrEventListener`. Attachments, seated
+    /// overlays and presence are updated before returning, so callers only need to update
+    /// their own state.
+    /// 保留中の IVRSystem イベントを取り出し、デバイス関連のものを順に返す。それ以外のイベントは
+    /// `setVrEventListener` で設定したリスナーに送る。返す前にアタッチメント・シーテッド配置の
+    /// オーバーレイ・在席状態を更新するため、呼び出し側は自身の状態を更新するだけでよい。
+    #[napi]
+    pub fn poll_device_events(&self) -> napi::Result<Vec<DeviceEvent>> {
+        let batch = self.process_system_events()?;
+        if !batch.otherEvents.is_empty() {
+            if let Some(listener) = self.borrow_vr_event_listener()?.as_ref() {
+                listener.call(batch.otherEvents, ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+        Ok(batch.deviceEvents)
+    }
+
+    /// Drain pending IVRSystem events and return all of them, for callers that handle
+    /// more than device events; updates the manager like `pollDeviceEvents`
+    /// 保留中の IVRSystem イベントを取り出してすべて返す。デバイス以外のイベントも扱う呼び出し側向け。
+    /// `pollDeviceEvents` と同様にマネージャーの状態を更新する
+    #[napi]
+    pub fn poll_vr_events(&self) -> napi::Result<VrEventBatch> {
+        self.process_system_events()
+    }
+
+    /// Push the events `pollDeviceEvents` does not return to `callback` in batches, after
+    /// the poll that drains them; `null` removes the listener
+    /// `pollDeviceEvents` が返さないイベントを、それを取り出したポーリングの後にまとめて
+    /// `callback` に送る。`null` でリスナーを外す
+    #[napi]
+    pub fn set_vr_event_listener(&self, callback: Option<VrEventCallback>) -> napi::Result<()> {
+        drop(self.replace_vr_event_listener(callback)?);
+        Ok(())
+    }
+}
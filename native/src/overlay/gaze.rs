@@ -0,0 +1,208 @@
+/// Dwell timing for the head-gaze pointer / 頭部視線ポインターの注視時間の設定
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct GazeParams {
+    pub dwell_seconds: f64,
+    /// After a selection, nothing fills / 選択後、何も進まない時間
+    pub cooldown_seconds: f64,
+    /// How long the gaze must rest on another key (or off the keys) before the current
+    /// key loses its progress, so head tremor at key borders does not restart the dwell
+    /// 現在のキーの進捗が失われるまでに、別のキー (またはキー外) に視線が留まる必要のある時間。
+    /// キーの境界での頭の揺れで注視がやり直しにならないようにする
+    pub switch_seconds: f64,
+}
+
+impl GazeParams {
+    pub(super) const DEFAULT: Self = Self {
+        dwell_seconds: 0.8,
+        cooldown_seconds: 0.4,
+        switch_seconds: 0.12,
+    };
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        if !(self.dwell_seconds.is_finite() && self.dwell_seconds > 0.0) {
+            return Err(napi::Error::from_reason(
+                "dwellMs must be a positive number",
+            ));
+        }
+        let non_negative = |value: f64| value.is_finite() && value >= 0.0;
+        if !non_negative(self.cooldown_seconds) || !non_negative(self.switch_seconds) {
+            return Err(napi::Error::from_reason(
+                "cooldownMs and switchMs must be non-negative numbers",
+            ));
+        }
+        if self.switch_seconds >= self.dwell_seconds {
+            return Err(napi::Error::from_reason(
+                "switchMs must be shorter than dwellMs",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Result of one gaze update / 視線の更新1回分の結果
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(super) struct GazeStatus {
+    /// Key whose dwell is filling / 注視が進んでいるキー
+    pub key: Option<usize>,
+    /// 0..1 fill of the dwell, for the UI ring / UI のリング用の注視の進み具合 (0..1)
+    pub progress: f64,
+    pub selected: Option<usize>,
+    pub cooling_down: bool,
+}
+
+/// Dwell-to-select over keys. The gaze has to stay on a key for `dwell_seconds` to
+/// select it; after a selection nothing fills for `cooldown_seconds`, then the key
+/// under the gaze starts over, so holding still types the same key again.
+/// キーに対する注視選択。キーを `dwell_seconds` 見続けると選択する。選択後は
+/// `cooldown_seconds` の間は何も進まず、その後視線の下のキーが最初からやり直すため、
+/// 見続ければ同じキーを再度入力する。
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct GazeDwell {
+    current: Option<(usize, f64)>,
+    /// Gaze target that differs from `current`, and since when / `current` と異なる視線先とその開始時刻
+    pending: Option<(Option<usize>, f64)>,
+    cooldown_until: Option<f64>,
+}
+
+impl GazeDwell {
+    pub(super) fn update(
+        &mut self,
+        params: &GazeParams,
+        key: Option<usize>,
+        seconds: f64,
+    ) -> GazeStatus {
+        if let Some(until) = self.cooldown_until {
+            if seconds < until {
+                return GazeStatus {
+                    key,
+                    progress: 0.0,
+                    selected: None,
+                    cooling_down: true,
+                };
+            }
+            self.cooldown_until = None;
+            self.pending = None;
+            self.current = key.map(|key| (key, until));
+        }
+
+        let current_key = self.current.map(|(key, _)| key);
+        if key == current_key {
+            self.pending = None;
+        } else {
+            let since = match self.pending {
+                Some((pending, since)) if pending == key => since,
+                _ => {
+                    self.pending = Some((key, seconds));
+                    seconds
+                }
+            };
+            if current_key.is_none() || seconds - since >= params.switch_seconds {
+                self.current = key.map(|key| (key, since));
+                self.pending = None;
+            }
+        }
+
+        let Some((key, since)) = self.current else {
+            return GazeStatus::default();
+        };
+        let progress = ((seconds - since) / params.dwell_seconds).clamp(0.0, 1.0);
+        if progress < 1.0 {
+            return GazeStatus {
+                key: Some(key),
+                progress,
+                selected: None,
+                cooling_down: false,
+            };
+        }
+        self.cooldown_until = Some(seconds + params.cooldown_seconds);
+        self.current = None;
+        self.pending = None;
+        GazeStatus {
+            key: Some(key),
+            progress: 1.0,
+            selected: Some(key),
+            cooling_down: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::key_layout::{layout_point, test_layout};
+    use super::*;
+
+    const PARAMS: GazeParams = GazeParams {
+        dwell_seconds: 1.0,
+        cooldown_seconds: 0.5,
+        switch_seconds: 0.2,
+    };
+
+    /// Feed (key, seconds) pairs and collect the selections
+    /// (キー, 秒) の組を入力して選択を集める
+    fn selections(reads: &[(Option<usize>, f64)]) -> Vec<(usize, f64)> {
+        let mut dwell = GazeDwell::default();
+        reads
+            .iter()
+            .filter_map(|&(key, seconds)| {
+                dwell
+                    .update(&PARAMS, key, seconds)
+                    .selected
+                    .map(|key| (key, (seconds * 1e6).round() / 1e6))
+            })
+            .collect()
+    }
+
+    fn steady(key: Option<usize>, from: f64, to: f64) -> Vec<(Option<usize>, f64)> {
+        let steps = ((to - from) / 0.1).round() as usize;
+        (0..=steps).map(|i| (key, from + i as f64 * 0.1)).collect()
+    }
+
+    #[test]
+    fn dwell_selects_then_cools_down_before_repeating() {
+        let reads = steady(Some(3), 0.0, 3.0);
+        // 1.0 dwell, 0.5 cooldown, then a fresh 1.0 dwell / 1.0 の注視、0.5 のクールダウン、再び 1.0 の注視
+        assert_eq!(selections(&reads), [(3, 1.0), (3, 2.5)]);
+    }
+
+    #[test]
+    fn progress_is_reported_for_the_ring() {
+        let mut dwell = GazeDwell::default();
+        dwell.update(&PARAMS, Some(1), 0.0);
+        let status = dwell.update(&PARAMS, Some(1), 0.25);
+        assert_eq!(status.key, Some(1));
+        assert!((status.progress - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn short_glances_away_keep_progress() {
+        let mut reads = steady(Some(1), 0.0, 0.5);
+        reads.push((Some(2), 0.55));
+        reads.push((None, 0.6));
+        reads.extend(steady(Some(1), 0.7, 1.2));
+        assert_eq!(selections(&reads), [(1, 1.0)]);
+    }
+
+    #[test]
+    fn moving_to_another_key_restarts_from_when_it_was_entered() {
+        let mut reads = steady(Some(1), 0.0, 0.5);
+        reads.extend(steady(Some(2), 0.6, 2.0));
+        // Key 2 was entered at 0.6 and took over after the switch delay
+        // キー2には 0.6 に入り、切り替え遅延の後に引き継いだ
+        assert_eq!(selections(&reads), [(2, 1.6)]);
+    }
+
+    #[test]
+    fn gaze_near_the_top_of_the_overlay_selects_the_top_row() {
+        // Intersection v runs bottom-up, so 0.9 is near the top edge, over "w"
+        // 交点の v は下から上に向かうため、0.9 は上端近くの "w" の上
+        let layout = test_layout();
+        let (u, v) = layout_point(0.5, 0.9);
+        let key = layout.key_at(u, v);
+        let reads: Vec<_> = (0..=10).map(|i| (key, i as f64 * 0.1)).collect();
+        let selected: Vec<_> = selections(&reads)
+            .into_iter()
+            .map(|(key, _)| layout.key(key).id.as_str())
+            .collect();
+        assert_eq!(selected, ["w"]);
+    }
+}
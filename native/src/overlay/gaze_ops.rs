@@ -0,0 +1,120 @@
+use napi_derive::napi;
+
+use super::constants::HMD_DEVICE_INDEX;
+use super::errors::timestamp_seconds;
+use super::gaze::{GazeDwell, GazeParams};
+use super::handles::{overlay_handle, OverlayHandle};
+use super::manager::OverlayManager;
+use super::math::{pose_ray, vec3_f32};
+use super::types::{GazeDwellParams, GazeDwellState, OverlayPoint};
+
+impl GazeParams {
+    fn from_napi(params: &GazeDwellParams) -> napi::Result<Self> {
+        let params = Self {
+            dwell_seconds: params.dwellMs / 1000.0,
+            cooldown_seconds: params.cooldownMs / 1000.0,
+            switch_seconds: params.switchMs / 1000.0,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn to_napi(self) -> GazeDwellParams {
+        GazeDwellParams {
+            dwellMs: self.dwell_seconds * 1000.0,
+            cooldownMs: self.cooldown_seconds * 1000.0,
+            switchMs: self.switch_seconds * 1000.0,
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Where the HMD's forward ray meets the overlay, from the shared pose cache
+    /// 共有ポーズキャッシュから求めた、HMD の前方レイとオーバーレイの交点
+    fn head_gaze_point(&self, handle: OverlayHandle) -> napi::Result<Option<(f64, f64)>> {
+        let (origin, direction) = {
+            let poses = self.fresh_poses()?;
+            let pose = &poses[HMD_DEVICE_INDEX as usize];
+            if !pose.bPoseIsValid || !pose.bDeviceIsConnected {
+                return Ok(None);
+            }
+            pose_ray(&pose.mDeviceToAbsoluteTracking.m)
+        };
+        let source = vec3_f32("source", &origin)?;
+        let direction = vec3_f32("direction", &direction)?;
+        Ok(self
+            .intersect_overlay(handle, source, direction)?
+            .map(|results| (results.vUVs.v[0] as f64, results.vUVs.v[1] as f64)))
+    }
+
+    fn advance_gaze_dwell(
+        &self,
+        point: Option<(f64, f64)>,
+        seconds: f64,
+    ) -> napi::Result<GazeDwellState> {
+        let key = match point {
+            Some((u, v)) => self.key_index_at(u, v)?,
+            None => None,
+        };
+        let mut dwell = self.gaze_dwell();
+        let status = dwell.update(&self.gaze_params(), key, seconds);
+        self.set_gaze_dwell(dwell);
+        let (u, v) = point.unwrap_or_default();
+        Ok(GazeDwellState {
+            hit: point.is_some(),
+            u,
+            v,
+            key: self.key_id(status.key)?,
+            progress: status.progress,
+            selectedKey: self.key_id(status.selected)?,
+            coolingDown: status.cooling_down,
+        })
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Cast the HMD forward ray at the keyboard overlay and advance dwell selection over
+    /// the keys set by `setKeyLayout`. Call once per frame; works without controllers.
+    /// HMD の前方レイをキーボードのオーバーレイに当て、`setKeyLayout` で設定したキーの
+    /// 注視選択を進める。毎フレーム1回呼ぶ。コントローラーが無くても動作する。
+    #[napi]
+    pub fn update_gaze_dwell(
+        &self,
+        handle: i64,
+        timestamp_ms: Option<f64>,
+    ) -> napi::Result<GazeDwellState> {
+        let handle = overlay_handle(handle)?;
+        let seconds = self.input_seconds(timestamp_ms)?;
+        let point = self.head_gaze_point(handle)?;
+        self.advance_gaze_dwell(point, seconds)
+    }
+
+    /// Feed a recorded gaze intersection UV (null when off the overlay) instead of the live
+    /// HMD pose / ライブの HMD ポーズの代わりに記録済みの視線の交点の UV (オーバーレイ外なら null) を入力する
+    #[napi]
+    pub fn feed_gaze_point(
+        &self,
+        point: Option<OverlayPoint>,
+        timestamp_ms: f64,
+    ) -> napi::Result<GazeDwellState> {
+        let seconds = timestamp_seconds(timestamp_ms)?;
+        self.advance_gaze_dwell(point.map(|point| (point.u, point.v)), seconds)
+    }
+
+    #[napi]
+    pub fn reset_gaze_dwell(&self) {
+        self.set_gaze_dwell(GazeDwell::default());
+    }
+
+    #[napi]
+    pub fn set_gaze_dwell_params(&self, params: GazeDwellParams) -> napi::Result<()> {
+        self.set_gaze_params(GazeParams::from_napi(&params)?);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_gaze_dwell_params(&self) -> GazeDwellParams {
+        self.gaze_params().to_napi()
+    }
+}
@@ -0,0 +1,416 @@
+/// Trigger gesture thresholds; distances in overlay UV, times in seconds
+/// トリガージェスチャーのしきい値 (距離はオーバーレイ UV、時間は秒)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct GestureParams {
+    pub drag_threshold: f64,         // Vertical travel / 縦方向の移動量
+    pub click_cancel_threshold: f64, // Cancels hold gestures / 長押し系を取り消す移動量
+    pub long_press_seconds: f64,     // 0 disables / 0 で無効
+    pub repeat_delay_seconds: f64,   // 0 disables / 0 で無効
+    pub repeat_interval_seconds: f64,
+}
+
+impl GestureParams {
+    /// Thresholds of the JS trigger handler; key repeat stays off until enabled
+    /// JS のトリガーハンドラーと同じしきい値。キーリピートは有効にするまでオフ
+    pub(super) const DEFAULT: Self = Self {
+        drag_threshold: 0.015,
+        click_cancel_threshold: 0.03,
+        long_press_seconds: 0.6,
+        repeat_delay_seconds: 0.0,
+        repeat_interval_seconds: 0.05,
+    };
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        let non_negative = |value: f64| value.is_finite() && value >= 0.0;
+        let all_non_negative = [
+            self.drag_threshold,
+            self.click_cancel_threshold,
+            self.long_press_seconds,
+            self.repeat_delay_seconds,
+        ]
+        .into_iter()
+        .all(non_negative);
+        if !all_non_negative {
+            return Err(napi::Error::from_reason(
+                "gesture thresholds must be non-negative numbers",
+            ));
+        }
+        if !self.repeat_interval_seconds.is_finite() || self.repeat_interval_seconds <= 0.0 {
+            return Err(napi::Error::from_reason(
+                "repeatIntervalMs must be a positive number",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Gesture {
+    Press { u: f64, v: f64 },
+    LongPress { u: f64, v: f64 },
+    Repeat { u: f64, v: f64 },
+    DragStart { u: f64, v: f64 },
+    Scroll { u: f64, v: f64, delta: f64 },
+    DragEnd { u: f64, v: f64 },
+    Release { u: f64, v: f64 },
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Hold {
+    start: (f64, f64),
+    last: (f64, f64),
+    started_at: f64,
+    moved: bool,
+    dragging: bool,
+    long_pressed: bool,
+    next_repeat_at: Option<f64>,
+}
+
+/// Per-controller trigger gesture recognizer, a port of `handleTriggerInput` extended with
+/// long-press and key repeat. A press that starts off the overlay is picked up as soon as
+/// the pointer reaches it while the trigger is still held, as the JS handler did.
+/// コントローラーごとのトリガージェスチャー認識器。`handleTriggerInput` の移植に長押しと
+/// キーリピートを加えたもの。オーバーレイ外で始まった押下は、JS ハンドラーと同様に
+/// トリガーを押したままポインターがオーバーレイに入った時点で押下として扱う。
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct TriggerGesture {
+    hold: Option<Hold>,
+}
+
+impl TriggerGesture {
+    /// Advance with the trigger state and overlay hit at `seconds`
+    /// `seconds` 時点のトリガー状態とオーバーレイのヒットで状態を進める
+    pub(super) fn update(
+        &mut self,
+        params: &GestureParams,
+        pressed: bool,
+        hit: Option<(f64, f64)>,
+        seconds: f64,
+    ) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+        if !pressed {
+            if let Some(hold) = self.hold.take() {
+                let (u, v) = hold.last;
+                if hold.dragging {
+                    gestures.push(Gesture::DragEnd { u, v });
+                }
+                gestures.push(Gesture::Release { u, v });
+            }
+            return gestures;
+        }
+
+        let Some(hold) = self.hold.as_mut() else {
+            if let Some((u, v)) = hit {
+                let next_repeat_at = (params.repeat_delay_seconds > 0.0)
+                    .then_some(seconds + params.repeat_delay_seconds);
+                self.hold = Some(Hold {
+                    start: (u, v),
+                    last: (u, v),
+                    started_at: seconds,
+                    moved: false,
+                    dragging: false,
+                    long_pressed: false,
+                    next_repeat_at,
+                });
+                gestures.push(Gesture::Press { u, v });
+            }
+            return gestures;
+        };
+
+        let Some((u, v)) = hit else {
+            // Leaving the overlay cancels hold gestures / オーバーレイ外に出ると長押し系を取り消す
+            hold.moved = true;
+            return gestures;
+        };
+        let total_u = u - hold.start.0;
+        let total_v = v - hold.start.1;
+        if !hold.moved && total_u.abs() + total_v.abs() > params.click_cancel_threshold {
+            hold.moved = true;
+        }
+        if !hold.dragging && total_v.abs() > params.drag_threshold {
+            hold.dragging = true;
+            hold.moved = true;
+            gestures.push(Gesture::DragStart {
+                u: hold.start.0,
+                v: hold.start.1,
+            });
+        }
+        if hold.dragging {
+            let delta = v - hold.last.1;
+            if delta != 0.0 {
+                gestures.push(Gesture::Scroll { u, v, delta });
+            }
+        }
+        hold.last = (u, v);
+
+        if !hold.moved {
+            let held = seconds - hold.started_at;
+            if params.long_press_seconds > 0.0
+                && !hold.long_pressed
+                && held >= params.long_press_seconds
+            {
+                hold.long_pressed = true;
+                gestures.push(Gesture::LongPress { u, v });
+            }
+            if let Some(due) = hold.next_repeat_at.filter(|&due| seconds >= due) {
+                gestures.push(Gesture::Repeat { u, v });
+                // One repeat per update; after a late update restart the cadence from now
+                // 1回の更新でリピートは1回。更新が遅れた場合は現在時刻から周期をやり直す
+                let next = due + params.repeat_interval_seconds;
+                hold.next_repeat_at = Some(if next > seconds {
+                    next
+                } else {
+                    seconds + params.repeat_interval_seconds
+                });
+            }
+        }
+        gestures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: GestureParams = GestureParams {
+        drag_threshold: 0.015,
+        click_cancel_threshold: 0.03,
+        long_press_seconds: 0.5,
+        repeat_delay_seconds: 0.4,
+        repeat_interval_seconds: 0.1,
+    };
+
+    /// Trigger pressed, overlay hit and time of one update / 1回の更新のトリガー押下・ヒット・時刻
+    type Step = (bool, Option<(f64, f64)>, f64);
+
+    fn run(steps: &[Step]) -> Vec<Gesture> {
+        let mut gesture = TriggerGesture::default();
+        steps
+            .iter()
+            .flat_map(|&(pressed, hit, seconds)| gesture.update(&PARAMS, pressed, hit, seconds))
+            .collect()
+    }
+
+    #[test]
+    fn idle_release_emits_nothing() {
+        assert!(run(&[(false, None, 0.0), (false, Some((0.1, 0.1)), 0.1)]).is_empty());
+    }
+
+    #[test]
+    fn press_with_hit_emits_press_then_release_at_last_position() {
+        assert_eq!(
+            run(&[(true, Some((0.1, 0.2)), 0.0), (false, None, 0.1)]),
+            vec![
+                Gesture::Press { u: 0.1, v: 0.2 },
+                Gesture::Release { u: 0.1, v: 0.2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn press_without_hit_waits_for_the_overlay() {
+        assert_eq!(
+            run(&[
+                (true, None, 0.0),
+                (true, Some((0.3, 0.3)), 0.1),
+                (false, None, 0.2),
+            ]),
+            vec![
+                Gesture::Press { u: 0.3, v: 0.3 },
+                Gesture::Release { u: 0.3, v: 0.3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn press_and_release_without_hit_emits_nothing() {
+        assert!(run(&[(true, None, 0.0), (false, None, 0.1)]).is_empty());
+    }
+
+    #[test]
+    fn vertical_travel_starts_drag_and_scrolls() {
+        let gestures = run(&[
+            (true, Some((0.3, 0.3)), 0.0),
+            (true, Some((0.3, 0.35)), 0.1),
+            (true, Some((0.3, 0.4)), 0.2),
+            (false, None, 0.3),
+        ]);
+        assert_eq!(gestures.len(), 6);
+        assert_eq!(gestures[0], Gesture::Press { u: 0.3, v: 0.3 });
+        assert_eq!(gestures[1], Gesture::DragStart { u: 0.3, v: 0.3 });
+        assert!(
+            matches!(gestures[2], Gesture::Scroll { delta, .. } if (delta - 0.05).abs() < 1e-9)
+        );
+        assert!(
+            matches!(gestures[3], Gesture::Scroll { delta, .. } if (delta - 0.05).abs() < 1e-9)
+        );
+        assert_eq!(gestures[4], Gesture::DragEnd { u: 0.3, v: 0.4 });
+        assert_eq!(gestures[5], Gesture::Release { u: 0.3, v: 0.4 });
+    }
+
+    #[test]
+    fn travel_within_drag_threshold_does_not_drag() {
+        assert_eq!(
+            run(&[
+                (true, Some((0.3, 0.3)), 0.0),
+                (true, Some((0.3, 0.31)), 0.1),
+                (false, None, 0.2),
+            ]),
+            vec![
+                Gesture::Press { u: 0.3, v: 0.3 },
+                Gesture::Release { u: 0.3, v: 0.31 },
+            ]
+        );
+    }
+
+    #[test]
+    fn horizontal_travel_never_drags() {
+        let gestures = run(&[
+            (true, Some((0.2, 0.2)), 0.0),
+            (true, Some((0.4, 0.2)), 0.1),
+            (false, None, 0.2),
+        ]);
+        assert!(!gestures
+            .iter()
+            .any(|g| matches!(g, Gesture::DragStart { .. } | Gesture::Scroll { .. })));
+    }
+
+    #[test]
+    fn dragging_without_vertical_change_emits_no_scroll() {
+        let gestures = run(&[
+            (true, Some((0.3, 0.3)), 0.0),
+            (true, Some((0.3, 0.35)), 0.1),
+            (true, Some((0.31, 0.35)), 0.2),
+        ]);
+        let scrolls = gestures
+            .iter()
+            .filter(|g| matches!(g, Gesture::Scroll { .. }))
+            .count();
+        assert_eq!(scrolls, 1);
+    }
+
+    #[test]
+    fn long_press_fires_once_after_holding_still() {
+        let gestures = run(&[
+            (true, Some((0.5, 0.5)), 0.0),
+            (true, Some((0.5, 0.5)), 0.3),
+            (true, Some((0.5, 0.5)), 0.5),
+            (true, Some((0.5, 0.5)), 0.6),
+        ]);
+        let long_presses = gestures
+            .iter()
+            .filter(|g| matches!(g, Gesture::LongPress { .. }))
+            .count();
+        assert_eq!(long_presses, 1);
+    }
+
+    #[test]
+    fn moving_past_cancel_threshold_prevents_long_press_and_repeat() {
+        let gestures = run(&[
+            (true, Some((0.5, 0.5)), 0.0),
+            (true, Some((0.54, 0.5)), 0.1),
+            (true, Some((0.5, 0.5)), 1.0),
+        ]);
+        assert_eq!(gestures, vec![Gesture::Press { u: 0.5, v: 0.5 }]);
+    }
+
+    #[test]
+    fn losing_the_hit_prevents_long_press() {
+        let gestures = run(&[
+            (true, Some((0.5, 0.5)), 0.0),
+            (true, None, 0.1),
+            (true, Some((0.5, 0.5)), 1.0),
+            (false, None, 1.1),
+        ]);
+        assert_eq!(
+            gestures,
+            vec![
+                Gesture::Press { u: 0.5, v: 0.5 },
+                Gesture::Release { u: 0.5, v: 0.5 },
+            ]
+        );
+    }
+
+    #[test]
+    fn repeat_starts_after_delay_and_follows_interval() {
+        let gestures = run(&[
+            (true, Some((0.5, 0.5)), 0.0),
+            (true, Some((0.5, 0.5)), 0.35),
+            (true, Some((0.5, 0.5)), 0.4),
+            (true, Some((0.5, 0.5)), 0.45),
+            (true, Some((0.5, 0.5)), 0.5),
+        ]);
+        let repeats = gestures
+            .iter()
+            .filter(|g| matches!(g, Gesture::Repeat { .. }))
+            .count();
+        assert_eq!(repeats, 2);
+    }
+
+    #[test]
+    fn late_update_emits_a_single_repeat() {
+        let mut gesture = TriggerGesture::default();
+        gesture.update(&PARAMS, true, Some((0.5, 0.5)), 0.0);
+        let late = gesture.update(&PARAMS, true, Some((0.5, 0.5)), 1.0);
+        let repeats = late
+            .iter()
+            .filter(|g| matches!(g, Gesture::Repeat { .. }))
+            .count();
+        assert_eq!(repeats, 1);
+        // Missed slots are skipped / 逃した枠は飛ばされる
+        let next = gesture.update(&PARAMS, true, Some((0.5, 0.5)), 1.05);
+        assert!(!next.iter().any(|g| matches!(g, Gesture::Repeat { .. })));
+        let next = gesture.update(&PARAMS, true, Some((0.5, 0.5)), 1.1);
+        assert!(next.iter().any(|g| matches!(g, Gesture::Repeat { .. })));
+    }
+
+    #[test]
+    fn disabled_hold_gestures_never_fire() {
+        let params = GestureParams {
+            long_press_seconds: 0.0,
+            repeat_delay_seconds: 0.0,
+            ..PARAMS
+        };
+        let mut gesture = TriggerGesture::default();
+        let gestures: Vec<Gesture> = [0.0, 1.0, 2.0, 3.0]
+            .into_iter()
+            .flat_map(|seconds| gesture.update(&params, true, Some((0.5, 0.5)), seconds))
+            .collect();
+        assert_eq!(gestures, vec![Gesture::Press { u: 0.5, v: 0.5 }]);
+    }
+
+    #[test]
+    fn release_resets_for_the_next_press() {
+        assert_eq!(
+            run(&[
+                (true, Some((0.1, 0.1)), 0.0),
+                (false, None, 0.1),
+                (true, Some((0.2, 0.2)), 0.2),
+                (false, Some((0.2, 0.2)), 0.3),
+            ]),
+            vec![
+                Gesture::Press { u: 0.1, v: 0.1 },
+                Gesture::Release { u: 0.1, v: 0.1 },
+                Gesture::Press { u: 0.2, v: 0.2 },
+                Gesture::Release { u: 0.2, v: 0.2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn default_params_are_valid() {
+        assert!(GestureParams::DEFAULT.validate().is_ok());
+        assert!(GestureParams {
+            repeat_interval_seconds: 0.0,
+            ..GestureParams::DEFAULT
+        }
+        .validate()
+        .is_err());
+        assert!(GestureParams {
+            drag_threshold: f64::NAN,
+            ..GestureParams::DEFAULT
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use napi_derive::napi;
+
+use super::gesture::{Gesture, GestureParams};
+use super::manager::OverlayManager;
+use super::types::{GestureEvent, GestureEventType, OverlayPoint, TriggerGestureParams};
+
+impl GestureParams {
+    fn from_napi(params: &TriggerGestureParams) -> napi::Result<Self> {
+        let params = Self {
+            drag_threshold: params.dragThreshold,
+            click_cancel_threshold: params.clickCancelThreshold,
+            long_press_seconds: params.longPressMs / 1000.0,
+            repeat_delay_seconds: params.repeatDelayMs / 1000.0,
+            repeat_interval_seconds: params.repeatIntervalMs / 1000.0,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn to_napi(self) -> TriggerGestureParams {
+        TriggerGestureParams {
+            dragThreshold: self.drag_threshold,
+            clickCancelThreshold: self.click_cancel_threshold,
+            longPressMs: self.long_press_seconds * 1000.0,
+            repeatDelayMs: self.repeat_delay_seconds * 1000.0,
+            repeatIntervalMs: self.repeat_interval_seconds * 1000.0,
+        }
+    }
+}
+
+fn gesture_event(controller_index: u32, gesture: Gesture) -> GestureEvent {
+    let (event_type, u, v, scroll_delta) = match gesture {
+        Gesture::Press { u, v } => (GestureEventType::Press, u, v, 0.0),
+        Gesture::LongPress { u, v } => (GestureEventType::LongPress, u, v, 0.0),
+        Gesture::Repeat { u, v } => (GestureEventType::Repeat, u, v, 0.0),
+        Gesture::DragStart { u, v } => (GestureEventType::DragStart, u, v, 0.0),
+        Gesture::Scroll { u, v, delta } => (GestureEventType::Scroll, u, v, delta),
+        Gesture::DragEnd { u, v } => (GestureEventType::DragEnd, u, v, 0.0),
+        Gesture::Release { u, v } => (GestureEventType::Release, u, v, 0.0),
+    };
+    GestureEvent {
+        eventType: event_type,
+        controllerIndex: controller_index,
+        u,
+        v,
+        scrollDelta: scroll_delta,
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Feed one controller's trigger state and overlay hit (null when off the overlay) and
+    /// return the recognized click, long-press, repeat, drag, scroll and release gestures.
+    /// `timestampMs` replays recorded input deterministically; the native clock is used otherwise.
+    /// 1台のコントローラーのトリガー状態とオーバーレイのヒット (オーバーレイ外なら null) を
+    /// 入力し、認識したクリック・長押し・リピート・ドラッグ・スクロール・解放を返す。
+    /// `timestampMs` を渡すと記録済みの入力を決定的に再生でき、省略時はネイティブの時計を使う。
+    #[napi]
+    pub fn update_trigger_gesture(
+        &self,
+        controller_index: u32,
+        pressed: bool,
+        hit: Option<OverlayPoint>,
+        timestamp_ms: Option<f64>,
+    ) -> napi::Result<Vec<GestureEvent>> {
+        let seconds = self.input_seconds(timestamp_ms)?;
+        let params = self.gesture_params();
+        let gestures = self
+            .borrow_trigger_gestures_mut()?
+            .entry(controller_index)
+            .or_default()
+            .update(&params, pressed, hit.map(|hit| (hit.u, hit.v)), seconds);
+        Ok(gestures
+            .into_iter()
+            .map(|gesture| gesture_event(controller_index, gesture))
+            .collect())
+    }
+
+    /// Drop gesture state without emitting events, for one controller or all of them
+    /// イベントを送らずにジェスチャー状態を破棄する (1台または全台)
+    #[napi]
+    pub fn reset_trigger_gesture(&self, controller_index: Option<u32>) -> napi::Result<()> {
+        let mut gestures = self.borrow_trigger_gestures_mut()?;
+        match controller_index {
+            Some(index) => {
+                gestures.remove(&index);
+            }
+            None => gestures.clear(),
+        }
+        Ok(())
+    }
+
+    #[napi]
+    pub fn set_trigger_gesture_params(&self, params: TriggerGestureParams) -> napi::Result<()> {
+        self.set_gesture_params(GestureParams::from_napi(&params)?);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_trigger_gesture_params(&self) -> TriggerGestureParams {
+        self.gesture_params().to_napi()
+    }
+}
@@ -1,115 +1,93 @@
 use napi_derive::napi;
 use openvr_sys as vr;
-use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::ffi::{c_char, CString};
 
+use super::constants::DEFAULT_ACTION_SET_PATH;
 use super::errors::{input_error, require_fn};
-use super::manager::OverlayManager;
-use super::types::CurrentBindings;
+use super::manager::{InputAction, OverlayManager};
+use super::manifest::ACTIONS;
+use super::types::{InputActionDefinition, InputActionSetConfig, InputActionType, InputHand};
 
-const ACTION_SET_PATH: &str = "/actions/vrkb2";
-const TOGGLE_ACTION_PATH: &str = "/actions/vrkb2/in/toggle_overlay";
-const TRIGGER_ACTION_PATH: &str = "/actions/vrkb2/in/trigger_click";
-const GRIP_ACTION_PATH: &str = "/actions/vrkb2/in/grip_click";
 const LEFT_HAND_PATH: &str = "/user/hand/left";
 const RIGHT_HAND_PATH: &str = "/user/hand/right";
-const TOGGLE_RELEASE_STREAK_TO_UNLOCK: u8 = 3;
 
-fn to_cstring(input: &str, label: &str) -> napi::Result<CString> {
+pub(super) fn to_cstring(input: &str, label: &str) -> napi::Result<CString> {
     CString::new(input)
         .map_err(|_| napi::Error::from_reason(format!("{label} contains a null byte")))
 }
 
-fn input_error_if_needed(action: &str, err: vr::EVRInputError) -> napi::Result<()> {
-    if err == vr::EVRInputError_VRInputError_None {
-        Ok(())
-    } else {
-        Err(input_error(action, err))
+/// Built-in action set used when `initInput` is given none, generated from `manifest::ACTIONS`
+/// like `steamvr/actions.json`
+/// `initInput` にアクションセットが渡されない場合の組み込みセット (`steamvr/actions.json` と同じく
+/// `manifest::ACTIONS` から生成)
+fn default_action_set() -> InputActionSetConfig {
+    InputActionSetConfig {
+        actionSet: DEFAULT_ACTION_SET_PATH.to_string(),
+        actions: ACTIONS
+            .iter()
+            .map(|spec| InputActionDefinition {
+                name: spec.name.to_string(),
+                actionType: spec.action_type,
+                hand: spec.hand,
+            })
+            .collect(),
     }
 }
 
-fn is_non_fatal_binding_info_error(err: vr::EVRInputError) -> bool {
-    err == vr::EVRInputError_VRInputError_NoData
-        || err == vr::EVRInputError_VRInputError_NoActiveActionSet
-}
-
-fn read_c_buffer(buf: &[c_char]) -> String {
-    // Safely scan for NUL within buffer bounds / バッファ境界内で安全にNULをスキャン
-    let bytes = unsafe { std::slice::from_raw_parts(buf.as_ptr() as *const u8, buf.len()) };
-    let len = bytes.iter().position(|&b| b == 0).unwrap_or(buf.len());
-    String::from_utf8_lossy(&bytes[..len]).trim().to_string()
-}
-
-fn get_binding_labels(
-    input: &vr::VR_IVRInput_FnTable,
-    action_handle: vr::VRActionHandle_t,
-) -> napi::Result<Vec<String>> {
-    let get_action_binding_info_fn =
-        require_fn(input.GetActionBindingInfo, "GetActionBindingInfo")?;
-    let mut bindings: [vr::InputBindingInfo_t; 16] = unsafe { std::mem::zeroed() };
-    let mut returned_count: u32 = 0;
-
-    unsafe {
-        let err = get_action_binding_info_fn(
-            action_handle,
-            bindings.as_mut_ptr(),
-            std::mem::size_of::<vr::InputBindingInfo_t>() as u32,
-            bindings.len() as u32,
-            &mut returned_count,
-        );
-        if err != vr::EVRInputError_VRInputError_None
-            && err != vr::EVRInputError_VRInputError_BufferTooSmall
-        {
-            if is_non_fatal_binding_info_error(err) {
-                return Ok(vec![]);
-            }
-            return Err(input_error("GetActionBindingInfo", err));
-        }
+fn action_path(action_set: &str, definition: &InputActionDefinition) -> String {
+    if definition.name.starts_with("/actions/") {
+        return definition.name.clone();
     }
+    let direction = match definition.actionType {
+        InputActionType::Vibration => "out",
+        _ => "in",
+    };
+    format!("{action_set}/{direction}/{}", definition.name)
+}
 
-    let mut names = BTreeSet::new();
-    for info in bindings.iter().take(returned_count as usize) {
-        let device = read_c_buffer(&info.rchDevicePathName);
-        let input_path = read_c_buffer(&info.rchInputPathName);
-        let mode = read_c_buffer(&info.rchModeName);
-        let slot = read_c_buffer(&info.rchSlotName);
-        let source = read_c_buffer(&info.rchInputSourceType);
-        let label = format!("{device} {input_path} {mode} {slot} {source}")
-            .trim()
-            .replace("  ", " ");
-        if !label.is_empty() {
-            names.insert(label);
-        }
+pub(super) fn input_error_if_needed(action: &str, err: vr::EVRInputError) -> napi::Result<()> {
+    if err == vr::EVRInputError_VRInputError_None {
+        Ok(())
+    } else {
+        Err(input_error(action, err))
     }
-
-    Ok(names.into_iter().collect())
 }
 
 #[napi]
 impl OverlayManager {
+    /// Load the action manifest and resolve `actionSet` (the built-in vrkb2 set when omitted).
+    /// Actions are then read by name through getDigital / getAnalog / getPose.
+    /// アクションマニフェストを読み込み `actionSet` (省略時は組み込みの vrkb2) を解決する。
+    /// 以後アクションは getDigital / getAnalog / getPose で名前により読み取る。
     #[napi]
-    pub fn init_input(&self, manifest_abs_path: String) -> napi::Result<()> {
+    pub fn init_input(
+        &self,
+        manifest_abs_path: String,
+        action_set: Option<InputActionSetConfig>,
+    ) -> napi::Result<()> {
         let input = self.input()?;
         let manifest = to_cstring(&manifest_abs_path, "manifest path")?;
+        let action_set = action_set.unwrap_or_else(default_action_set);
+        if !action_set.actionSet.starts_with("/actions/") {
+            return Err(napi::Error::from_reason(
+                "action set path must start with /actions/",
+            ));
+        }
 
         let set_manifest_fn = require_fn(input.SetActionManifestPath, "SetActionManifestPath")?;
         let get_action_set_fn = require_fn(input.GetActionSetHandle, "GetActionSetHandle")?;
         let get_action_fn = require_fn(input.GetActionHandle, "GetActionHandle")?;
         let get_input_source_fn = require_fn(input.GetInputSourceHandle, "GetInputSourceHandle")?;
 
-        let action_set_path = to_cstring(ACTION_SET_PATH, "action set path")?;
-        let toggle_action_path = to_cstring(TOGGLE_ACTION_PATH, "toggle action path")?;
-        let trigger_action_path = to_cstring(TRIGGER_ACTION_PATH, "trigger action path")?;
-        let grip_action_path = to_cstring(GRIP_ACTION_PATH, "grip action path")?;
+        let action_set_path = to_cstring(&action_set.actionSet, "action set path")?;
         let left_hand_path = to_cstring(LEFT_HAND_PATH, "left hand path")?;
         let right_hand_path = to_cstring(RIGHT_HAND_PATH, "right hand path")?;
 
         let mut action_set_handle: vr::VRActionSetHandle_t = vr::k_ulInvalidActionSetHandle;
-        let mut toggle_action_handle: vr::VRActionHandle_t = vr::k_ulInvalidActionHandle;
-        let mut trigger_action_handle: vr::VRActionHandle_t = vr::k_ulInvalidActionHandle;
-        let mut grip_action_handle: vr::VRActionHandle_t = vr::k_ulInvalidActionHandle;
         let mut left_hand_source: vr::VRInputValueHandle_t = vr::k_ulInvalidInputValueHandle;
         let mut right_hand_source: vr::VRInputValueHandle_t = vr::k_ulInvalidInputValueHandle;
+        let mut actions = HashMap::new();
 
         unsafe {
             input_error_if_needed(
@@ -123,27 +101,31 @@ impl OverlayManager {
                     &mut action_set_handle,
                 ),
             )?;
-            input_error_if_needed(
-                "GetActionHandle",
-                get_action_fn(
-                    toggle_action_path.as_ptr() as *mut c_char,
-                    &mut toggle_action_handle,
-                ),
-            )?;
-            input_error_if_needed(
-                "GetActionHandle",
-                get_action_fn(
-                    trigger_action_path.as_ptr() as *mut c_char,
-                    &mut trigger_action_handle,
-                ),
-            )?;
-            input_error_if_needed(
-                "GetActionHandle",
-                get_action_fn(
-                    grip_action_path.as_ptr() as *mut c_char,
-                    &mut grip_action_handle,
-                ),
-            )?;
+            for definition in &action_set.actions {
+                if definition.name.trim().is_empty() {
+                    return Err(napi::Error::from_reason("action name is required"));
+                }
+                let path = to_cstring(
+                    &action_path(&action_set.actionSet, definition),
+                    "action path",
+                )?;
+                let mut handle: vr::VRActionHandle_t = vr::k_ulInvalidActionHandle;
+                input_error_if_needed(
+                    "GetActionHandle",
+                    get_action_fn(path.as_ptr() as *mut c_char, &mut handle),
+                )?;
+                let action = InputAction {
+                    handle,
+                    action_type: definition.actionType,
+                    hand: definition.hand.unwrap_or(InputHand::Any),
+                };
+                if actions.insert(definition.name.clone(), action).is_some() {
+                    return Err(napi::Error::from_reason(format!(
+                        "duplicate action name: {}",
+                        definition.name
+                    )));
+                }
+            }
             input_error_if_needed(
                 "GetInputSourceHandle",
                 get_input_source_fn(
@@ -163,83 +145,13 @@ impl OverlayManager {
         let mut cache = self.borrow_input_cache_mut()?;
         cache.initialized = true;
         cache.action_set_handle = action_set_handle;
-        cache.toggle_action_handle = toggle_action_handle;
-        cache.trigger_action_handle = trigger_action_handle;
-        cache.grip_action_handle = grip_action_handle;
+        cache.actions = actions;
         cache.left_hand_source = left_hand_source;
         cache.right_hand_source = right_hand_source;
-        cache.last_toggle_state = false;
-        cache.toggle_lock = false;
-        cache.toggle_release_streak = 0;
+        cache.toggle = Default::default();
         Ok(())
     }
 
-    #[napi]
-    pub fn poll_toggle_clicked(&self) -> napi::Result<bool> {
-        let input = self.input()?;
-        let mut cache = self.borrow_input_cache_mut()?;
-        if !cache.initialized {
-            return Err(napi::Error::from_reason("SteamVR input is not initialized"));
-        }
-
-        let update_action_state_fn = require_fn(input.UpdateActionState, "UpdateActionState")?;
-        let get_digital_action_data_fn =
-            require_fn(input.GetDigitalActionData, "GetDigitalActionData")?;
-
-        let mut active_set = vr::VRActiveActionSet_t {
-            ulActionSet: cache.action_set_handle,
-            ulRestrictedToDevice: vr::k_ulInvalidInputValueHandle,
-            ulSecondaryActionSet: vr::k_ulInvalidActionSetHandle,
-            unPadding: 0,
-            nPriority: 0,
-        };
-
-        let mut digital: vr::InputDigitalActionData_t = unsafe { std::mem::zeroed() };
-
-        unsafe {
-            input_error_if_needed(
-                "UpdateActionState",
-                update_action_state_fn(
-                    &mut active_set,
-                    std::mem::size_of::<vr::VRActiveActionSet_t>() as u32,
-                    1,
-                ),
-            )?;
-            input_error_if_needed(
-                "GetDigitalActionData",
-                get_digital_action_data_fn(
-                    cache.toggle_action_handle,
-                    &mut digital,
-                    std::mem::size_of::<vr::InputDigitalActionData_t>() as u32,
-                    vr::k_ulInvalidInputValueHandle,
-                ),
-            )?;
-        }
-
-        let current_state = digital.bActive && digital.bState;
-
-        // Latch toggle while held:
-        // avoid repeated toggles if SteamVR briefly drops action activity/state
-        // during overlay visibility changes.
-        // 押しっぱなしの間のトグルをラッチする:
-        // オーバーレイの可視性が変わる間に、SteamVRがアクションのアクティビティ/状態を一時的に落とした場合の連続トグルを避けるため。
-        if current_state {
-            cache.toggle_release_streak = 0;
-        } else if cache.toggle_release_streak < u8::MAX {
-            cache.toggle_release_streak = cache.toggle_release_streak.saturating_add(1);
-            if cache.toggle_release_streak >= TOGGLE_RELEASE_STREAK_TO_UNLOCK {
-                cache.toggle_lock = false;
-            }
-        }
-
-        let clicked = current_state && !cache.toggle_lock;
-        if clicked {
-            cache.toggle_lock = true;
-        }
-        cache.last_toggle_state = current_state;
-        Ok(clicked)
-    }
-
     #[napi]
     pub fn open_binding_ui(&self, app_key: String, show_on_desktop: bool) -> napi::Result<()> {
         let input = self.input()?;
@@ -266,36 +178,4 @@ impl OverlayManager {
             )
         }
     }
-
-    #[napi]
-    pub fn get_current_bindings(&self) -> napi::Result<CurrentBindings> {
-        let input = self.input()?;
-        let cache = self.borrow_input_cache()?;
-        if !cache.initialized {
-            return Ok(CurrentBindings {
-                initialized: false,
-                toggleOverlay: vec![],
-                triggerBindings: vec![],
-                gripBindings: vec![],
-                triggerBound: false,
-                gripBound: false,
-            });
-        }
-
-        let toggle_labels = get_binding_labels(input, cache.toggle_action_handle)?;
-        let trigger_labels = get_binding_labels(input, cache.trigger_action_handle)?;
-        let grip_labels = get_binding_labels(input, cache.grip_action_handle)?;
-
-        let trigger_bound = !trigger_labels.is_empty();
-        let grip_bound = !grip_labels.is_empty();
-
-        Ok(CurrentBindings {
-            initialized: true,
-            toggleOverlay: toggle_labels,
-            triggerBindings: trigger_labels,
-            gripBindings: grip_labels,
-            triggerBound: trigger_bound,
-            gripBound: grip_bound,
-        })
-    }
 }
@@ -0,0 +1,479 @@
+use std::fmt::Write as _;
+
+/// Deepest nesting of arrays and objects the parser accepts, keeping recursion off the
+/// end of the stack / パーサーが受け付ける配列・オブジェクトの最大の入れ子の深さ。
+/// 再帰でスタックを使い果たさないようにする
+const MAX_DEPTH: usize = 128;
+
+/// Minimal JSON value for the SteamVR input files; objects keep their key order
+/// SteamVR 入力ファイル用の最小限の JSON 値。オブジェクトはキーの順序を保つ
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub(super) fn str(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+
+    pub(super) fn object<'a>(entries: impl IntoIterator<Item = (&'a str, Json)>) -> Self {
+        Json::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    pub(super) fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub(super) fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub(super) fn as_array(&self) -> &[Json] {
+        match self {
+            Json::Array(items) => items,
+            _ => &[],
+        }
+    }
+
+    pub(super) fn entries(&self) -> &[(String, Json)] {
+        match self {
+            Json::Object(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    pub(super) fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            bytes: text.as_bytes(),
+            pos: 0,
+            depth: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos != parser.bytes.len() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(value)
+    }
+
+    /// Two-space indented text, the same layout as `JSON.stringify(value, null, 2)`
+    /// 2スペースでインデントしたテキスト (`JSON.stringify(value, null, 2)` と同じ体裁)
+    pub(super) fn to_pretty(&self) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, 0);
+        out
+    }
+
+    fn write_pretty(&self, out: &mut String, depth: usize) {
+        let indent = |out: &mut String, depth: usize| out.push_str(&"  ".repeat(depth));
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            Json::Number(value) => write_number(out, *value),
+            Json::String(value) => write_string(out, value),
+            Json::Array(items) if items.is_empty() => out.push_str("[]"),
+            Json::Object(entries) if entries.is_empty() => out.push_str("{}"),
+            Json::Array(items) => {
+                out.push_str("[\n");
+                for (i, item) in items.iter().enumerate() {
+                    indent(out, depth + 1);
+                    item.write_pretty(out, depth + 1);
+                    out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+                }
+                indent(out, depth);
+                out.push(']');
+            }
+            Json::Object(entries) => {
+                out.push_str("{\n");
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    indent(out, depth + 1);
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.write_pretty(out, depth + 1);
+                    out.push_str(if i + 1 < entries.len() { ",\n" } else { "\n" });
+                }
+                indent(out, depth);
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn write_number(out: &mut String, value: f64) {
+    if !value.is_finite() {
+        out.push_str("null");
+    } else if value.fract() == 0.0 && value.abs() < 1e15 {
+        let _ = write!(out, "{}", value as i64);
+    } else {
+        let _ = write!(out, "{value}");
+    }
+}
+
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> String {
+        format!("{message} at byte {}", self.pos)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.bytes.get(self.pos), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), String> {
+        if self.bytes[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{literal}`")))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b'{') => self.nested(Self::object),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b't') => self.expect("true").map(|_| Json::Bool(true)),
+            Some(b'f') => self.expect("false").map(|_| Json::Bool(false)),
+            Some(b'n') => self.expect("null").map(|_| Json::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    /// Parse an array or object one level deeper / 配列またはオブジェクトを1段深く解析する
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Json, String>) -> Result<Json, String> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.pos += 1;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.bytes.get(self.pos) != Some(&b'"') {
+                return Err(self.error("expected object key"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(":")?;
+            entries.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(entries));
+                }
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            let Some(&byte) = self.bytes.get(self.pos) else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escape = self.bytes.get(self.pos).copied();
+                    self.pos += 1;
+                    let c = match escape {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{08}',
+                        Some(b'f') => '\u{0c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    let mut buf = [0; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                byte if byte < 0x20 => {
+                    self.pos -= 1;
+                    return Err(self.error("control character in string"));
+                }
+                byte => bytes.push(byte),
+            }
+        }
+        String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8 in string"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .bytes
+            .get(self.pos..self.pos + 4)
+            .filter(|digits| digits.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("invalid \\u escape"))?;
+        self.pos += 4;
+        Ok(digits)
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) {
+            self.expect("\\u")?;
+            let low = self.hex4()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.error("invalid surrogate pair"));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid code point"))
+    }
+
+    /// Consume `byte` if it is next / 次が `byte` なら読み進める
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.bytes.get(self.pos) == Some(&byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    /// Consume a run of digits, false if there was none / 連続する数字を読み進める。無ければ false
+    fn digits(&mut self) -> bool {
+        let start = self.pos;
+        while matches!(self.bytes.get(self.pos), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    /// Strict JSON number grammar; Rust's float parser alone accepts `01`, `-.5` and `1.`
+    /// JSON の数値文法に厳密に従う。Rust の浮動小数点パーサーだけでは `01`・`-.5`・`1.` も通る
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.pos;
+        self.eat(b'-');
+        let integer_ok = if self.eat(b'0') { true } else { self.digits() };
+        let fraction_ok = !self.eat(b'.') || self.digits();
+        let exponent_ok = !(self.eat(b'e') || self.eat(b'E')) || {
+            let _ = self.eat(b'+') || self.eat(b'-');
+            self.digits()
+        };
+        if !(integer_ok && fraction_ok && exponent_ok) {
+            return Err(self.error("invalid number"));
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|text| text.parse::<f64>().ok())
+            .map(Json::Number)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"{
+  "actions": [
+    {
+      "name": "/actions/main/in/trigger_click",
+      "type": "boolean",
+      "weight": -1.5e2
+    }
+  ],
+  "default": null,
+  "enabled": true,
+  "note": "tab\t \"quoted\" é 🎹"
+}"#;
+
+    #[test]
+    fn parses_a_document_and_keeps_key_order() {
+        let json = Json::parse(DOCUMENT).unwrap();
+        let keys: Vec<_> = json.entries().iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["actions", "default", "enabled", "note"]);
+        let action = &json.get("actions").unwrap().as_array()[0];
+        assert_eq!(action.get("weight"), Some(&Json::Number(-150.0)));
+        assert_eq!(json.get("default"), Some(&Json::Null));
+        assert_eq!(json.get("enabled"), Some(&Json::Bool(true)));
+        assert_eq!(
+            json.get("note").and_then(Json::as_str),
+            Some("tab\t \"quoted\" é 🎹")
+        );
+    }
+
+    #[test]
+    fn pretty_output_parses_back_to_the_same_value() {
+        let json = Json::parse(DOCUMENT).unwrap();
+        let pretty = json.to_pretty();
+        assert!(pretty.contains("\"weight\": -150\n"));
+        assert_eq!(Json::parse(&pretty), Ok(json));
+    }
+
+    #[test]
+    fn decodes_escapes_and_surrogate_pairs() {
+        let parse = |text: &str| Json::parse(text).map(|json| json.as_str().map(str::to_string));
+        assert_eq!(
+            parse(r#""\" \\ \/ \b \f \n \r \t""#),
+            Ok(Some("\" \\ / \u{08} \u{0c} \n \r \t".to_string()))
+        );
+        assert_eq!(parse(r#""\u00e9\u3042""#), Ok(Some("éあ".to_string())));
+        assert_eq!(parse(r#""\ud83d\ude00""#), Ok(Some("😀".to_string())));
+        for bad in [
+            r#""\ud83d""#,       // Lone high surrogate / 単独の上位サロゲート
+            r#""\ud83d x""#,     // High surrogate without its pair / 対のない上位サロゲート
+            r#""\ud83d\u0041""#, // High surrogate, then no low one / 上位の後が下位サロゲートでない
+            r#""\ude00""#,       // Lone low surrogate / 単独の下位サロゲート
+            r#""\u+123""#, // Sign accepted by from_str_radix / from_str_radix が受け付ける符号
+            r#""\u12""#,   // Short escape / 短いエスケープ
+            r#""\x""#,     // Unknown escape / 未知のエスケープ
+            "\"tab\tinside\"", // Raw control character / 生の制御文字
+            "\"unterminated",
+        ] {
+            assert!(Json::parse(bad).is_err(), "{bad} should not parse");
+        }
+    }
+
+    #[test]
+    fn follows_the_json_number_grammar() {
+        for (text, value) in [
+            ("0", 0.0),
+            ("-0", 0.0),
+            ("12", 12.0),
+            ("-1.25", -1.25),
+            ("1e3", 1000.0),
+            ("1E+2", 100.0),
+            ("25e-2", 0.25),
+        ] {
+            assert_eq!(Json::parse(text), Ok(Json::Number(value)), "{text}");
+        }
+        for bad in [
+            "-", "1e", "+1", "01", "-.5", ".5", "1.", "1.e5", "1e+", "--1", "1-2", "0x10",
+        ] {
+            assert!(Json::parse(bad).is_err(), "{bad} should not parse");
+        }
+    }
+
+    #[test]
+    fn rejects_trailing_garbage() {
+        for bad in [
+            "{} x",
+            "[1] ]",
+            "true false",
+            "nullx",
+            "1 2",
+            "\"a\"\"b\"",
+            "{\"a\": 1}}",
+        ] {
+            assert!(Json::parse(bad).is_err(), "{bad} should not parse");
+        }
+        assert!(Json::parse(" [1, 2] \n").is_ok());
+    }
+
+    #[test]
+    fn limits_nesting_depth_without_overflowing_the_stack() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(Json::parse(&nested(MAX_DEPTH)).is_ok());
+        let error = Json::parse(&nested(MAX_DEPTH + 1)).unwrap_err();
+        assert!(error.starts_with("nesting too deep"), "{error}");
+        assert!(Json::parse(&"{\"a\":".repeat(100_000)).is_err());
+    }
+
+    #[test]
+    fn rejects_every_truncation_without_panicking() {
+        let text = DOCUMENT.trim_end();
+        for end in (0..text.len()).filter(|&end| text.is_char_boundary(end)) {
+            assert!(
+                Json::parse(&text[..end]).is_err(),
+                "prefix of {end} bytes should not parse"
+            );
+        }
+    }
+}
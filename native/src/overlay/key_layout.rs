@@ -0,0 +1,161 @@
+/// One key of the virtual keyboard in layout space (overlay 0..1, v down)
+/// レイアウト空間 (オーバーレイの 0..1、v は下向き) における仮想キーボードのキー1つ
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct KeyRect {
+    pub id: String,
+    pub row: u32,
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+impl KeyRect {
+    pub(super) fn contains(&self, u: f64, v: f64) -> bool {
+        (self.left..self.right).contains(&u) && (self.top..self.bottom).contains(&v)
+    }
+
+    pub(super) fn center(&self) -> (f64, f64) {
+        (
+            (self.left + self.right) / 2.0,
+            (self.top + self.bottom) / 2.0,
+        )
+    }
+}
+
+/// Layout point of an overlay intersection UV. `ComputeOverlayIntersection` reports v
+/// bottom-up, which JS flips the same way in `mapUvToClient`.
+/// オーバーレイの交点の UV に対応するレイアウト上の点。`ComputeOverlayIntersection` の v は
+/// 下から上に向かうため、JS の `mapUvToClient` と同様に反転する。
+pub(super) fn layout_point(u: f64, v: f64) -> (f64, f64) {
+    (u, 1.0 - v)
+}
+
+/// Key geometry shared by the pointer-free typing modes (gaze, scanning, navigation,
+/// swipe); keys are addressed by their index in the layout. Every point passed in must be
+/// in layout space (v down, as the page is laid out); overlay intersections, whose v runs
+/// bottom-up, go through `layout_point` first.
+/// ポインターを使わない入力モード (視線・スキャン・ナビゲーション・スワイプ) が共有する
+/// キーの形状。キーはレイアウト内のインデックスで指す。渡す点はすべてレイアウト空間
+/// (ページと同じく v は下向き) でなければならない。v が下から上に向かうオーバーレイの交点は
+/// 先に `layout_point` を通す。
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct KeyLayout {
+    keys: Vec<KeyRect>,
+}
+
+impl KeyLayout {
+    pub(super) fn new(keys: Vec<KeyRect>) -> napi::Result<Self> {
+        let mut ids = std::collections::HashSet::new();
+        for key in &keys {
+            if key.id.is_empty() {
+                return Err(napi::Error::from_reason("key id must not be empty"));
+            }
+            if !ids.insert(key.id.as_str()) {
+                return Err(napi::Error::from_reason(format!(
+                    "duplicate key id: {}",
+                    key.id
+                )));
+            }
+            let in_range = |low: f64, high: f64| {
+                low.is_finite() && high.is_finite() && 0.0 <= low && low < high && high <= 1.0
+            };
+            if !in_range(key.left, key.right) || !in_range(key.top, key.bottom) {
+                return Err(napi::Error::from_reason(format!(
+                    "key {} must be a non-empty rectangle within 0..1",
+                    key.id
+                )));
+            }
+        }
+        Ok(Self { keys })
+    }
+
+    pub(super) fn keys(&self) -> &[KeyRect] {
+        &self.keys
+    }
+
+    pub(super) fn key(&self, index: usize) -> &KeyRect {
+        &self.keys[index]
+    }
+
+    /// Key indices grouped by row, rows in ascending order and keys left to right
+    /// 行ごとにまとめたキーのインデックス。行は昇順、キーは左から右の順
+    pub(super) fn rows(&self) -> Vec<Vec<usize>> {
+        let mut rows = std::collections::BTreeMap::<u32, Vec<usize>>::new();
+        for (index, key) in self.keys.iter().enumerate() {
+            rows.entry(key.row).or_default().push(index);
+        }
+        rows.into_values()
+            .map(|mut row| {
+                row.sort_by(|&a, &b| self.keys[a].left.total_cmp(&self.keys[b].left));
+                row
+            })
+            .collect()
+    }
+
+    /// Key under a point; the first one wins where keys overlap
+    /// 点の下にあるキー。重なっている場合は先のキーを優先する
+    pub(super) fn key_at(&self, u: f64, v: f64) -> Option<usize> {
+        self.keys.iter().position(|key| key.contains(u, v))
+    }
+}
+
+#[cfg(test)]
+pub(super) fn key(id: &str, row: u32, left: f64, top: f64, width: f64, height: f64) -> KeyRect {
+    KeyRect {
+        id: id.to_string(),
+        row,
+        left,
+        top,
+        right: left + width,
+        bottom: top + height,
+    }
+}
+
+/// A 2x3 grid ("q w e" / "a s d") filling the overlay, for the input mode tests
+/// 入力モードのテスト用に、オーバーレイ全体を埋める 2x3 のグリッド ("q w e" / "a s d")
+#[cfg(test)]
+pub(super) fn test_layout() -> KeyLayout {
+    let third = 1.0 / 3.0;
+    let mut keys = Vec::new();
+    for (row, ids) in [["q", "w", "e"], ["a", "s", "d"]].iter().enumerate() {
+        for (column, id) in ids.iter().enumerate() {
+            keys.push(key(
+                id,
+                row as u32,
+                column as f64 * third,
+                row as f64 * 0.5,
+                third,
+                0.5,
+            ));
+        }
+    }
+    KeyLayout::new(keys).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hit_testing() {
+        let layout = test_layout();
+        assert_eq!(
+            layout.key_at(0.5, 0.75).map(|i| layout.key(i).id.as_str()),
+            Some("s")
+        );
+        assert_eq!(layout.key_at(1.0, 0.5), None, "right edge is exclusive");
+    }
+
+    #[test]
+    fn rejects_bad_keys() {
+        assert!(KeyLayout::new(vec![key("", 0, 0.0, 0.0, 0.1, 0.1)]).is_err());
+        assert!(KeyLayout::new(vec![key("a", 0, 0.95, 0.0, 0.1, 0.1)]).is_err());
+        assert!(KeyLayout::new(vec![key("a", 0, 0.0, 0.0, 0.0, 0.1)]).is_err());
+        assert!(KeyLayout::new(vec![
+            key("a", 0, 0.0, 0.0, 0.1, 0.1),
+            key("a", 0, 0.2, 0.0, 0.1, 0.1),
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use napi_derive::napi;
+
+use super::gaze::GazeDwell;
+use super::key_layout::{layout_point, KeyLayout, KeyRect};
+use super::manager::OverlayManager;
+use super::scanning::SwitchScanner;
+use super::swipe::SwipeRecorder;
+use super::touch_model::CorrectionTracker;
+use super::types::KeyBounds;
+
+impl KeyRect {
+    fn from_napi(key: KeyBounds) -> Self {
+        Self {
+            left: key.left,
+            top: key.top,
+            right: key.left + key.width,
+            bottom: key.top + key.height,
+            id: key.id,
+            row: key.row,
+        }
+    }
+
+    fn to_napi(&self) -> KeyBounds {
+        KeyBounds {
+            id: self.id.clone(),
+            row: self.row,
+            left: self.left,
+            top: self.top,
+            width: self.right - self.left,
+            height: self.bottom - self.top,
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Key index under an overlay intersection UV / オーバーレイの交点の UV の下にあるキーのインデックス
+    pub(super) fn key_index_at(&self, u: f64, v: f64) -> napi::Result<Option<usize>> {
+        let (u, v) = layout_point(u, v);
+        Ok(self.borrow_key_layout()?.key_at(u, v))
+    }
+
+    pub(super) fn key_id(&self, index: Option<usize>) -> napi::Result<Option<String>> {
+        let layout = self.borrow_key_layout()?;
+        Ok(index.map(|index| layout.key(index).id.clone()))
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Describe the keyboard's keys for the native typing modes (gaze dwell, switch
+    /// scanning, D-pad navigation, swipe). Replacing the layout restarts whatever mode is
+    /// in progress.
+    /// ネイティブの入力モード (視線の注視、スイッチスキャン、D-pad ナビゲーション、スワイプ) 用に
+    /// キーボードのキーを設定する。レイアウトを置き換えると進行中のモードはやり直しになる。
+    #[napi]
+    pub fn set_key_layout(&self, keys: Vec<KeyBounds>) -> napi::Result<()> {
+        let layout = KeyLayout::new(keys.into_iter().map(KeyRect::from_napi).collect())?;
+        *self.borrow_key_layout_mut()? = layout;
+        self.set_gaze_dwell(GazeDwell::default());
+        self.set_switch_scanner(SwitchScanner::default());
+        self.borrow_key_navigators_mut()?.clear();
+        *self.borrow_swipe_recorder_mut()? = SwipeRecorder::default();
+        // Pending presses refer to keys by index / 保留中の押下はキーをインデックスで指す
+        *self.borrow_correction_tracker_mut()? = CorrectionTracker::default();
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_key_layout(&self) -> napi::Result<Vec<KeyBounds>> {
+        Ok(self
+            .borrow_key_layout()?
+            .keys()
+            .iter()
+            .map(KeyRect::to_napi)
+            .collect())
+    }
+
+    /// Id of the key under an overlay intersection UV (v up, as `computeOverlayIntersection`
+    /// returns it) / オーバーレイの交点の UV (`computeOverlayIntersection` が返す、v が上向きの値) の下にあるキーの ID
+    #[napi]
+    pub fn key_at_point(&self, u: f64, v: f64) -> napi::Result<Option<String>> {
+        self.key_id(self.key_index_at(u, v)?)
+    }
+}
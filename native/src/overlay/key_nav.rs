@@ -0,0 +1,396 @@
+use super::key_layout::KeyLayout;
+
+/// D-pad navigation timing and stick handling; the stick is in -1..1 with y up
+/// D-pad ナビゲーションのタイミングとスティックの扱い。スティックは -1..1 で y は上向き
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct KeyNavParams {
+    /// Stick deflection that starts a move / 移動を始めるスティックの倒し量
+    pub deadzone: f64,
+    /// Hold before the first repeat / 最初のリピートまでの保持時間
+    pub repeat_delay_seconds: f64,
+    pub repeat_interval_seconds: f64,
+    /// Floor the interval accelerates down to / 加速で縮む間隔の下限
+    pub min_repeat_interval_seconds: f64,
+    /// Factor applied to the interval after every repeat (1 = no acceleration)
+    /// リピートのたびに間隔に掛ける係数 (1 で加速なし)
+    pub acceleration: f64,
+    /// Moving past the edge of a row or of the grid wraps around / 行やグリッドの端を越えると反対側に回る
+    pub wrap: bool,
+}
+
+impl KeyNavParams {
+    pub(super) const DEFAULT: Self = Self {
+        deadzone: 0.4,
+        repeat_delay_seconds: 0.4,
+        repeat_interval_seconds: 0.2,
+        min_repeat_interval_seconds: 0.06,
+        acceleration: 0.85,
+        wrap: false,
+    };
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        if !(self.deadzone > 0.0 && self.deadzone < 1.0) {
+            return Err(napi::Error::from_reason("deadzone must be between 0 and 1"));
+        }
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+        let all_positive = [
+            self.repeat_delay_seconds,
+            self.repeat_interval_seconds,
+            self.min_repeat_interval_seconds,
+        ]
+        .into_iter()
+        .all(positive);
+        if !all_positive {
+            return Err(napi::Error::from_reason(
+                "repeat timings must be positive numbers",
+            ));
+        }
+        if self.min_repeat_interval_seconds > self.repeat_interval_seconds {
+            return Err(napi::Error::from_reason(
+                "minRepeatIntervalMs must not exceed repeatIntervalMs",
+            ));
+        }
+        if !(self.acceleration > 0.0 && self.acceleration <= 1.0) {
+            return Err(napi::Error::from_reason(
+                "acceleration must be greater than 0 and at most 1",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Dominant axis of a stick reading outside the deadzone / デッドゾーン外のスティックの主な軸
+    fn of(stick: (f64, f64), deadzone: f64) -> Option<Self> {
+        let (x, y) = stick;
+        if x.hypot(y) < deadzone {
+            return None;
+        }
+        Some(match (x.abs() >= y.abs(), x >= 0.0, y >= 0.0) {
+            (true, true, _) => Self::Right,
+            (true, false, _) => Self::Left,
+            (false, _, true) => Self::Up,
+            (false, _, false) => Self::Down,
+        })
+    }
+
+    fn component(self, (x, y): (f64, f64)) -> f64 {
+        match self {
+            Self::Up => y,
+            Self::Down => -y,
+            Self::Left => -x,
+            Self::Right => x,
+        }
+    }
+}
+
+/// A held direction keeps going until its own component drops below this share of the
+/// deadzone, so a stick rolled through a diagonal does not flicker between directions
+/// 保持中の方向は、その方向の成分がデッドゾーンのこの割合を下回るまで続く。
+/// スティックを斜めに回したときに方向がちらつかないようにする
+const RELEASE_RATIO: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug)]
+struct Held {
+    direction: Direction,
+    next_repeat_at: f64,
+    interval: f64,
+}
+
+/// Result of one navigation update / ナビゲーションの更新1回分の結果
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(super) struct KeyNavStatus {
+    pub focus: Option<usize>,
+    pub moved: bool,
+    pub committed: Option<usize>,
+}
+
+/// Focus cursor moved across the key grid by a stick or touchpad. A push moves one key at
+/// once; holding repeats after `repeat_delay_seconds` at an interval that shrinks by
+/// `acceleration` per repeat. Up and down go to the key of the next row nearest the
+/// current key horizontally, so staggered rows navigate naturally. The focus starts on
+/// the key nearest the middle of the keyboard.
+/// スティックやタッチパッドでキーのグリッド上を動くフォーカスカーソル。倒すとすぐに1キー
+/// 動き、保持すると `repeat_delay_seconds` の後、リピートごとに `acceleration` 倍に縮む間隔で
+/// 繰り返す。上下は隣の行のうち現在のキーと横位置が最も近いキーへ移るため、ずれた行でも
+/// 自然に移動できる。フォーカスはキーボード中央に最も近いキーから始まる。
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct KeyNavigator {
+    focus: Option<usize>,
+    held: Option<Held>,
+    commit_pressed: bool,
+}
+
+impl KeyNavigator {
+    pub(super) fn update(
+        &mut self,
+        params: &KeyNavParams,
+        layout: &KeyLayout,
+        stick: (f64, f64),
+        commit: bool,
+        seconds: f64,
+    ) -> KeyNavStatus {
+        let commit_edge = commit && !self.commit_pressed;
+        self.commit_pressed = commit;
+        let keys = layout.keys();
+        let focus = match self.focus {
+            Some(focus) if focus < keys.len() => focus,
+            _ => {
+                self.held = None;
+                let Some(start) = Self::nearest_key(layout, 0..keys.len(), 0.5, 0.5) else {
+                    self.focus = None;
+                    return KeyNavStatus::default();
+                };
+                start
+            }
+        };
+        self.focus = Some(focus);
+
+        if commit_edge {
+            // Commit the key the user saw focused, never one moved onto in the same frame
+            // 同じフレームで移った先ではなく、ユーザーが見ていたフォーカス中のキーを確定する
+            return KeyNavStatus {
+                focus: Some(focus),
+                moved: false,
+                committed: Some(focus),
+            };
+        }
+
+        let direction = match self.held {
+            Some(held) if held.direction.component(stick) >= params.deadzone * RELEASE_RATIO => {
+                Some(held.direction)
+            }
+            _ => Direction::of(stick, params.deadzone),
+        };
+        let step = match (direction, self.held) {
+            (None, _) => {
+                self.held = None;
+                false
+            }
+            (Some(direction), Some(held)) if held.direction == direction => {
+                if seconds < held.next_repeat_at {
+                    false
+                } else {
+                    let interval = (held.interval * params.acceleration)
+                        .max(params.min_repeat_interval_seconds);
+                    self.held = Some(Held {
+                        direction,
+                        next_repeat_at: seconds + held.interval,
+                        interval,
+                    });
+                    true
+                }
+            }
+            (Some(direction), _) => {
+                self.held = Some(Held {
+                    direction,
+                    next_repeat_at: seconds + params.repeat_delay_seconds,
+                    interval: params.repeat_interval_seconds,
+                });
+                true
+            }
+        };
+        let next = match (step, direction) {
+            (true, Some(direction)) => Self::step(layout, focus, direction, params.wrap),
+            _ => focus,
+        };
+        self.focus = Some(next);
+        KeyNavStatus {
+            focus: Some(next),
+            moved: next != focus,
+            committed: None,
+        }
+    }
+
+    fn step(layout: &KeyLayout, key: usize, direction: Direction, wrap: bool) -> usize {
+        let rows = layout.rows();
+        let Some((row, position)) = rows.iter().enumerate().find_map(|(row, keys)| {
+            keys.iter()
+                .position(|&other| other == key)
+                .map(|position| (row, position))
+        }) else {
+            return key;
+        };
+        let offset = |index: usize, len: usize, forward: bool| match (forward, wrap) {
+            (true, _) if index + 1 < len => index + 1,
+            (true, true) => 0,
+            (false, _) if index > 0 => index - 1,
+            (false, true) => len - 1,
+            _ => index,
+        };
+        match direction {
+            Direction::Left | Direction::Right => {
+                let keys = &rows[row];
+                keys[offset(position, keys.len(), direction == Direction::Right)]
+            }
+            Direction::Up | Direction::Down => {
+                let target = offset(row, rows.len(), direction == Direction::Down);
+                if target == row {
+                    return key;
+                }
+                let (u, _) = layout.key(key).center();
+                Self::nearest_key(layout, rows[target].iter().copied(), u, f64::NAN).unwrap_or(key)
+            }
+        }
+    }
+
+    /// Key whose center is nearest (u, v); a NaN `v` compares horizontally only
+    /// 中心が (u, v) に最も近いキー。`v` が NaN なら横方向だけで比べる
+    fn nearest_key(
+        layout: &KeyLayout,
+        keys: impl Iterator<Item = usize>,
+        u: f64,
+        v: f64,
+    ) -> Option<usize> {
+        let distance = |key: usize| {
+            let (center_u, center_v) = layout.key(key).center();
+            let dv = if v.is_nan() { 0.0 } else { center_v - v };
+            (center_u - u).hypot(dv)
+        };
+        keys.min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::key_layout::{key, test_layout};
+    use super::*;
+
+    const PARAMS: KeyNavParams = KeyNavParams {
+        deadzone: 0.4,
+        repeat_delay_seconds: 0.5,
+        repeat_interval_seconds: 0.25,
+        min_repeat_interval_seconds: 0.125,
+        acceleration: 0.5,
+        wrap: false,
+    };
+
+    const RIGHT: (f64, f64) = (1.0, 0.0);
+    const CENTER: (f64, f64) = (0.0, 0.0);
+
+    /// Feed (stick, commit, seconds) reads and collect the focused key ids
+    /// (スティック, 確定, 秒) を入力し、フォーカス中のキー ID を集める
+    fn focus_ids(
+        layout: &KeyLayout,
+        params: &KeyNavParams,
+        reads: &[((f64, f64), bool, f64)],
+    ) -> Vec<String> {
+        let mut navigator = KeyNavigator::default();
+        reads
+            .iter()
+            .map(|&(stick, commit, seconds)| {
+                let status = navigator.update(params, layout, stick, commit, seconds);
+                layout.key(status.focus.unwrap()).id.clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn starts_in_the_middle_and_moves_once_per_push() {
+        let reads = [
+            (CENTER, false, 0.0),
+            (RIGHT, false, 0.1),
+            (RIGHT, false, 0.2),
+            (CENTER, false, 0.3),
+            ((0.0, -1.0), false, 0.4),
+            ((0.0, -1.0), false, 0.45),
+        ];
+        // "w" and "s" tie for the middle; the first in layout order wins
+        // 中央は "w" と "s" が同点で、レイアウト順で先のキーになる
+        assert_eq!(
+            focus_ids(&test_layout(), &PARAMS, &reads),
+            ["w", "e", "e", "e", "d", "d"]
+        );
+    }
+
+    #[test]
+    fn held_direction_repeats_with_acceleration() {
+        let keys = (0..20)
+            .map(|i| key(&i.to_string(), 0, i as f64 * 0.05, 0.0, 0.04, 1.0))
+            .collect();
+        let layout = KeyLayout::new(keys).unwrap();
+        let mut navigator = KeyNavigator::default();
+        let moves: Vec<f64> = (0..=12)
+            .map(|i| i as f64 * 0.125)
+            .filter(|&seconds| {
+                let stick = if seconds == 0.0 { CENTER } else { RIGHT };
+                navigator
+                    .update(&PARAMS, &layout, stick, false, seconds)
+                    .moved
+            })
+            .collect();
+        // Push at 0.125, first repeat after the 0.5 delay, then 0.25, then the 0.125 floor
+        // 0.125 に倒し、0.5 の遅延後に最初のリピート、次は 0.25、その後は下限の 0.125
+        assert_eq!(moves, [0.125, 0.625, 0.875, 1.0, 1.125, 1.25, 1.375, 1.5]);
+    }
+
+    #[test]
+    fn edges_clamp_or_wrap() {
+        let reads = [
+            (RIGHT, false, 0.0),
+            (CENTER, false, 0.1),
+            (RIGHT, false, 0.2),
+            (CENTER, false, 0.3),
+            ((0.0, 1.0), false, 0.4),
+        ];
+        assert_eq!(
+            focus_ids(&test_layout(), &PARAMS, &reads),
+            ["e", "e", "e", "e", "e"]
+        );
+        let wrap = KeyNavParams {
+            wrap: true,
+            ..PARAMS
+        };
+        assert_eq!(
+            focus_ids(&test_layout(), &wrap, &reads),
+            ["e", "e", "q", "q", "a"]
+        );
+    }
+
+    #[test]
+    fn commit_fires_once_per_press_on_the_focused_key() {
+        let layout = test_layout();
+        let mut navigator = KeyNavigator::default();
+        let mut commits = Vec::new();
+        for (stick, commit, seconds) in [
+            (CENTER, false, 0.0),
+            (CENTER, true, 0.1),
+            (CENTER, true, 0.2),
+            (RIGHT, false, 0.3),
+            (CENTER, true, 0.4),
+        ] {
+            let status = navigator.update(&PARAMS, &layout, stick, commit, seconds);
+            commits.extend(status.committed.map(|key| layout.key(key).id.clone()));
+        }
+        assert_eq!(commits, ["w", "e"]);
+    }
+
+    #[test]
+    fn diagonal_roll_keeps_the_held_direction() {
+        let reads = [
+            (RIGHT, false, 0.0),
+            ((0.6, -0.7), false, 0.05),
+            ((0.1, -0.9), false, 0.1),
+        ];
+        // Down takes over only once the right component falls below half the deadzone
+        // 右成分がデッドゾーンの半分を下回って初めて下に切り替わる
+        let wide = vec![
+            key("a", 0, 0.0, 0.0, 0.25, 0.5),
+            key("b", 0, 0.25, 0.0, 0.25, 0.5),
+            key("c", 0, 0.5, 0.0, 0.25, 0.5),
+            key("x", 1, 0.0, 0.5, 0.25, 0.5),
+            key("y", 1, 0.25, 0.5, 0.25, 0.5),
+            key("z", 1, 0.5, 0.5, 0.25, 0.5),
+        ];
+        let layout = KeyLayout::new(wide).unwrap();
+        assert_eq!(focus_ids(&layout, &PARAMS, &reads), ["c", "c", "z"]);
+    }
+}
@@ -0,0 +1,130 @@
+use napi_derive::napi;
+
+use super::errors::timestamp_seconds;
+use super::key_nav::KeyNavParams;
+use super::manager::OverlayManager;
+use super::types::{KeyNavigationParams, KeyNavigationState};
+
+impl KeyNavParams {
+    fn from_napi(params: &KeyNavigationParams) -> napi::Result<Self> {
+        let params = Self {
+            deadzone: params.deadzone,
+            repeat_delay_seconds: params.repeatDelayMs / 1000.0,
+            repeat_interval_seconds: params.repeatIntervalMs / 1000.0,
+            min_repeat_interval_seconds: params.minRepeatIntervalMs / 1000.0,
+            acceleration: params.acceleration,
+            wrap: params.wrap,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn to_napi(self) -> KeyNavigationParams {
+        KeyNavigationParams {
+            deadzone: self.deadzone,
+            repeatDelayMs: self.repeat_delay_seconds * 1000.0,
+            repeatIntervalMs: self.repeat_interval_seconds * 1000.0,
+            minRepeatIntervalMs: self.min_repeat_interval_seconds * 1000.0,
+            acceleration: self.acceleration,
+            wrap: self.wrap,
+        }
+    }
+}
+
+impl OverlayManager {
+    fn advance_key_navigation(
+        &self,
+        controller_index: u32,
+        stick: (f64, f64),
+        commit: bool,
+        seconds: f64,
+    ) -> napi::Result<KeyNavigationState> {
+        let status = {
+            let layout = self.borrow_key_layout()?;
+            self.borrow_key_navigators_mut()?
+                .entry(controller_index)
+                .or_default()
+                .update(&self.key_nav_params(), &layout, stick, commit, seconds)
+        };
+        Ok(KeyNavigationState {
+            key: self.key_id(status.focus)?,
+            moved: status.moved,
+            committedKey: self.key_id(status.committed)?,
+        })
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Move the key focus cursor with a controller's joystick, or its touchpad while the
+    /// pad is clicked, over the keys set by `setKeyLayout`. The trigger, a stick click or a
+    /// click in the middle of the touchpad commits the focused key. Each controller keeps
+    /// its own focus. Call once per frame; needs no pose, so it keeps working when
+    /// tracking is lost.
+    /// コントローラーのジョイスティック (またはクリック中のタッチパッド) で、`setKeyLayout` で
+    /// 設定したキーの上のフォーカスカーソルを動かす。トリガー、スティックのクリック、
+    /// タッチパッド中央のクリックでフォーカス中のキーを確定する。フォーカスはコントローラーごとに
+    /// 持つ。毎フレーム1回呼ぶ。ポーズを使わないため、トラッキングが失われても動作する。
+    #[napi]
+    pub fn update_key_navigation(
+        &self,
+        controller_index: u32,
+        timestamp_ms: Option<f64>,
+    ) -> napi::Result<KeyNavigationState> {
+        let state = self.get_controller_state(controller_index)?;
+        let seconds = self.input_seconds(timestamp_ms)?;
+        // A clicked touchpad acts as a D-pad: its edges move and its middle commits
+        // クリック中のタッチパッドは D-pad として働き、端で移動、中央で確定する
+        let (stick, pad_commit) = if state.touchpadPressed {
+            let pad = (state.touchpadX, state.touchpadY);
+            let centered = pad.0.hypot(pad.1) < self.key_nav_params().deadzone;
+            (if centered { (0.0, 0.0) } else { pad }, centered)
+        } else {
+            ((state.joystickX, state.joystickY), false)
+        };
+        let commit = state.triggerPressed || state.joystickPressed || pad_commit;
+        self.advance_key_navigation(controller_index, stick, commit, seconds)
+    }
+
+    /// Feed a recorded stick position (-1..1, y up) and commit button for a controller
+    /// instead of reading it / コントローラーを読む代わりに、記録済みのスティック位置
+    /// (-1..1、y は上) と確定ボタンをそのコントローラーの入力として与える
+    #[napi]
+    pub fn feed_key_navigation(
+        &self,
+        controller_index: u32,
+        x: f64,
+        y: f64,
+        commit: bool,
+        timestamp_ms: f64,
+    ) -> napi::Result<KeyNavigationState> {
+        let seconds = timestamp_seconds(timestamp_ms)?;
+        self.advance_key_navigation(controller_index, (x, y), commit, seconds)
+    }
+
+    /// Drop the focus of one controller or all of them; the next update starts again
+    /// from the middle of the keyboard
+    /// 1台または全台のフォーカスを捨てる。次の更新でキーボード中央からやり直す
+    #[napi]
+    pub fn reset_key_navigation(&self, controller_index: Option<u32>) -> napi::Result<()> {
+        let mut navigators = self.borrow_key_navigators_mut()?;
+        match controller_index {
+            Some(index) => {
+                navigators.remove(&index);
+            }
+            None => navigators.clear(),
+        }
+        Ok(())
+    }
+
+    #[napi]
+    pub fn set_key_navigation_params(&self, params: KeyNavigationParams) -> napi::Result<()> {
+        self.set_key_nav_params(KeyNavParams::from_napi(&params)?);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_key_navigation_params(&self) -> KeyNavigationParams {
+        self.key_nav_params().to_napi()
+    }
+}
@@ -0,0 +1,303 @@
+use super::poke::{PlaneSample, TouchTransition};
+
+/// Drumstick mallet shape and strike thresholds; lengths in meters
+/// ドラムスティックのマレット形状と打鍵しきい値 (長さはメートル)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct MalletParams {
+    pub radius: f64,
+    pub head_offset: f64,      // Ahead of the aim pose / エイムポーズの前方
+    pub press_depth: f64,      // Behind the surface / 面の奥側
+    pub release_height: f64,   // Releases and re-arms / 解放して再装填する高さ
+    pub hover_range: f64,      // Of the sphere surface / 球面の高さ
+    pub min_velocity: f64,     // Approach speed (m/s) / 接近速度 (m/s)
+    pub debounce_seconds: f64, // Between strikes / 打鍵間の最短時間
+}
+
+impl MalletParams {
+    /// A small head just past the controller tip that needs a deliberate tap
+    /// コントローラー先端の少し先にある小さなヘッド。意図的なタップが必要
+    pub(super) const DEFAULT: Self = Self {
+        radius: 0.015,
+        head_offset: 0.05,
+        press_depth: 0.002,
+        release_height: 0.01,
+        hover_range: 0.1,
+        min_velocity: 0.3,
+        debounce_seconds: 0.06,
+    };
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        let non_negative = |value: f64| value.is_finite() && value >= 0.0;
+        let all_non_negative = [
+            self.radius,
+            self.head_offset,
+            self.press_depth,
+            self.release_height,
+            self.min_velocity,
+            self.debounce_seconds,
+        ]
+        .into_iter()
+        .all(non_negative);
+        if !all_non_negative {
+            return Err(napi::Error::from_reason(
+                "drumstick parameters must be non-negative numbers",
+            ));
+        }
+        if !self.hover_range.is_finite() || self.hover_range < self.release_height {
+            return Err(napi::Error::from_reason(
+                "hoverRange must be a number no smaller than releaseHeight",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Center-to-surface range to cast over / キャストする中心から面までの範囲
+    pub(super) fn cast_range(&self) -> f64 {
+        (self.hover_range + self.radius) * 2.0
+    }
+}
+
+/// Strike state of one mallet. A strike needs the sphere to come from above
+/// `release_height` (armed), sink `press_depth` into the overlay while approaching at
+/// `min_velocity` or faster, and be at least `debounce_seconds` after the previous strike.
+/// 1本のマレットの打鍵状態。打鍵には、球が `release_height` より上から来て (装填済み)、
+/// `min_velocity` 以上で接近しながらオーバーレイに `press_depth` 沈み込み、
+/// 前回の打鍵から `debounce_seconds` 以上経過している必要がある。
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct MalletTracker {
+    hovering: bool,
+    pressed: bool,
+    armed: bool,
+    last: Option<(PlaneSample, f64)>,
+    last_strike: Option<f64>,
+}
+
+impl MalletTracker {
+    /// Advance with the sphere center's sample at `seconds`; reported distances are of the
+    /// sphere surface. Same inputs always give the same transitions.
+    /// `seconds` 時点の球の中心のサンプルで状態を進める。報告する距離は球面のもの。
+    /// 同じ入力からは常に同じ遷移が得られる。
+    pub(super) fn update(
+        &mut self,
+        params: &MalletParams,
+        sample: Option<PlaneSample>,
+        seconds: f64,
+    ) -> Vec<(TouchTransition, PlaneSample)> {
+        let mut transitions = Vec::new();
+        let surface = sample
+            .map(|s| PlaneSample {
+                distance: s.distance - params.radius,
+                ..s
+            })
+            .filter(|s| s.distance <= params.hover_range);
+        let Some(current) = surface else {
+            if let Some((last, _)) = self.last.take() {
+                if self.pressed {
+                    transitions.push((TouchTransition::Release, last));
+                }
+                if self.hovering {
+                    transitions.push((TouchTransition::HoverEnd, last));
+                }
+            }
+            // Re-arm only once seen above the surface again / 面より上で再び検出されるまで再装填しない
+            self.hovering = false;
+            self.pressed = false;
+            self.armed = false;
+            return transitions;
+        };
+
+        let approach_speed = match self.last {
+            Some((previous, at)) if seconds > at => {
+                (previous.distance - current.distance) / (seconds - at)
+            }
+            _ => 0.0,
+        };
+        self.hovering = true;
+        self.last = Some((current, seconds));
+        transitions.push((TouchTransition::Hover, current));
+
+        if self.pressed {
+            if current.distance >= params.release_height {
+                self.pressed = false;
+                self.armed = true;
+                transitions.push((TouchTransition::Release, current));
+            }
+        } else if current.distance >= params.release_height {
+            self.armed = true;
+        } else if self.armed && current.distance <= -params.press_depth {
+            // Too slow or too soon still uses up the stroke / 遅すぎ・早すぎでもそのストロークは消費する
+            self.armed = false;
+            let debounced = self
+                .last_strike
+                .is_none_or(|at| seconds - at >= params.debounce_seconds);
+            if approach_speed >= params.min_velocity && debounced {
+                self.pressed = true;
+                self.last_strike = Some(seconds);
+                transitions.push((TouchTransition::Press, current));
+            }
+        }
+        transitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::poke::sample_test_plane;
+    use super::*;
+    use TouchTransition::{Hover, HoverEnd, Press, Release};
+
+    const PARAMS: MalletParams = MalletParams {
+        radius: 0.25,
+        head_offset: 0.0,
+        press_depth: 0.125,
+        release_height: 0.25,
+        hover_range: 1.0,
+        min_velocity: 1.0,
+        debounce_seconds: 0.5,
+    };
+
+    /// Feed (sphere surface height, seconds) pairs, None when the mallet is lost, and
+    /// collect the transitions of each update
+    /// (球面の高さ, 秒) の組を入力し (マレットを見失ったら None)、更新ごとの遷移を集める
+    fn strikes(samples: &[(Option<f64>, f64)]) -> Vec<Vec<TouchTransition>> {
+        let mut tracker = MalletTracker::default();
+        samples
+            .iter()
+            .map(|&(height, seconds)| {
+                let sample = height.map(|height| PlaneSample {
+                    u: 0.5,
+                    v: 0.5,
+                    distance: height + PARAMS.radius,
+                });
+                tracker
+                    .update(&PARAMS, sample, seconds)
+                    .into_iter()
+                    .map(|(transition, _)| transition)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn strike_at_min_velocity_presses_and_releases_at_release_height() {
+        let transitions = strikes(&[
+            (Some(0.5), 0.0),
+            (Some(0.0), 0.5),
+            // 0.125 m in 0.125 s, exactly min_velocity / ちょうど min_velocity
+            (Some(-0.125), 0.625),
+            (Some(0.125), 0.75),
+            (Some(0.25), 1.0),
+        ]);
+        assert_eq!(
+            transitions,
+            [
+                vec![Hover],
+                vec![Hover],
+                vec![Hover, Press],
+                vec![Hover],
+                vec![Hover, Release],
+            ]
+        );
+    }
+
+    #[test]
+    fn slow_push_uses_up_the_stroke_until_rearmed_above_release_height() {
+        let transitions = strikes(&[
+            (Some(0.5), 0.0),
+            (Some(0.0), 1.0),
+            (Some(-0.125), 2.0), // Too slow / 遅すぎる
+            (Some(-0.5), 2.25),  // Fast, but the stroke is spent / 速いがストロークは消費済み
+            (Some(0.125), 3.0), // Below release_height: not armed / release_height 未満なので未装填
+            (Some(-0.25), 3.125),
+            (Some(0.25), 4.0), // Armed / 装填
+            (Some(-0.125), 4.125),
+        ]);
+        assert_eq!(
+            transitions,
+            [
+                vec![Hover],
+                vec![Hover],
+                vec![Hover],
+                vec![Hover],
+                vec![Hover],
+                vec![Hover],
+                vec![Hover],
+                vec![Hover, Press],
+            ]
+        );
+    }
+
+    #[test]
+    fn second_strike_within_debounce_is_rejected() {
+        let transitions = strikes(&[
+            (Some(0.5), 0.0),
+            (Some(-0.125), 0.25), // Strike / 打鍵
+            (Some(0.25), 0.375),
+            (Some(-0.125), 0.5), // 0.25 s after the strike / 打鍵から 0.25 秒
+            (Some(0.25), 0.625),
+            (Some(-0.125), 0.75), // 0.5 s after the strike / 打鍵から 0.5 秒
+        ]);
+        assert_eq!(
+            transitions,
+            [
+                vec![Hover],
+                vec![Hover, Press],
+                vec![Hover, Release],
+                vec![Hover],
+                vec![Hover],
+                vec![Hover, Press],
+            ]
+        );
+    }
+
+    #[test]
+    fn losing_the_mallet_releases_ends_hover_and_disarms() {
+        let transitions = strikes(&[
+            (Some(0.5), 0.0),
+            (Some(-0.125), 0.25),
+            (None, 0.375),
+            (None, 0.5),
+            // Back below release_height: still not armed / release_height 未満で戻っても未装填
+            (Some(0.125), 1.0),
+            (Some(-0.25), 1.125),
+        ]);
+        assert_eq!(
+            transitions,
+            [
+                vec![Hover],
+                vec![Hover, Press],
+                vec![Release, HoverEnd],
+                vec![],
+                vec![Hover],
+                vec![Hover],
+            ]
+        );
+    }
+
+    #[test]
+    fn strikes_only_once_the_head_sinks_behind_the_surface_of_a_sampled_plane() {
+        // Sphere centers over the plane z = 0, sampled like `sample_overlay_plane`
+        // `sample_overlay_plane` と同じ方法で平面 z = 0 上の球の中心をサンプリングする
+        let mut tracker = MalletTracker::default();
+        let mut strike = |center_z: f64, seconds: f64| -> Vec<TouchTransition> {
+            let sample = sample_test_plane([0.5, 0.5, center_z], PARAMS.cast_range());
+            tracker
+                .update(&PARAMS, sample, seconds)
+                .into_iter()
+                .map(|(transition, _)| transition)
+                .collect()
+        };
+        assert_eq!(strike(1.0, 0.0), [Hover]);
+        // Fast, but still in front of the surface / 速いがまだ面の手前
+        assert_eq!(strike(0.375, 0.25), [Hover]);
+        // The surface sinks 0.125 behind the overlay / 球面がオーバーレイの 0.125 奥に沈む
+        assert_eq!(strike(0.125, 0.375), [Hover, Press]);
+        assert_eq!(strike(0.5, 0.5), [Hover, Release]);
+    }
+
+    #[test]
+    fn samples_beyond_hover_range_count_as_lost() {
+        let transitions = strikes(&[(Some(0.5), 0.0), (Some(1.5), 0.25)]);
+        assert_eq!(transitions, [vec![Hover], vec![HoverEnd]]);
+    }
+}
@@ -0,0 +1,139 @@
+use napi_derive::napi;
+
+use super::errors::timestamp_seconds;
+use super::handles::overlay_handle;
+use super::mallet::MalletParams;
+use super::manager::OverlayManager;
+use super::math::transform_point;
+use super::poke::PlaneSample;
+use super::poke_ops::{hand_slot, touch_event, TOUCH_HANDS};
+use super::types::{DrumstickParams, InputHand, TouchEvent, TouchSample};
+
+impl MalletParams {
+    fn from_napi(params: &DrumstickParams) -> napi::Result<Self> {
+        let params = Self {
+            radius: params.radius,
+            head_offset: params.headOffset,
+            press_depth: params.pressDepth,
+            release_height: params.releaseHeight,
+            hover_range: params.hoverRange,
+            min_velocity: params.minVelocity,
+            debounce_seconds: params.debounceMs / 1000.0,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn to_napi(self) -> DrumstickParams {
+        DrumstickParams {
+            radius: self.radius,
+            headOffset: self.head_offset,
+            pressDepth: self.press_depth,
+            releaseHeight: self.release_height,
+            hoverRange: self.hover_range,
+            minVelocity: self.min_velocity,
+            debounceMs: self.debounce_seconds * 1000.0,
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Mallet sphere center in the configured universe, ahead of the hand's aim pose
+    /// 設定ユニバースでのマレット球の中心 (手のエイムポーズの前方)
+    fn mallet_head_position(
+        &self,
+        hand: InputHand,
+        params: &MalletParams,
+    ) -> napi::Result<Option<[f64; 3]>> {
+        Ok(self.aim_pose(hand)?.map(|pose| {
+            transform_point(
+                &pose.mDeviceToAbsoluteTracking,
+                [0.0, 0.0, -params.head_offset],
+            )
+        }))
+    }
+
+    fn advance_mallet(
+        &self,
+        hand: InputHand,
+        sample: Option<PlaneSample>,
+        seconds: f64,
+    ) -> napi::Result<Vec<TouchEvent>> {
+        let params = self.mallet_params();
+        let transitions =
+            self.borrow_mallet_trackers_mut()?[hand_slot(hand)].update(&params, sample, seconds);
+        Ok(transitions
+            .into_iter()
+            .map(|(transition, sample)| touch_event(hand, transition, sample))
+            .collect())
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Strike the overlay with a sphere at each controller tip ("drumstick" typing) and
+    /// return hover, press, release and hover-end events; distances are of the sphere surface.
+    /// 各コントローラー先端の球でオーバーレイを叩き (ドラムスティック入力)、ホバー・押下・
+    /// 解放・ホバー終了のイベントを返す。距離は球面のもの。
+    #[napi]
+    pub fn poll_drumsticks(
+        &self,
+        handle: i64,
+        timestamp_ms: Option<f64>,
+    ) -> napi::Result<Vec<TouchEvent>> {
+        let handle = overlay_handle(handle)?;
+        let seconds = self.input_seconds(timestamp_ms)?;
+        let params = self.mallet_params();
+        let mut events = Vec::new();
+        for hand in TOUCH_HANDS {
+            let sample = match self.mallet_head_position(hand, &params)? {
+                Some(head) => self.sample_overlay_plane(handle, head, params.cast_range())?,
+                None => None,
+            };
+            events.extend(self.advance_mallet(hand, sample, seconds)?);
+        }
+        Ok(events)
+    }
+
+    /// Feed a recorded sphere-center sample (None when lost) instead of live poses, so strike
+    /// sequences can be replayed deterministically.
+    /// ライブのポーズの代わりに記録済みの球の中心のサンプル (消失時は None) を入力し、
+    /// 打鍵シーケンスを決定的に再生できるようにする。
+    #[napi]
+    pub fn feed_drumstick_sample(
+        &self,
+        hand: InputHand,
+        sample: Option<TouchSample>,
+        timestamp_ms: f64,
+    ) -> napi::Result<Vec<TouchEvent>> {
+        if hand == InputHand::Any {
+            return Err(napi::Error::from_reason(
+                "drumstick hand must be Left or Right",
+            ));
+        }
+        let seconds = timestamp_seconds(timestamp_ms)?;
+        let sample = sample.map(|sample| PlaneSample {
+            u: sample.u,
+            v: sample.v,
+            distance: sample.distance,
+        });
+        self.advance_mallet(hand, sample, seconds)
+    }
+
+    #[napi]
+    pub fn reset_drumsticks(&self) -> napi::Result<()> {
+        *self.borrow_mallet_trackers_mut()? = Default::default();
+        Ok(())
+    }
+
+    #[napi]
+    pub fn set_drumstick_params(&self, params: DrumstickParams) -> napi::Result<()> {
+        self.set_mallet_params(MalletParams::from_napi(&params)?);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_drumstick_params(&self) -> DrumstickParams {
+        self.mallet_params().to_napi()
+    }
+}
@@ -1,6 +1,7 @@
 use napi_derive::napi;
 use openvr_sys as vr;
 use std::cell::{Cell, RefCell};
+use std::collections::{BTreeSet, HashMap};
 use std::marker::PhantomData;
 use std::ptr::NonNull;
 use std::rc::Rc;
@@ -10,18 +11,37 @@ use std::sync::{
 };
 use std::time::{Duration, Instant};
 
+use super::arbitration::{ArbitrationPolicy, PointerArbiter};
+use super::button_map::LegacyButtonMaps;
 use super::constants::{
     DEFAULT_INPUT_INTERFACE, DEFAULT_OVERLAY_INTERFACE, DEFAULT_SYSTEM_INTERFACE,
     INPUT_INTERFACE_ENV, OVERLAY_INTERFACE_ENV, SYSTEM_INTERFACE_ENV,
 };
 use super::d3d11;
 use super::d3d11::D3D11Context;
-use super::errors::{cstring_from_env, init_error_message};
+use super::device_ops::DeviceSelector;
+use super::errors::{cstring_from_env, init_error_message, timestamp_seconds};
+use super::event_ops::VrEventCallback;
+use super::gaze::{GazeDwell, GazeParams};
+use super::gesture::{GestureParams, TriggerGesture};
+use super::key_layout::KeyLayout;
+use super::key_nav::{KeyNavParams, KeyNavigator};
+use super::mallet::{MalletParams, MalletTracker};
+use super::poke::{PokeParams, PokeTracker};
+use super::poller::{InputPoller, PollerConfig};
+use super::pose_ops::PosePrediction;
+use super::presence_ops::{PresenceFlags, TextureThrottle};
+use super::scanning::{ScanConfig, SwitchScanner};
+use super::stabilizer::{PointerStabilizer, StabilizerParams};
+use super::swipe::{Lexicon, SwipeParams, SwipeRecorder};
+use super::toggle::{ToggleConfig, ToggleRecognizer};
+use super::touch_model::{CorrectionTracker, TouchModels, TouchParams};
+use super::types::{InputActionType, InputHand};
 
 static VR_INIT_COUNT: AtomicUsize = AtomicUsize::new(0);
 static VR_INIT_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 
-fn create_poses_cache() -> Vec<vr::TrackedDevicePose_t> {
+pub(super) fn create_poses_cache() -> Vec<vr::TrackedDevicePose_t> {
     let mut poses = Vec::with_capacity(vr::k_unMaxTrackedDeviceCount as usize);
     poses.resize_with(vr::k_unMaxTrackedDeviceCount as usize, || unsafe {
         std::mem::zeroed()
@@ -29,8 +49,8 @@ fn create_poses_cache() -> Vec<vr::TrackedDevicePose_t> {
     poses
 }
 
-/// Pose cache time-to-live / ポーズキャッシュの有効期間
-const POSE_CACHE_TTL: Duration = Duration::from_micros(2000);
+/// Default pose cache time-to-live / ポーズキャッシュの既定の有効期間
+const DEFAULT_POSE_CACHE_TTL: Duration = Duration::from_micros(2000);
 
 struct VrContext {
     overlay: Option<NonNull<vr::VR_IVROverlay_FnTable>>,
@@ -38,17 +58,21 @@ struct VrContext {
     input: Option<NonNull<vr::VR_IVRInput_FnTable>>,
 }
 
+/// Action resolved from an `InputActionDefinition` / `InputActionDefinition` から解決したアクション
+#[derive(Clone, Copy, Debug)]
+pub(super) struct InputAction {
+    pub handle: vr::VRActionHandle_t,
+    pub action_type: InputActionType,
+    pub hand: InputHand,
+}
+
 pub(super) struct InputActionCache {
     pub initialized: bool,
     pub action_set_handle: vr::VRActionSetHandle_t,
-    pub toggle_action_handle: vr::VRActionHandle_t,
-    pub trigger_action_handle: vr::VRActionHandle_t,
-    pub grip_action_handle: vr::VRActionHandle_t,
+    pub actions: HashMap<String, InputAction>,
     pub left_hand_source: vr::VRInputValueHandle_t,
     pub right_hand_source: vr::VRInputValueHandle_t,
-    pub last_toggle_state: bool,
-    pub toggle_lock: bool,
-    pub toggle_release_streak: u8,
+    pub toggle: ToggleRecognizer,
 }
 
 impl InputActionCache {
@@ -56,18 +80,42 @@ impl InputActionCache {
         Self {
             initialized: false,
             action_set_handle: 0,
-            toggle_action_handle: 0,
-            trigger_action_handle: 0,
-            grip_action_handle: 0,
+            actions: HashMap::new(),
             left_hand_source: 0,
             right_hand_source: 0,
-            last_toggle_state: false,
-            toggle_lock: false,
-            toggle_release_streak: 0,
+            toggle: ToggleRecognizer::default(),
+        }
+    }
+
+    pub(super) fn action(&self, name: &str) -> Option<InputAction> {
+        self.actions.get(name).copied()
+    }
+
+    /// Input source for a hand, `k_ulInvalidInputValueHandle` for any
+    /// 手に対応する入力ソース。どちらでもよい場合は `k_ulInvalidInputValueHandle`
+    pub(super) fn hand_source(&self, hand: InputHand) -> vr::VRInputValueHandle_t {
+        match hand {
+            InputHand::Any => vr::k_ulInvalidInputValueHandle,
+            InputHand::Left => self.left_hand_source,
+            InputHand::Right => self.right_hand_source,
         }
     }
 }
 
+/// Overlay pinned to a tracked device, re-applied when the device index changes
+/// トラッキングデバイスに固定されたオーバーレイ。デバイスインデックスが変わると再適用される
+pub(super) struct DeviceAttachment {
+    pub selector: DeviceSelector,
+    pub device_index: Option<u32>,
+    pub transform: vr::HmdMatrix34_t,
+}
+
+/// Overlay placed relative to another overlay / 別のオーバーレイに対して相対配置されたオーバーレイ
+pub(super) struct OverlayParentLink {
+    pub parent: u64,
+    pub transform: vr::HmdMatrix34_t,
+}
+
 /// VRオーバーレイを管理するN-APIラッパー。
 ///
 /// 注意: `!Send` / `!Sync` のため同一スレッドでのみ使用してください。
@@ -77,7 +125,46 @@ pub struct OverlayManager {
     d3d11: Option<D3D11Context>,
     poses_cache: RefCell<Vec<vr::TrackedDevicePose_t>>,
     poses_timestamp: Cell<Option<Instant>>,
+    poses_cache_ttl: Cell<Duration>,
+    pose_prediction: Cell<PosePrediction>,
     input_cache: RefCell<InputActionCache>,
+    device_attachments: RefCell<HashMap<u64, DeviceAttachment>>,
+    overlay_parents: RefCell<HashMap<u64, OverlayParentLink>>,
+    owned_overlays: RefCell<BTreeSet<u64>>,
+    tracking_universe: Cell<vr::ETrackingUniverseOrigin>,
+    seated_zero_pose: Cell<Option<vr::HmdMatrix34_t>>,
+    presence_flags: Cell<PresenceFlags>,
+    texture_throttle: Cell<TextureThrottle>,
+    last_texture_upload: Cell<Option<Instant>>,
+    default_stabilizer_params: Cell<StabilizerParams>,
+    stabilizer_params: RefCell<HashMap<u32, StabilizerParams>>,
+    pointer_stabilizers: RefCell<HashMap<u32, PointerStabilizer>>,
+    stabilizer_epoch: Instant,
+    input_poller: RefCell<Option<InputPoller>>,
+    vr_event_listener: RefCell<Option<VrEventCallback>>,
+    poke_params: Cell<PokeParams>,
+    poke_trackers: RefCell<[PokeTracker; 2]>,
+    mallet_params: Cell<MalletParams>,
+    mallet_trackers: RefCell<[MalletTracker; 2]>,
+    gesture_params: Cell<GestureParams>,
+    trigger_gestures: RefCell<HashMap<u32, TriggerGesture>>,
+    toggle_config: RefCell<ToggleConfig>,
+    legacy_button_maps: RefCell<LegacyButtonMaps>,
+    arbitration_policy: Cell<ArbitrationPolicy>,
+    pointer_arbiter: RefCell<PointerArbiter>,
+    key_layout: RefCell<KeyLayout>,
+    gaze_params: Cell<GazeParams>,
+    gaze_dwell: Cell<GazeDwell>,
+    scan_config: RefCell<ScanConfig>,
+    switch_scanner: Cell<SwitchScanner>,
+    key_nav_params: Cell<KeyNavParams>,
+    key_navigators: RefCell<HashMap<u32, KeyNavigator>>,
+    swipe_params: Cell<SwipeParams>,
+    swipe_lexicon: RefCell<Lexicon>,
+    swipe_recorder: RefCell<SwipeRecorder>,
+    touch_params: Cell<TouchParams>,
+    touch_models: RefCell<TouchModels>,
+    correction_tracker: RefCell<CorrectionTracker>,
     _vr_token: Option<isize>,
     // Make the manager !Send/!Sync unless we can prove thread safety / スレッドセーフティを証明できない限り、マネージャーを!Send/!Syncにする
     _not_send: PhantomData<Rc<()>>,
@@ -121,7 +208,7 @@ impl OverlayManager {
     /// Check if cached poses are still valid / キャッシュされたポーズがまだ有効か確認
     pub(super) fn poses_cache_valid(&self) -> bool {
         match self.poses_timestamp.get() {
-            Some(ts) => ts.elapsed() < POSE_CACHE_TTL,
+            Some(ts) => ts.elapsed() < self.poses_cache_ttl.get(),
             None => false,
         }
     }
@@ -131,6 +218,239 @@ impl OverlayManager {
         self.poses_timestamp.set(Some(Instant::now()));
     }
 
+    pub(super) fn poses_cache_ttl(&self) -> Duration {
+        self.poses_cache_ttl.get()
+    }
+
+    pub(super) fn set_poses_cache_ttl(&self, ttl: Duration) {
+        self.poses_cache_ttl.set(ttl);
+    }
+
+    pub(super) fn pose_prediction(&self) -> PosePrediction {
+        self.pose_prediction.get()
+    }
+
+    pub(super) fn set_pose_prediction_mode(&self, prediction: PosePrediction) {
+        self.pose_prediction.set(prediction);
+        self.invalidate_poses_cache();
+    }
+
+    /// Force the next pose query to hit OpenVR / 次のポーズ取得で必ず OpenVR に問い合わせる
+    pub(super) fn invalidate_poses_cache(&self) {
+        self.poses_timestamp.set(None);
+    }
+
+    /// Universe origin used for every pose and absolute transform call
+    /// すべてのポーズ取得と絶対変換呼び出しで使うユニバース原点
+    pub(super) fn tracking_universe(&self) -> vr::ETrackingUniverseOrigin {
+        self.tracking_universe.get()
+    }
+
+    pub(super) fn set_tracking_universe_origin(&self, origin: vr::ETrackingUniverseOrigin) {
+        self.tracking_universe.set(origin);
+        self.invalidate_poses_cache();
+    }
+
+    pub(super) fn seated_zero_pose(&self) -> Option<vr::HmdMatrix34_t> {
+        self.seated_zero_pose.get()
+    }
+
+    pub(super) fn set_seated_zero_pose(&self, pose: vr::HmdMatrix34_t) {
+        self.seated_zero_pose.set(Some(pose));
+    }
+
+    pub(super) fn presence_flags(&self) -> PresenceFlags {
+        self.presence_flags.get()
+    }
+
+    pub(super) fn set_presence_flags(&self, flags: PresenceFlags) {
+        self.presence_flags.set(flags);
+    }
+
+    pub(super) fn texture_throttle(&self) -> TextureThrottle {
+        self.texture_throttle.get()
+    }
+
+    pub(super) fn set_texture_throttle_config(&self, throttle: TextureThrottle) {
+        self.texture_throttle.set(throttle);
+    }
+
+    pub(super) fn last_texture_upload(&self) -> Option<Instant> {
+        self.last_texture_upload.get()
+    }
+
+    pub(super) fn set_last_texture_upload(&self, at: Instant) {
+        self.last_texture_upload.set(Some(at));
+    }
+
+    pub(super) fn default_stabilizer_params(&self) -> StabilizerParams {
+        self.default_stabilizer_params.get()
+    }
+
+    pub(super) fn set_default_stabilizer_params(&self, params: StabilizerParams) {
+        self.default_stabilizer_params.set(params);
+    }
+
+    /// Seconds since the manager was created, the clock of the pointer filters
+    /// マネージャー生成からの秒数 (ポインターフィルターの時計)
+    pub(super) fn stabilizer_seconds(&self) -> f64 {
+        self.stabilizer_epoch.elapsed().as_secs_f64()
+    }
+
+    /// Clock of a call taking an optional `timestampMs`: the given time, so recorded traces
+    /// replay deterministically, or the native clock when it is omitted
+    /// 省略可能な `timestampMs` を取る呼び出しの時刻。指定された時刻 (記録済みトレースを決定的に
+    /// 再生できる) か、省略時はネイティブの時計
+    pub(super) fn input_seconds(&self, timestamp_ms: Option<f64>) -> napi::Result<f64> {
+        match timestamp_ms {
+            Some(ms) => timestamp_seconds(ms),
+            None => Ok(self.stabilizer_seconds()),
+        }
+    }
+
+    pub(super) fn poke_params(&self) -> PokeParams {
+        self.poke_params.get()
+    }
+
+    pub(super) fn set_poke_params(&self, params: PokeParams) {
+        self.poke_params.set(params);
+    }
+
+    pub(super) fn mallet_params(&self) -> MalletParams {
+        self.mallet_params.get()
+    }
+
+    pub(super) fn set_mallet_params(&self, params: MalletParams) {
+        self.mallet_params.set(params);
+    }
+
+    pub(super) fn gesture_params(&self) -> GestureParams {
+        self.gesture_params.get()
+    }
+
+    pub(super) fn set_gesture_params(&self, params: GestureParams) {
+        self.gesture_params.set(params);
+    }
+
+    pub(super) fn gaze_params(&self) -> GazeParams {
+        self.gaze_params.get()
+    }
+
+    pub(super) fn set_gaze_params(&self, params: GazeParams) {
+        self.gaze_params.set(params);
+    }
+
+    pub(super) fn gaze_dwell(&self) -> GazeDwell {
+        self.gaze_dwell.get()
+    }
+
+    pub(super) fn set_gaze_dwell(&self, dwell: GazeDwell) {
+        self.gaze_dwell.set(dwell);
+    }
+
+    pub(super) fn switch_scanner(&self) -> SwitchScanner {
+        self.switch_scanner.get()
+    }
+
+    pub(super) fn set_switch_scanner(&self, scanner: SwitchScanner) {
+        self.switch_scanner.set(scanner);
+    }
+
+    pub(super) fn key_nav_params(&self) -> KeyNavParams {
+        self.key_nav_params.get()
+    }
+
+    pub(super) fn set_key_nav_params(&self, params: KeyNavParams) {
+        self.key_nav_params.set(params);
+    }
+
+    pub(super) fn borrow_key_navigators_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, HashMap<u32, KeyNavigator>>> {
+        self.key_navigators
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("key_navigators is already borrowed"))
+    }
+
+    pub(super) fn swipe_params(&self) -> SwipeParams {
+        self.swipe_params.get()
+    }
+
+    pub(super) fn set_swipe_params(&self, params: SwipeParams) {
+        self.swipe_params.set(params);
+    }
+
+    pub(super) fn touch_params(&self) -> TouchParams {
+        self.touch_params.get()
+    }
+
+    pub(super) fn set_touch_params(&self, params: TouchParams) {
+        self.touch_params.set(params);
+    }
+
+    pub(super) fn arbitration_policy(&self) -> ArbitrationPolicy {
+        self.arbitration_policy.get()
+    }
+
+    pub(super) fn set_arbitration_policy(&self, policy: ArbitrationPolicy) {
+        self.arbitration_policy.set(policy);
+    }
+
+    /// Replace the running poller; dropping the old one joins its thread
+    /// 実行中のポーラーを置き換える。古いポーラーは破棄時にスレッドを join する
+    pub(super) fn replace_input_poller(
+        &self,
+        poller: Option<InputPoller>,
+    ) -> napi::Result<Option<InputPoller>> {
+        let mut slot = self
+            .input_poller
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("input_poller is already borrowed"))?;
+        Ok(std::mem::replace(&mut *slot, poller))
+    }
+
+    pub(super) fn replace_vr_event_listener(
+        &self,
+        listener: Option<VrEventCallback>,
+    ) -> napi::Result<Option<VrEventCallback>> {
+        let mut slot = self
+            .vr_event_listener
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("vr_event_listener is already borrowed"))?;
+        Ok(std::mem::replace(&mut *slot, listener))
+    }
+
+    pub(super) fn borrow_vr_event_listener(
+        &self,
+    ) -> napi::Result<std::cell::Ref<'_, Option<VrEventCallback>>> {
+        self.vr_event_listener
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("vr_event_listener is already mutably borrowed"))
+    }
+
+    pub(super) fn input_poller_running(&self) -> napi::Result<bool> {
+        let slot = self
+            .input_poller
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("input_poller is already mutably borrowed"))?;
+        Ok(slot.as_ref().is_some_and(InputPoller::is_running))
+    }
+
+    /// Apply `update` to the running poller's config, if any / 実行中のポーラーがあれば設定に `update` を適用する
+    pub(super) fn update_input_poller(
+        &self,
+        update: impl FnOnce(&mut PollerConfig),
+    ) -> napi::Result<()> {
+        let slot = self
+            .input_poller
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("input_poller is already mutably borrowed"))?;
+        if let Some(poller) = slot.as_ref() {
+            update(&mut *poller.lock_config()?);
+        }
+        Ok(())
+    }
+
     pub(super) fn input(&self) -> napi::Result<&vr::VR_IVRInput_FnTable> {
         let ptr = self
             .context
@@ -152,6 +472,206 @@ impl OverlayManager {
             .try_borrow_mut()
             .map_err(|_| napi::Error::from_reason("input_cache is already mutably borrowed"))
     }
+
+    pub(super) fn borrow_stabilizer_params(
+        &self,
+    ) -> napi::Result<std::cell::Ref<'_, HashMap<u32, StabilizerParams>>> {
+        self.stabilizer_params
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("stabilizer_params is already mutably borrowed"))
+    }
+
+    pub(super) fn borrow_stabilizer_params_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, HashMap<u32, StabilizerParams>>> {
+        self.stabilizer_params
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("stabilizer_params is already borrowed"))
+    }
+
+    pub(super) fn borrow_pointer_stabilizers_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, HashMap<u32, PointerStabilizer>>> {
+        self.pointer_stabilizers
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("pointer_stabilizers is already borrowed"))
+    }
+
+    /// Per-hand trackers, left then right / 手ごとのトラッカー (左、右の順)
+    pub(super) fn borrow_poke_trackers_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, [PokeTracker; 2]>> {
+        self.poke_trackers
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("poke_trackers is already borrowed"))
+    }
+
+    /// Per-hand trackers, left then right / 手ごとのトラッカー (左、右の順)
+    pub(super) fn borrow_mallet_trackers_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, [MalletTracker; 2]>> {
+        self.mallet_trackers
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("mallet_trackers is already borrowed"))
+    }
+
+    pub(super) fn borrow_trigger_gestures_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, HashMap<u32, TriggerGesture>>> {
+        self.trigger_gestures
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("trigger_gestures is already borrowed"))
+    }
+
+    pub(super) fn borrow_pointer_arbiter_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, PointerArbiter>> {
+        self.pointer_arbiter
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("pointer_arbiter is already borrowed"))
+    }
+
+    pub(super) fn borrow_pointer_arbiter(
+        &self,
+    ) -> napi::Result<std::cell::Ref<'_, PointerArbiter>> {
+        self.pointer_arbiter
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("pointer_arbiter is already mutably borrowed"))
+    }
+
+    pub(super) fn borrow_key_layout(&self) -> napi::Result<std::cell::Ref<'_, KeyLayout>> {
+        self.key_layout
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("key_layout is already mutably borrowed"))
+    }
+
+    pub(super) fn borrow_key_layout_mut(&self) -> napi::Result<std::cell::RefMut<'_, KeyLayout>> {
+        self.key_layout
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("key_layout is already borrowed"))
+    }
+
+    pub(super) fn borrow_scan_config(&self) -> napi::Result<std::cell::Ref<'_, ScanConfig>> {
+        self.scan_config
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("scan_config is already mutably borrowed"))
+    }
+
+    pub(super) fn borrow_scan_config_mut(&self) -> napi::Result<std::cell::RefMut<'_, ScanConfig>> {
+        self.scan_config
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("scan_config is already borrowed"))
+    }
+
+    pub(super) fn borrow_swipe_lexicon(&self) -> napi::Result<std::cell::Ref<'_, Lexicon>> {
+        self.swipe_lexicon
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("swipe_lexicon is already mutably borrowed"))
+    }
+
+    pub(super) fn borrow_swipe_lexicon_mut(&self) -> napi::Result<std::cell::RefMut<'_, Lexicon>> {
+        self.swipe_lexicon
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("swipe_lexicon is already borrowed"))
+    }
+
+    pub(super) fn borrow_swipe_recorder_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, SwipeRecorder>> {
+        self.swipe_recorder
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("swipe_recorder is already borrowed"))
+    }
+
+    pub(super) fn borrow_touch_models(&self) -> napi::Result<std::cell::Ref<'_, TouchModels>> {
+        self.touch_models
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("touch_models is already mutably borrowed"))
+    }
+
+    pub(super) fn borrow_touch_models_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, TouchModels>> {
+        self.touch_models
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("touch_models is already borrowed"))
+    }
+
+    pub(super) fn borrow_correction_tracker_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, CorrectionTracker>> {
+        self.correction_tracker
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("correction_tracker is already borrowed"))
+    }
+
+    pub(super) fn borrow_toggle_config(&self) -> napi::Result<std::cell::Ref<'_, ToggleConfig>> {
+        self.toggle_config
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("toggle_config is already mutably borrowed"))
+    }
+
+    pub(super) fn borrow_toggle_config_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, ToggleConfig>> {
+        self.toggle_config
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("toggle_config is already borrowed"))
+    }
+
+    pub(super) fn borrow_legacy_button_maps(
+        &self,
+    ) -> napi::Result<std::cell::Ref<'_, LegacyButtonMaps>> {
+        self.legacy_button_maps
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("legacy_button_maps is already mutably borrowed"))
+    }
+
+    pub(super) fn borrow_legacy_button_maps_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, LegacyButtonMaps>> {
+        self.legacy_button_maps
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("legacy_button_maps is already borrowed"))
+    }
+
+    pub(super) fn borrow_device_attachments_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, HashMap<u64, DeviceAttachment>>> {
+        self.device_attachments
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("device_attachments is already borrowed"))
+    }
+
+    pub(super) fn borrow_owned_overlays(&self) -> napi::Result<std::cell::Ref<'_, BTreeSet<u64>>> {
+        self.owned_overlays
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("owned_overlays is already mutably borrowed"))
+    }
+
+    pub(super) fn borrow_owned_overlays_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, BTreeSet<u64>>> {
+        self.owned_overlays
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("owned_overlays is already borrowed"))
+    }
+
+    pub(super) fn borrow_overlay_parents(
+        &self,
+    ) -> napi::Result<std::cell::Ref<'_, HashMap<u64, OverlayParentLink>>> {
+        self.overlay_parents
+            .try_borrow()
+            .map_err(|_| napi::Error::from_reason("overlay_parents is already mutably borrowed"))
+    }
+
+    pub(super) fn borrow_overlay_parents_mut(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, HashMap<u64, OverlayParentLink>>> {
+        self.overlay_parents
+            .try_borrow_mut()
+            .map_err(|_| napi::Error::from_reason("overlay_parents is already borrowed"))
+    }
 }
 
 #[napi]
@@ -255,7 +775,7 @@ impl OverlayManager {
             // 早期リターンにより古いカウントが残らないよう、すべての失敗しうるステップの後にインクリメントする
             VR_INIT_COUNT.fetch_add(1, Ordering::SeqCst);
 
-            Ok(OverlayManager {
+            let manager = OverlayManager {
                 context: VrContext {
                     overlay: Some(overlay_ptr),
                     system: system_ptr,
@@ -264,16 +784,63 @@ impl OverlayManager {
                 d3d11: d3d11_ctx,
                 poses_cache: RefCell::new(create_poses_cache()),
                 poses_timestamp: Cell::new(None),
+                poses_cache_ttl: Cell::new(DEFAULT_POSE_CACHE_TTL),
+                pose_prediction: Cell::new(PosePrediction::Disabled),
                 input_cache: RefCell::new(InputActionCache::new()),
+                device_attachments: RefCell::new(HashMap::new()),
+                overlay_parents: RefCell::new(HashMap::new()),
+                owned_overlays: RefCell::new(BTreeSet::new()),
+                tracking_universe: Cell::new(vr::ETrackingUniverseOrigin_TrackingUniverseStanding),
+                seated_zero_pose: Cell::new(None),
+                presence_flags: Cell::new(PresenceFlags::new()),
+                texture_throttle: Cell::new(TextureThrottle::new()),
+                last_texture_upload: Cell::new(None),
+                default_stabilizer_params: Cell::new(StabilizerParams::DEFAULT),
+                stabilizer_params: RefCell::new(HashMap::new()),
+                pointer_stabilizers: RefCell::new(HashMap::new()),
+                stabilizer_epoch: Instant::now(),
+                input_poller: RefCell::new(None),
+                vr_event_listener: RefCell::new(None),
+                poke_params: Cell::new(PokeParams::DEFAULT),
+                poke_trackers: RefCell::new(Default::default()),
+                mallet_params: Cell::new(MalletParams::DEFAULT),
+                mallet_trackers: RefCell::new(Default::default()),
+                gesture_params: Cell::new(GestureParams::DEFAULT),
+                trigger_gestures: RefCell::new(HashMap::new()),
+                toggle_config: RefCell::new(ToggleConfig::new()),
+                legacy_button_maps: RefCell::new(LegacyButtonMaps::default()),
+                arbitration_policy: Cell::new(ArbitrationPolicy::DEFAULT),
+                pointer_arbiter: RefCell::new(PointerArbiter::default()),
+                key_layout: RefCell::new(KeyLayout::default()),
+                gaze_params: Cell::new(GazeParams::DEFAULT),
+                gaze_dwell: Cell::new(GazeDwell::default()),
+                scan_config: RefCell::new(ScanConfig::new()),
+                switch_scanner: Cell::new(SwitchScanner::default()),
+                key_nav_params: Cell::new(KeyNavParams::DEFAULT),
+                key_navigators: RefCell::new(HashMap::new()),
+                swipe_params: Cell::new(SwipeParams::DEFAULT),
+                swipe_lexicon: RefCell::new(Lexicon::default()),
+                swipe_recorder: RefCell::new(SwipeRecorder::default()),
+                touch_params: Cell::new(TouchParams::DEFAULT),
+                touch_models: RefCell::new(TouchModels::default()),
+                correction_tracker: RefCell::new(CorrectionTracker::default()),
                 _vr_token: init_token,
                 _not_send: PhantomData,
-            })
+            };
+            // Record the baseline now so the first "Reset seated position" is detected
+            // 最初の「着座位置をリセット」を検出できるよう、ここで基準を記録する
+            manager.capture_seated_zero_pose();
+            Ok(manager)
         }
     }
 }
 
 impl Drop for OverlayManager {
     fn drop(&mut self) {
+        // Join the input poller while the function tables are still valid
+        // 関数テーブルが有効なうちに入力ポーラーを join する
+        self.input_poller.get_mut().take();
+
         // Clear pointers before VR shutdown to prevent dangling access
         // VR シャットダウン前にポインタをクリアしダングリングアクセスを防止
         self.context.overlay = None;
@@ -0,0 +1,853 @@
+use super::constants::{
+    AIM_LEFT_ACTION, AIM_RIGHT_ACTION, DEFAULT_ACTION_SET_PATH, GRIP_ACTION, JOYSTICK_ACTION,
+    SCROLL_ACTION, SKELETON_LEFT_ACTION, SKELETON_RIGHT_ACTION, TOGGLE_ACTION, TRIGGER_ACTION,
+    TRIGGER_PULL_ACTION,
+};
+use super::json::Json;
+use super::types::{InputActionType, InputHand};
+
+pub(super) const DEFAULT_APP_KEY: &str = "VRChat-OSC-Keyboard";
+/// Languages every action needs a localized name in / すべてのアクションにローカライズ名が必要な言語
+pub(super) const LOCALIZATION_LANGUAGES: [&str; 2] = ["en_US", "ja_JP"];
+const ACTION_SET_LOCALIZED: [&str; 2] = ["VRKB", "VRKB"];
+
+/// One built-in action; the single source for `initInput`, `actions.json` and the bindings
+/// 組み込みアクション1件。`initInput`、`actions.json`、バインディングの唯一の定義元
+pub(super) struct ActionSpec {
+    pub name: &'static str,
+    pub action_type: InputActionType,
+    pub hand: Option<InputHand>,
+    pub localized: [&'static str; 2], // In LOCALIZATION_LANGUAGES order / LOCALIZATION_LANGUAGES の順
+}
+
+impl ActionSpec {
+    pub(super) fn path(&self) -> String {
+        action_path(self.name)
+    }
+}
+
+pub(super) const ACTIONS: [ActionSpec; 10] = [
+    ActionSpec {
+        name: TOGGLE_ACTION,
+        action_type: InputActionType::Boolean,
+        hand: None,
+        localized: ["Toggle Overlay", "オーバーレイ表示切替"],
+    },
+    ActionSpec {
+        name: TRIGGER_ACTION,
+        action_type: InputActionType::Boolean,
+        hand: None,
+        localized: ["Trigger Click", "トリガークリック"],
+    },
+    ActionSpec {
+        name: GRIP_ACTION,
+        action_type: InputActionType::Boolean,
+        hand: None,
+        localized: ["Grip Click", "グリップクリック"],
+    },
+    ActionSpec {
+        name: TRIGGER_PULL_ACTION,
+        action_type: InputActionType::Vector1,
+        hand: None,
+        localized: ["Trigger Pull", "トリガー引き量"],
+    },
+    ActionSpec {
+        name: SCROLL_ACTION,
+        action_type: InputActionType::Vector2,
+        hand: None,
+        localized: ["Scroll", "スクロール"],
+    },
+    ActionSpec {
+        name: JOYSTICK_ACTION,
+        action_type: InputActionType::Vector2,
+        hand: None,
+        localized: ["Joystick", "ジョイスティック"],
+    },
+    ActionSpec {
+        name: AIM_LEFT_ACTION,
+        action_type: InputActionType::Pose,
+        hand: Some(InputHand::Left),
+        localized: ["Aim (Left)", "ポインター (左手)"],
+    },
+    ActionSpec {
+        name: AIM_RIGHT_ACTION,
+        action_type: InputActionType::Pose,
+        hand: Some(InputHand::Right),
+        localized: ["Aim (Right)", "ポインター (右手)"],
+    },
+    ActionSpec {
+        name: SKELETON_LEFT_ACTION,
+        action_type: InputActionType::Skeleton,
+        hand: Some(InputHand::Left),
+        localized: ["Hand Skeleton (Left)", "手のスケルトン (左手)"],
+    },
+    ActionSpec {
+        name: SKELETON_RIGHT_ACTION,
+        action_type: InputActionType::Skeleton,
+        hand: Some(InputHand::Right),
+        localized: ["Hand Skeleton (Right)", "手のスケルトン (右手)"],
+    },
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum GripMode {
+    Button,
+    Grab, // Force-sensing grip / 感圧グリップ
+}
+
+/// Default binding layout of one controller type / コントローラー種別ごとの既定バインディング
+pub(super) struct ControllerSpec {
+    pub controller_type: &'static str,
+    pub description: &'static str,
+    pub stick: &'static str, // Toggle click, joystick and scroll / トグル・ジョイスティック・スクロール
+    pub stick_mode: &'static str,
+    pub grip: GripMode,
+    pub components: &'static [&'static str], // Known `/input/*` / 既知の `/input/*`
+}
+
+pub(super) const CONTROLLERS: [ControllerSpec; 5] = [
+    ControllerSpec {
+        controller_type: "knuckles",
+        description: "Default binding for Valve Index controllers",
+        stick: "thumbstick",
+        stick_mode: "joystick",
+        grip: GripMode::Grab,
+        components: &[
+            "a",
+            "b",
+            "system",
+            "trigger",
+            "grip",
+            "thumbstick",
+            "trackpad",
+        ],
+    },
+    ControllerSpec {
+        controller_type: "vive_controller",
+        description: "Default binding for HTC Vive controllers",
+        stick: "trackpad",
+        stick_mode: "trackpad",
+        grip: GripMode::Button,
+        components: &["application_menu", "system", "trigger", "grip", "trackpad"],
+    },
+    ControllerSpec {
+        controller_type: "holographic_controller",
+        description: "Default binding for Windows MR controllers",
+        stick: "joystick",
+        stick_mode: "joystick",
+        grip: GripMode::Button,
+        components: &[
+            "application_menu",
+            "system",
+            "trigger",
+            "grip",
+            "joystick",
+            "trackpad",
+        ],
+    },
+    ControllerSpec {
+        controller_type: "oculus_touch",
+        description: "Default binding for Oculus Touch controllers",
+        stick: "joystick",
+        stick_mode: "joystick",
+        grip: GripMode::Button,
+        components: &["a", "b", "x", "y", "system", "trigger", "grip", "joystick"],
+    },
+    ControllerSpec {
+        controller_type: "pico_controller",
+        description: "Default binding for Pico controllers",
+        stick: "joystick",
+        stick_mode: "joystick",
+        grip: GripMode::Button,
+        components: &[
+            "a", "b", "x", "y", "menu", "system", "trigger", "grip", "joystick",
+        ],
+    },
+];
+
+const POSE_COMPONENTS: [&str; 4] = ["raw", "base", "handgrip", "tip"];
+const HANDS: [&str; 2] = ["left", "right"];
+
+pub(super) fn controller(controller_type: &str) -> Option<&'static ControllerSpec> {
+    CONTROLLERS
+        .iter()
+        .find(|spec| spec.controller_type == controller_type)
+}
+
+pub(super) fn binding_file_name(controller_type: &str) -> String {
+    format!("{controller_type}.json")
+}
+
+fn action_spec(path: &str) -> Option<&'static ActionSpec> {
+    ACTIONS.iter().find(|spec| spec.path() == path)
+}
+
+fn action_path(name: &str) -> String {
+    format!("{DEFAULT_ACTION_SET_PATH}/in/{name}")
+}
+
+fn hand_name(hand: InputHand) -> &'static str {
+    match hand {
+        InputHand::Left => "left",
+        _ => "right",
+    }
+}
+
+fn manifest_type(action_type: InputActionType) -> &'static str {
+    match action_type {
+        InputActionType::Boolean => "boolean",
+        InputActionType::Vector1 => "vector1",
+        InputActionType::Vector2 => "vector2",
+        InputActionType::Pose => "pose",
+        InputActionType::Vibration => "vibration",
+        InputActionType::Skeleton => "skeleton",
+    }
+}
+
+fn skeleton_path(hand: InputHand) -> String {
+    format!("/skeleton/hand/{}", hand_name(hand))
+}
+
+/// `actions.json` for the built-in action set / 組み込みアクションセットの `actions.json`
+pub(super) fn generate_action_manifest(app_key: &str) -> Json {
+    let actions = ACTIONS
+        .iter()
+        .map(|spec| {
+            let mut entries = vec![
+                ("name", Json::String(spec.path())),
+                ("type", Json::str(manifest_type(spec.action_type))),
+            ];
+            if let (InputActionType::Skeleton, Some(hand)) = (spec.action_type, spec.hand) {
+                entries.push(("skeleton", Json::String(skeleton_path(hand))));
+            }
+            entries.push(("requirement", Json::str("optional")));
+            Json::object(entries)
+        })
+        .collect();
+    let default_bindings = CONTROLLERS
+        .iter()
+        .map(|spec| {
+            Json::object([
+                ("controller_type", Json::str(spec.controller_type)),
+                (
+                    "binding_url",
+                    Json::String(format!(
+                        "bindings/{}",
+                        binding_file_name(spec.controller_type)
+                    )),
+                ),
+            ])
+        })
+        .collect();
+    let localization = LOCALIZATION_LANGUAGES
+        .iter()
+        .enumerate()
+        .map(|(i, language)| {
+            let mut entries = vec![
+                ("language_tag".to_string(), Json::str(language)),
+                (
+                    DEFAULT_ACTION_SET_PATH.to_string(),
+                    Json::str(ACTION_SET_LOCALIZED[i]),
+                ),
+            ];
+            entries.extend(
+                ACTIONS
+                    .iter()
+                    .map(|spec| (spec.path(), Json::str(spec.localized[i]))),
+            );
+            Json::Object(entries)
+        })
+        .collect();
+
+    Json::object([
+        ("version", Json::Number(2.0)),
+        ("app_key", Json::str(app_key)),
+        ("category", Json::str("steamvr_input")),
+        ("actions", Json::Array(actions)),
+        (
+            "action_sets",
+            Json::Array(vec![Json::object([
+                ("name", Json::str(DEFAULT_ACTION_SET_PATH)),
+                ("usage", Json::str("single")),
+            ])]),
+        ),
+        ("default_bindings", Json::Array(default_bindings)),
+        ("localization", Json::Array(localization)),
+    ])
+}
+
+fn source(
+    hand: &str,
+    component: &str,
+    mode: &str,
+    input: &str,
+    action: &str,
+    parameters: &[(&str, &str)],
+) -> Json {
+    let mut entries = vec![
+        (
+            "path",
+            Json::String(format!("/user/hand/{hand}/input/{component}")),
+        ),
+        ("mode", Json::str(mode)),
+        (
+            "inputs",
+            Json::object([(
+                input,
+                Json::object([("output", Json::String(action_path(action)))]),
+            )]),
+        ),
+    ];
+    if !parameters.is_empty() {
+        entries.push((
+            "parameters",
+            Json::object(
+                parameters
+                    .iter()
+                    .map(|&(key, value)| (key, Json::str(value))),
+            ),
+        ));
+    }
+    Json::object(entries)
+}
+
+/// Default bindings of one controller type / コントローラー種別ごとの既定バインディング
+pub(super) fn generate_default_bindings(spec: &ControllerSpec, app_key: &str) -> Json {
+    let mut sources = vec![source(
+        "right",
+        spec.stick,
+        "button",
+        "click",
+        TOGGLE_ACTION,
+        &[],
+    )];
+    for hand in ["right", "left"] {
+        sources.push(source(
+            hand,
+            "trigger",
+            "button",
+            "click",
+            TRIGGER_ACTION,
+            &[],
+        ));
+    }
+    for hand in ["right", "left"] {
+        sources.push(match spec.grip {
+            GripMode::Button => source(hand, "grip", "button", "click", GRIP_ACTION, &[]),
+            GripMode::Grab => source(
+                hand,
+                "grip",
+                "grab",
+                "grab",
+                GRIP_ACTION,
+                &[
+                    ("grab_activate_threshold", "0.20"),
+                    ("grab_deactivate_threshold", "0.15"),
+                ],
+            ),
+        });
+    }
+    for hand in ["right", "left"] {
+        sources.push(source(
+            hand,
+            "trigger",
+            "trigger",
+            "pull",
+            TRIGGER_PULL_ACTION,
+            &[],
+        ));
+    }
+    for hand in ["right", "left"] {
+        sources.push(source(
+            hand,
+            spec.stick,
+            spec.stick_mode,
+            "position",
+            JOYSTICK_ACTION,
+            &[],
+        ));
+    }
+    for hand in ["right", "left"] {
+        sources.push(source(
+            hand,
+            spec.stick,
+            "scroll",
+            "scroll",
+            SCROLL_ACTION,
+            &[("scroll_mode", "smooth")],
+        ));
+    }
+
+    let per_hand = |action_type: InputActionType, path: fn(&str) -> String| -> Vec<Json> {
+        ACTIONS
+            .iter()
+            .filter(|action| action.action_type == action_type)
+            .filter_map(|action| {
+                let hand = hand_name(action.hand?);
+                Some(Json::object([
+                    ("output", Json::String(action.path())),
+                    ("path", Json::String(path(hand))),
+                ]))
+            })
+            .collect()
+    };
+    let poses = per_hand(InputActionType::Pose, |hand| {
+        format!("/user/hand/{hand}/pose/tip")
+    });
+    let skeleton = per_hand(InputActionType::Skeleton, |hand| {
+        format!("/user/hand/{hand}/input/skeleton/{hand}")
+    });
+
+    Json::object([
+        ("action_manifest_version", Json::Number(2.0)),
+        ("app_key", Json::str(app_key)),
+        ("controller_type", Json::str(spec.controller_type)),
+        ("description", Json::str(spec.description)),
+        (
+            "bindings",
+            Json::object([(
+                DEFAULT_ACTION_SET_PATH,
+                Json::object([
+                    ("sources", Json::Array(sources)),
+                    ("poses", Json::Array(poses)),
+                    ("skeleton", Json::Array(skeleton)),
+                ]),
+            )]),
+        ),
+    ])
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum IssueKind {
+    InvalidJson,
+    MissingAction,
+    UnknownAction,
+    TypeMismatch,
+    UnknownPath,
+    MissingLocalization,
+    MissingBindingFile,
+    UnknownControllerType,
+}
+
+/// Problem found in an input file; `path` is the action or input path it is about
+/// 入力ファイルで見つかった問題。`path` は対象のアクションまたは入力パス
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Issue {
+    pub kind: IssueKind,
+    pub file: String,
+    pub path: String,
+    pub message: String,
+}
+
+struct Issues(Vec<Issue>);
+
+impl Issues {
+    fn push(&mut self, kind: IssueKind, file: &str, path: &str, message: String) {
+        self.0.push(Issue {
+            kind,
+            file: file.to_string(),
+            path: path.to_string(),
+            message,
+        });
+    }
+}
+
+fn validate_actions(file: &str, manifest: &Json, issues: &mut Issues) {
+    let declared = manifest.get("actions").map(Json::as_array).unwrap_or(&[]);
+    for spec in &ACTIONS {
+        let path = spec.path();
+        let Some(action) = declared
+            .iter()
+            .find(|action| action.get("name").and_then(Json::as_str) == Some(&path))
+        else {
+            issues.push(
+                IssueKind::MissingAction,
+                file,
+                &path,
+                format!("action {path} is not declared"),
+            );
+            continue;
+        };
+        let expected = manifest_type(spec.action_type);
+        let actual = action.get("type").and_then(Json::as_str).unwrap_or("");
+        if actual != expected {
+            issues.push(
+                IssueKind::TypeMismatch,
+                file,
+                &path,
+                format!("action {path} is {actual:?}, expected {expected:?}"),
+            );
+        }
+        if let (InputActionType::Skeleton, Some(hand)) = (spec.action_type, spec.hand) {
+            let expected = skeleton_path(hand);
+            if action.get("skeleton").and_then(Json::as_str) != Some(expected.as_str()) {
+                issues.push(
+                    IssueKind::UnknownPath,
+                    file,
+                    &path,
+                    format!("skeleton action {path} must use {expected}"),
+                );
+            }
+        }
+    }
+    for name in declared
+        .iter()
+        .filter_map(|action| action.get("name").and_then(Json::as_str))
+        .filter(|name| action_spec(name).is_none())
+    {
+        issues.push(
+            IssueKind::UnknownAction,
+            file,
+            name,
+            format!("action {name} is not a built-in action"),
+        );
+    }
+
+    let has_set = manifest
+        .get("action_sets")
+        .map(Json::as_array)
+        .unwrap_or(&[])
+        .iter()
+        .any(|set| set.get("name").and_then(Json::as_str) == Some(DEFAULT_ACTION_SET_PATH));
+    if !has_set {
+        issues.push(
+            IssueKind::MissingAction,
+            file,
+            DEFAULT_ACTION_SET_PATH,
+            format!("action set {DEFAULT_ACTION_SET_PATH} is not declared"),
+        );
+    }
+
+    let blocks = manifest
+        .get("localization")
+        .map(Json::as_array)
+        .unwrap_or(&[]);
+    let keys: Vec<String> = std::iter::once(DEFAULT_ACTION_SET_PATH.to_string())
+        .chain(ACTIONS.iter().map(ActionSpec::path))
+        .collect();
+    for language in LOCALIZATION_LANGUAGES {
+        let block = blocks
+            .iter()
+            .find(|block| block.get("language_tag").and_then(Json::as_str) == Some(language));
+        for key in &keys {
+            let localized = block
+                .and_then(|block| block.get(key))
+                .and_then(Json::as_str)
+                .is_some_and(|name| !name.trim().is_empty());
+            if !localized {
+                issues.push(
+                    IssueKind::MissingLocalization,
+                    file,
+                    key,
+                    format!("{key} has no {language} name"),
+                );
+            }
+        }
+    }
+}
+
+fn is_known_source_path(spec: &ControllerSpec, path: &str) -> bool {
+    HANDS.iter().any(|hand| {
+        path.strip_prefix(&format!("/user/hand/{hand}/input/"))
+            .is_some_and(|component| spec.components.contains(&component))
+    })
+}
+
+fn is_known_pose_path(path: &str) -> bool {
+    HANDS.iter().any(|hand| {
+        path.strip_prefix(&format!("/user/hand/{hand}/pose/"))
+            .is_some_and(|component| POSE_COMPONENTS.contains(&component))
+    })
+}
+
+fn is_known_skeleton_path(path: &str) -> bool {
+    HANDS
+        .iter()
+        .any(|hand| path == format!("/user/hand/{hand}/input/skeleton/{hand}"))
+}
+
+fn validate_bindings(file: &str, bindings: &Json, issues: &mut Issues) {
+    let controller_type = bindings
+        .get("controller_type")
+        .and_then(Json::as_str)
+        .unwrap_or("");
+    let Some(spec) = controller(controller_type) else {
+        issues.push(
+            IssueKind::UnknownControllerType,
+            file,
+            controller_type,
+            format!("unknown controller_type {controller_type:?}"),
+        );
+        return;
+    };
+    let set = bindings
+        .get("bindings")
+        .and_then(|sets| sets.get(DEFAULT_ACTION_SET_PATH));
+
+    // (section, input path, output action path) / (セクション, 入力パス, 出力アクションパス)
+    let mut outputs: Vec<(&str, &str, &str)> = Vec::new();
+    if let Some(set) = set {
+        for source in set.get("sources").map(Json::as_array).unwrap_or(&[]) {
+            let path = source.get("path").and_then(Json::as_str).unwrap_or("");
+            for (_, input) in source.get("inputs").map(Json::entries).unwrap_or(&[]) {
+                if let Some(output) = input.get("output").and_then(Json::as_str) {
+                    outputs.push(("sources", path, output));
+                }
+            }
+        }
+        for section in ["poses", "skeleton"] {
+            for binding in set.get(section).map(Json::as_array).unwrap_or(&[]) {
+                let path = binding.get("path").and_then(Json::as_str).unwrap_or("");
+                if let Some(output) = binding.get("output").and_then(Json::as_str) {
+                    outputs.push((section, path, output));
+                }
+            }
+        }
+    }
+
+    for &(section, path, output) in &outputs {
+        let known_path = match section {
+            "poses" => is_known_pose_path(path),
+            "skeleton" => is_known_skeleton_path(path),
+            _ => is_known_source_path(spec, path),
+        };
+        if !known_path {
+            issues.push(
+                IssueKind::UnknownPath,
+                file,
+                path,
+                format!("{path} is not an input of {controller_type}"),
+            );
+        }
+        let Some(action) = action_spec(output) else {
+            issues.push(
+                IssueKind::UnknownAction,
+                file,
+                output,
+                format!("{path} outputs to unknown action {output}"),
+            );
+            continue;
+        };
+        let section_matches = match action.action_type {
+            InputActionType::Pose => section == "poses",
+            InputActionType::Skeleton => section == "skeleton",
+            InputActionType::Vibration => false,
+            _ => section == "sources",
+        };
+        if !section_matches {
+            issues.push(
+                IssueKind::TypeMismatch,
+                file,
+                output,
+                format!(
+                    "{} action {output} cannot be bound under {section}",
+                    manifest_type(action.action_type)
+                ),
+            );
+        }
+    }
+
+    for action in &ACTIONS {
+        let path = action.path();
+        if !outputs.iter().any(|&(_, _, output)| output == path) {
+            issues.push(
+                IssueKind::MissingAction,
+                file,
+                &path,
+                format!("{controller_type} does not bind {path}"),
+            );
+        }
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// Check `actions.json` and the default binding files against the built-in actions.
+/// Files are `(name, contents)`; binding files are matched by file name to `default_bindings`.
+/// `actions.json` と既定バインディングファイルを組み込みアクションと照合する。
+/// ファイルは `(名前, 内容)` で、バインディングファイルはファイル名で `default_bindings` と対応付ける。
+pub(super) fn validate(actions: (&str, &str), bindings: &[(&str, &str)]) -> Vec<Issue> {
+    let mut issues = Issues(Vec::new());
+    let parse = |issues: &mut Issues, (name, text): (&str, &str)| match Json::parse(text) {
+        Ok(json) => Some(json),
+        Err(error) => {
+            issues.push(IssueKind::InvalidJson, name, "", error);
+            None
+        }
+    };
+
+    let (actions_file, _) = actions;
+    let manifest = parse(&mut issues, actions);
+    if let Some(manifest) = &manifest {
+        validate_actions(actions_file, manifest, &mut issues);
+        let listed = manifest
+            .get("default_bindings")
+            .map(Json::as_array)
+            .unwrap_or(&[]);
+        for spec in &CONTROLLERS {
+            let url = listed
+                .iter()
+                .find(|entry| {
+                    entry.get("controller_type").and_then(Json::as_str)
+                        == Some(spec.controller_type)
+                })
+                .and_then(|entry| entry.get("binding_url"))
+                .and_then(Json::as_str);
+            let Some(url) = url else {
+                issues.push(
+                    IssueKind::MissingBindingFile,
+                    actions_file,
+                    spec.controller_type,
+                    format!("default_bindings has no entry for {}", spec.controller_type),
+                );
+                continue;
+            };
+            if !bindings
+                .iter()
+                .any(|&(name, _)| file_name(name) == file_name(url))
+            {
+                issues.push(
+                    IssueKind::MissingBindingFile,
+                    actions_file,
+                    url,
+                    format!("binding file {url} was not provided"),
+                );
+            }
+        }
+    }
+
+    for &file in bindings {
+        if let Some(json) = parse(&mut issues, file) {
+            validate_bindings(file.0, &json, &mut issues);
+        }
+    }
+    issues.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACTIONS_JSON: &str = include_str!("../../../steamvr/actions.json");
+    const BINDINGS: [(&str, &str); 5] = [
+        (
+            "knuckles.json",
+            include_str!("../../../steamvr/bindings/knuckles.json"),
+        ),
+        (
+            "vive_controller.json",
+            include_str!("../../../steamvr/bindings/vive_controller.json"),
+        ),
+        (
+            "holographic_controller.json",
+            include_str!("../../../steamvr/bindings/holographic_controller.json"),
+        ),
+        (
+            "oculus_touch.json",
+            include_str!("../../../steamvr/bindings/oculus_touch.json"),
+        ),
+        (
+            "pico_controller.json",
+            include_str!("../../../steamvr/bindings/pico_controller.json"),
+        ),
+    ];
+
+    fn kinds(issues: &[Issue]) -> Vec<(IssueKind, &str)> {
+        issues
+            .iter()
+            .map(|issue| (issue.kind, issue.path.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn checked_in_files_match_generated() {
+        assert_eq!(
+            Json::parse(ACTIONS_JSON).unwrap(),
+            generate_action_manifest(DEFAULT_APP_KEY)
+        );
+        for (name, text) in BINDINGS {
+            let controller_type = name.trim_end_matches(".json");
+            let spec = controller(controller_type).unwrap();
+            assert_eq!(
+                Json::parse(text).unwrap(),
+                generate_default_bindings(spec, DEFAULT_APP_KEY),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn checked_in_files_validate_cleanly() {
+        assert_eq!(validate(("actions.json", ACTIONS_JSON), &BINDINGS), vec![]);
+    }
+
+    #[test]
+    fn generated_text_round_trips() {
+        let manifest = generate_action_manifest(DEFAULT_APP_KEY);
+        assert_eq!(Json::parse(&manifest.to_pretty()).unwrap(), manifest);
+    }
+
+    #[test]
+    fn reports_missing_action_and_localization() {
+        let text = ACTIONS_JSON
+            .replace("\"/actions/vrkb2/in/scroll\": \"スクロール\",", "")
+            .replacen(
+                "\"/actions/vrkb2/in/grip_click\"",
+                "\"/actions/vrkb2/in/grip\"",
+                1,
+            );
+        let issues = validate(("actions.json", &text), &BINDINGS);
+        assert_eq!(
+            kinds(&issues),
+            vec![
+                (IssueKind::MissingAction, "/actions/vrkb2/in/grip_click"),
+                (IssueKind::UnknownAction, "/actions/vrkb2/in/grip"),
+                (IssueKind::MissingLocalization, "/actions/vrkb2/in/scroll"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unknown_paths_and_outputs() {
+        let text = BINDINGS[1]
+            .1
+            .replacen(
+                "/user/hand/right/input/trackpad",
+                "/user/hand/right/input/thumbstick",
+                1,
+            )
+            .replace(
+                "/actions/vrkb2/in/trigger_pull",
+                "/actions/vrkb2/in/trigger_pul",
+            );
+        let mut bindings = BINDINGS;
+        bindings[1].1 = &text;
+        let issues = validate(("actions.json", ACTIONS_JSON), &bindings);
+        assert_eq!(
+            kinds(&issues),
+            vec![
+                (IssueKind::UnknownPath, "/user/hand/right/input/thumbstick"),
+                (IssueKind::UnknownAction, "/actions/vrkb2/in/trigger_pul"),
+                (IssueKind::UnknownAction, "/actions/vrkb2/in/trigger_pul"),
+                (IssueKind::MissingAction, "/actions/vrkb2/in/trigger_pull"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_json_and_missing_files() {
+        let issues = validate(("actions.json", ACTIONS_JSON), &BINDINGS[..4]);
+        assert_eq!(
+            kinds(&issues),
+            vec![(
+                IssueKind::MissingBindingFile,
+                "bindings/pico_controller.json"
+            )]
+        );
+        let issues = validate(("actions.json", "{"), &[]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, IssueKind::InvalidJson);
+    }
+}
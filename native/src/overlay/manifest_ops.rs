@@ -0,0 +1,88 @@
+use napi_derive::napi;
+
+use super::manifest::{
+    controller, generate_action_manifest as build_action_manifest,
+    generate_default_bindings as build_default_bindings, validate, Issue, IssueKind, CONTROLLERS,
+    DEFAULT_APP_KEY,
+};
+use super::types::{ManifestFile, ManifestIssue, ManifestIssueKind};
+
+impl IssueKind {
+    fn to_napi(self) -> ManifestIssueKind {
+        match self {
+            IssueKind::InvalidJson => ManifestIssueKind::InvalidJson,
+            IssueKind::MissingAction => ManifestIssueKind::MissingAction,
+            IssueKind::UnknownAction => ManifestIssueKind::UnknownAction,
+            IssueKind::TypeMismatch => ManifestIssueKind::TypeMismatch,
+            IssueKind::UnknownPath => ManifestIssueKind::UnknownPath,
+            IssueKind::MissingLocalization => ManifestIssueKind::MissingLocalization,
+            IssueKind::MissingBindingFile => ManifestIssueKind::MissingBindingFile,
+            IssueKind::UnknownControllerType => ManifestIssueKind::UnknownControllerType,
+        }
+    }
+}
+
+impl Issue {
+    fn into_napi(self) -> ManifestIssue {
+        ManifestIssue {
+            kind: self.kind.to_napi(),
+            file: self.file,
+            path: self.path,
+            message: self.message,
+        }
+    }
+}
+
+fn with_newline(mut text: String) -> String {
+    text.push('\n');
+    text
+}
+
+/// `actions.json` text for the built-in actions / 組み込みアクションの `actions.json` テキスト
+#[napi]
+pub fn generate_action_manifest(app_key: Option<String>) -> String {
+    let app_key = app_key.as_deref().unwrap_or(DEFAULT_APP_KEY);
+    with_newline(build_action_manifest(app_key).to_pretty())
+}
+
+/// Default binding file text for a controller type / コントローラー種別の既定バインディングのテキスト
+#[napi]
+pub fn generate_default_bindings(
+    controller_type: String,
+    app_key: Option<String>,
+) -> napi::Result<String> {
+    let spec = controller(&controller_type).ok_or_else(|| {
+        napi::Error::from_reason(format!("unknown controller type: {controller_type}"))
+    })?;
+    let app_key = app_key.as_deref().unwrap_or(DEFAULT_APP_KEY);
+    Ok(with_newline(
+        build_default_bindings(spec, app_key).to_pretty(),
+    ))
+}
+
+#[napi]
+pub fn default_binding_controller_types() -> Vec<String> {
+    CONTROLLERS
+        .iter()
+        .map(|spec| spec.controller_type.to_string())
+        .collect()
+}
+
+/// Check an action manifest and its binding files for missing actions, unknown paths and
+/// localization gaps; an empty result means they match the built-in actions.
+/// アクションマニフェストとバインディングファイルの、アクションの欠落・不明なパス・
+/// ローカライズ漏れを検査する。結果が空なら組み込みアクションと一致している。
+#[napi]
+pub fn validate_action_manifest(
+    actions: ManifestFile,
+    bindings: Vec<ManifestFile>,
+) -> Vec<ManifestIssue> {
+    let bindings: Vec<(&str, &str)> = bindings
+        .iter()
+        .map(|file| (file.name.as_str(), file.contents.as_str()))
+        .collect();
+    validate((&actions.name, &actions.contents), &bindings)
+        .into_iter()
+        .map(Issue::into_napi)
+        .collect()
+}
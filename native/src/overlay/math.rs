@@ -1,3 +1,5 @@
+use openvr_sys as vr;
+
 pub(super) fn vec3_f32(name: &str, values: &[f64]) -> napi::Result<[f32; 3]> {
     if values.len() != 3 {
         return Err(napi::Error::from_reason(format!(
@@ -39,3 +41,114 @@ pub(super) fn hmd_matrix34_to_vec(m: &[[f32; 4]; 3]) -> Vec<f64> {
         1.0,
     ]
 }
+
+/// Origin and forward (-Z) direction of a pose / ポーズの原点と前方 (-Z) 方向
+pub(super) fn pose_ray(m: &[[f32; 4]; 3]) -> (Vec<f64>, Vec<f64>) {
+    let origin = (0..3).map(|row| m[row][3] as f64).collect();
+    let direction = (0..3).map(|row| -(m[row][2] as f64)).collect();
+    (origin, direction)
+}
+
+/// Convert a validated 4x4 flattened row-major matrix to OpenVR 3x4
+/// 検証済みの 4x4 フラット行優先行列を OpenVR の 3x4 に変換
+pub(super) fn vec_to_hmd_matrix34(matrix: &[f64]) -> vr::HmdMatrix34_t {
+    vr::HmdMatrix34_t {
+        m: [
+            [
+                matrix[0] as f32,
+                matrix[1] as f32,
+                matrix[2] as f32,
+                matrix[3] as f32,
+            ],
+            [
+                matrix[4] as f32,
+                matrix[5] as f32,
+                matrix[6] as f32,
+                matrix[7] as f32,
+            ],
+            [
+                matrix[8] as f32,
+                matrix[9] as f32,
+                matrix[10] as f32,
+                matrix[11] as f32,
+            ],
+        ],
+    }
+}
+
+/// Compose two affine 3x4 transforms (`a * b`) / 2つのアフィン 3x4 変換を合成 (`a * b`)
+pub(super) fn mul_hmd_matrix34(a: &vr::HmdMatrix34_t, b: &vr::HmdMatrix34_t) -> vr::HmdMatrix34_t {
+    let mut out = vr::HmdMatrix34_t { m: [[0.0; 4]; 3] };
+    for row in 0..3 {
+        for col in 0..4 {
+            let mut value =
+                a.m[row][0] * b.m[0][col] + a.m[row][1] * b.m[1][col] + a.m[row][2] * b.m[2][col];
+            if col == 3 {
+                value += a.m[row][3];
+            }
+            out.m[row][col] = value;
+        }
+    }
+    out
+}
+
+/// Apply an affine 3x4 transform to a point / アフィン 3x4 変換を点に適用する
+pub(super) fn transform_point(m: &vr::HmdMatrix34_t, point: [f64; 3]) -> [f64; 3] {
+    let mut out = [0.0; 3];
+    for (row, value) in out.iter_mut().enumerate() {
+        let r = &m.m[row];
+        *value =
+            r[0] as f64 * point[0] + r[1] as f64 * point[1] + r[2] as f64 * point[2] + r[3] as f64;
+    }
+    out
+}
+
+/// Unit +Z axis of a transform, the facing of an overlay; `None` if degenerate
+/// 変換の単位 +Z 軸 (オーバーレイの向き)。退化していれば `None`
+pub(super) fn unit_z_axis(m: &vr::HmdMatrix34_t) -> Option<[f64; 3]> {
+    let axis = [m.m[0][2] as f64, m.m[1][2] as f64, m.m[2][2] as f64];
+    let length = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+    if !length.is_finite() || length < f64::EPSILON {
+        return None;
+    }
+    Some(axis.map(|value| value / length))
+}
+
+/// Invert an affine 3x4 transform, `None` if it is singular
+/// アフィン 3x4 変換の逆行列を求める。特異なら `None`
+pub(super) fn invert_hmd_matrix34(m: &vr::HmdMatrix34_t) -> Option<vr::HmdMatrix34_t> {
+    let a = &m.m;
+    let c00 = a[1][1] * a[2][2] - a[1][2] * a[2][1];
+    let c01 = a[1][2] * a[2][0] - a[1][0] * a[2][2];
+    let c02 = a[1][0] * a[2][1] - a[1][1] * a[2][0];
+    let det = a[0][0] * c00 + a[0][1] * c01 + a[0][2] * c02;
+    if det.abs() < f32::EPSILON {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+
+    let r = [
+        [
+            c00 * inv_det,
+            (a[0][2] * a[2][1] - a[0][1] * a[2][2]) * inv_det,
+            (a[0][1] * a[1][2] - a[0][2] * a[1][1]) * inv_det,
+        ],
+        [
+            c01 * inv_det,
+            (a[0][0] * a[2][2] - a[0][2] * a[2][0]) * inv_det,
+            (a[0][2] * a[1][0] - a[0][0] * a[1][2]) * inv_det,
+        ],
+        [
+            c02 * inv_det,
+            (a[0][1] * a[2][0] - a[0][0] * a[2][1]) * inv_det,
+            (a[0][0] * a[1][1] - a[0][1] * a[1][0]) * inv_det,
+        ],
+    ];
+
+    let mut out = vr::HmdMatrix34_t { m: [[0.0; 4]; 3] };
+    for (out_row, r_row) in out.m.iter_mut().zip(r.iter()) {
+        out_row[..3].copy_from_slice(r_row);
+        out_row[3] = -(r_row[0] * a[0][3] + r_row[1] * a[1][3] + r_row[2] * a[2][3]);
+    }
+    Some(out)
+}
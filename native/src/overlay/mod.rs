@@ -1,16 +1,74 @@
+mod action_ops;
+mod arbitration;
+mod arbitration_ops;
+mod binding_ops;
 mod buffers;
+mod button_map;
+mod button_map_ops;
 mod constants;
-mod manager;
 mod controller_ops;
 mod d3d11;
+mod device_ops;
 mod errors;
+mod event_ops;
+mod gaze;
+mod gaze_ops;
+mod gesture;
+mod gesture_ops;
 mod handles;
 mod input_ops;
+mod json;
+mod key_layout;
+mod key_layout_ops;
+mod key_nav;
+mod key_nav_ops;
+mod mallet;
+mod mallet_ops;
+mod manager;
+mod manifest;
+mod manifest_ops;
 mod math;
 mod overlay_ops;
+mod pointer_ops;
+mod poke;
+mod poke_ops;
+mod poller;
+mod poller_ops;
+mod pose_ops;
+mod presence_ops;
+mod scanning;
+mod scanning_ops;
+mod stabilizer;
+mod swipe;
+mod swipe_ops;
 mod texture_ops;
+mod toggle;
+mod toggle_ops;
+mod touch_model;
+mod touch_model_ops;
 mod transform_ops;
 mod types;
+mod universe_ops;
 
 pub use manager::OverlayManager;
-pub use types::{ControllerState, CurrentBindings, IntersectionResult, OverlayRelativeTransform};
+pub use manifest_ops::{
+    default_binding_controller_types, generate_action_manifest, generate_default_bindings,
+    validate_action_manifest,
+};
+pub use types::{
+    ActionBindings, ActionOrigin, AimRay, AnalogActionState, BindingInfo, ControllerButtons,
+    ControllerState, ControllerType, CurrentBindings, DeviceActivityLevel, DeviceEvent,
+    DeviceEventType, DeviceInfo, DevicePose, DigitalActionState, DrumstickParams,
+    FingertipPokeParams, GazeDwellParams, GazeDwellState, GestureEvent, GestureEventType,
+    InputActionDefinition, InputActionSetConfig, InputActionType, InputEvent, InputEventType,
+    InputHand, InputPollerOptions, IntersectionResult, KeyBounds, KeyNavigationParams,
+    KeyNavigationState, LegacyButtonMapInfo, LegacyButtonMapSource, ManifestFile, ManifestIssue,
+    ManifestIssueKind, OverlayParentTransform, OverlayPoint, OverlayRelativeTransform,
+    PointerArbitration, PointerArbitrationPolicy, PointerRole, PointerStabilizerParams,
+    PoseActionState, PosePredictionMode, PresenceState, SecondaryPointerPolicy, SwipeCandidate,
+    SwipeTypingParams, SwipeTypingState, SwitchScanConfig, SwitchScanEvent, SwitchScanEventType,
+    TextureThrottleOptions, ToggleGestureConfig, ToggleGestureEvent, ToggleGestureMode, TouchEvent,
+    TouchEventType, TouchKeyOffset, TouchModelParams, TouchSample, TrackedControllerRole,
+    TrackedDeviceClass, TrackedDeviceSelector, TrackingResult, TrackingUniverse,
+    TriggerGestureParams, VrEvent, VrEventBatch,
+};
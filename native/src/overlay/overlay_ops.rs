@@ -8,6 +8,36 @@ use super::manager::OverlayManager;
 use super::math::vec3_f32;
 use super::types::IntersectionResult;
 
+impl OverlayManager {
+    /// Ray cast in the configured universe / 設定ユニバースでのレイキャスト
+    pub(super) fn intersect_overlay(
+        &self,
+        handle: OverlayHandle,
+        source: [f32; 3],
+        direction: [f32; 3],
+    ) -> napi::Result<Option<vr::VROverlayIntersectionResults_t>> {
+        let overlay = self.overlay()?;
+        let compute_intersection_fn = require_fn(
+            overlay.ComputeOverlayIntersection,
+            "ComputeOverlayIntersection",
+        )?;
+        let mut params = vr::VROverlayIntersectionParams_t {
+            vSource: vr::HmdVector3_t { v: source },
+            vDirection: vr::HmdVector3_t { v: direction },
+            eOrigin: self.tracking_universe(),
+        };
+        let mut results = vr::VROverlayIntersectionResults_t {
+            vPoint: vr::HmdVector3_t { v: [0.0; 3] },
+            vNormal: vr::HmdVector3_t { v: [0.0; 3] },
+            vUVs: vr::HmdVector2_t { v: [0.0; 2] },
+            fDistance: 0.0,
+        };
+        let success =
+            unsafe { compute_intersection_fn(handle.as_u64(), &mut params, &mut results) };
+        Ok(success.then_some(results))
+    }
+}
+
 #[napi]
 impl OverlayManager {
     #[napi]
@@ -32,6 +62,8 @@ impl OverlayManager {
             }
         }
 
+        self.borrow_owned_overlays_mut()?.insert(handle);
+
         // u64 -> i64 cast (Napi compatibility) / u64 から i64 へのキャスト（Napi との互換性のため）
         OverlayHandle::from_u64(handle).to_i64()
     }
@@ -47,7 +79,8 @@ impl OverlayManager {
                 return Err(overlay_error("DestroyOverlay", overlay, err));
             }
         }
-        Ok(())
+        self.borrow_owned_overlays_mut()?.remove(&handle.as_u64());
+        self.forget_overlay_placement(handle)
     }
 
     #[napi]
@@ -151,42 +184,18 @@ impl OverlayManager {
         source: Vec<f64>,
         direction: Vec<f64>,
     ) -> napi::Result<Option<IntersectionResult>> {
-        let overlay = self.overlay()?;
-        let compute_intersection_fn = require_fn(
-            overlay.ComputeOverlayIntersection,
-            "ComputeOverlayIntersection",
-        )?;
         let handle = overlay_handle(handle)?;
         let source = vec3_f32("source", &source)?;
         let direction = vec3_f32("direction", &direction)?;
-        unsafe {
-            let mut params = vr::VROverlayIntersectionParams_t {
-                vSource: vr::HmdVector3_t { v: source },
-                vDirection: vr::HmdVector3_t { v: direction },
-                eOrigin: vr::ETrackingUniverseOrigin_TrackingUniverseStanding,
-            };
-
-            let mut results = vr::VROverlayIntersectionResults_t {
-                vPoint: vr::HmdVector3_t { v: [0.0; 3] },
-                vNormal: vr::HmdVector3_t { v: [0.0; 3] },
-                vUVs: vr::HmdVector2_t { v: [0.0; 2] },
-                fDistance: 0.0,
-            };
-
-            let success = compute_intersection_fn(handle.as_u64(), &mut params, &mut results);
-
-            if success {
-                Ok(Some(IntersectionResult {
-                    x: results.vPoint.v[0] as f64,
-                    y: results.vPoint.v[1] as f64,
-                    z: results.vPoint.v[2] as f64,
-                    u: results.vUVs.v[0] as f64,
-                    v: results.vUVs.v[1] as f64,
-                    distance: results.fDistance as f64,
-                }))
-            } else {
-                Ok(None)
-            }
-        }
+        Ok(self
+            .intersect_overlay(handle, source, direction)?
+            .map(|results| IntersectionResult {
+                x: results.vPoint.v[0] as f64,
+                y: results.vPoint.v[1] as f64,
+                z: results.vPoint.v[2] as f64,
+                u: results.vUVs.v[0] as f64,
+                v: results.vUVs.v[1] as f64,
+                distance: results.fDistance as f64,
+            }))
     }
 }
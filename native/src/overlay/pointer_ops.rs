@@ -0,0 +1,137 @@
+use napi_derive::napi;
+
+use super::manager::OverlayManager;
+use super::stabilizer::StabilizerParams;
+use super::types::PointerStabilizerParams;
+
+impl StabilizerParams {
+    fn from_napi(params: &PointerStabilizerParams) -> napi::Result<Self> {
+        let params = Self {
+            enabled: params.enabled,
+            min_cutoff: params.minCutoff,
+            beta: params.beta,
+            dcutoff: params.dcutoff,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn to_napi(self) -> PointerStabilizerParams {
+        PointerStabilizerParams {
+            enabled: self.enabled,
+            minCutoff: self.min_cutoff,
+            beta: self.beta,
+            dcutoff: self.dcutoff,
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Parameters in effect for a controller / コントローラーに適用されるパラメータ
+    pub(super) fn stabilizer_params_for(
+        &self,
+        controller_index: u32,
+    ) -> napi::Result<StabilizerParams> {
+        Ok(self
+            .borrow_stabilizer_params()?
+            .get(&controller_index)
+            .copied()
+            .unwrap_or_else(|| self.default_stabilizer_params()))
+    }
+
+    /// Run a UV sample through the controller's filter / UV サンプルをコントローラーのフィルターに通す
+    pub(super) fn stabilize_uv(
+        &self,
+        controller_index: u32,
+        u: f64,
+        v: f64,
+        seconds: f64,
+    ) -> napi::Result<(f64, f64)> {
+        let params = self.stabilizer_params_for(controller_index)?;
+        Ok(self
+            .borrow_pointer_stabilizers_mut()?
+            .entry(controller_index)
+            .or_default()
+            .update(&params, u, v, seconds))
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Smooth an intersection UV for `controllerIndex` with its 1€ filter and return `[u, v]`.
+    /// `timestampMs` replays recorded traces deterministically; the native clock is used otherwise.
+    /// `controllerIndex` の交差 UV を 1€ フィルターで平滑化し `[u, v]` を返す。
+    /// `timestampMs` を渡すと記録済みトレースを決定的に再生でき、省略時はネイティブの時計を使う。
+    #[napi]
+    pub fn stabilize_pointer(
+        &self,
+        controller_index: u32,
+        u: f64,
+        v: f64,
+        timestamp_ms: Option<f64>,
+    ) -> napi::Result<Vec<f64>> {
+        let seconds = self.input_seconds(timestamp_ms)?;
+        let (u, v) = self.stabilize_uv(controller_index, u, v, seconds)?;
+        Ok(vec![u, v])
+    }
+
+    /// Forget filter history, for one controller or all of them / フィルター履歴を破棄する (1台または全台)
+    #[napi]
+    pub fn reset_pointer_stabilizer(&self, controller_index: Option<u32>) -> napi::Result<()> {
+        let mut stabilizers = self.borrow_pointer_stabilizers_mut()?;
+        match controller_index {
+            Some(index) => {
+                stabilizers.remove(&index);
+            }
+            None => stabilizers.clear(),
+        }
+        Ok(())
+    }
+
+    /// Tune one controller, or the default used by controllers without their own tuning
+    /// 1台のコントローラー、または個別設定のないコントローラーが使う既定値を調整する
+    #[napi]
+    pub fn set_pointer_stabilizer(
+        &self,
+        params: PointerStabilizerParams,
+        controller_index: Option<u32>,
+    ) -> napi::Result<()> {
+        let params = StabilizerParams::from_napi(&params)?;
+        match controller_index {
+            Some(index) => {
+                self.borrow_stabilizer_params_mut()?.insert(index, params);
+                self.update_input_poller(|config| {
+                    config.stabilizer_overrides.insert(index, params);
+                })
+            }
+            None => {
+                self.set_default_stabilizer_params(params);
+                self.update_input_poller(|config| config.stabilizer_default = params)
+            }
+        }
+    }
+
+    /// Drop a controller's own tuning so it follows the default again
+    /// コントローラー個別の調整を破棄し、既定値に戻す
+    #[napi]
+    pub fn clear_pointer_stabilizer(&self, controller_index: u32) -> napi::Result<()> {
+        let mut overrides = self.borrow_stabilizer_params_mut()?;
+        overrides.remove(&controller_index);
+        drop(overrides);
+        self.update_input_poller(|config| {
+            config.stabilizer_overrides.remove(&controller_index);
+        })
+    }
+
+    #[napi]
+    pub fn get_pointer_stabilizer(
+        &self,
+        controller_index: Option<u32>,
+    ) -> napi::Result<PointerStabilizerParams> {
+        let params = match controller_index {
+            Some(index) => self.stabilizer_params_for(index)?,
+            None => self.default_stabilizer_params(),
+        };
+        Ok(params.to_napi())
+    }
+}
@@ -0,0 +1,253 @@
+/// Fingertip poke thresholds in meters / 指先ポークのしきい値 (メートル)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct PokeParams {
+    pub press_depth: f64,    // Behind the surface / 面の奥側
+    pub release_height: f64, // In front of the surface / 面の手前側
+    pub hover_range: f64,    // In front of the surface / 面の手前側
+}
+
+impl PokeParams {
+    /// Press slightly past the surface, release a centimeter above it
+    /// 面を少し越えたら押下し、1cm 手前に戻ったら解放する
+    pub(super) const DEFAULT: Self = Self {
+        press_depth: 0.003,
+        release_height: 0.01,
+        hover_range: 0.08,
+    };
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        let non_negative = |value: f64| value.is_finite() && value >= 0.0;
+        if !non_negative(self.press_depth) || !non_negative(self.release_height) {
+            return Err(napi::Error::from_reason(
+                "pressDepth and releaseHeight must be non-negative numbers",
+            ));
+        }
+        if !self.hover_range.is_finite() || self.hover_range < self.release_height {
+            return Err(napi::Error::from_reason(
+                "hoverRange must be a number no smaller than releaseHeight",
+            ));
+        }
+        if self.press_depth + self.release_height <= 0.0 {
+            return Err(napi::Error::from_reason(
+                "pressDepth and releaseHeight cannot both be zero",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Where a tracked point sits over the overlay; `distance` is positive in front of it
+/// 追跡点のオーバーレイ上の位置。`distance` は面の手前側で正
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct PlaneSample {
+    pub u: f64,
+    pub v: f64,
+    pub distance: f64,
+}
+
+/// Ray that finds `point` on an overlay with unit normal `normal`: cast back toward the
+/// surface from `range` in front of the point. Returns (source, direction).
+/// 単位法線 `normal` のオーバーレイ上で `point` を探すレイ。点から `range` だけ手前の位置から
+/// 面に向けてキャストする。(始点, 方向) を返す。
+pub(super) fn plane_cast(point: [f64; 3], normal: [f64; 3], range: f64) -> ([f32; 3], [f32; 3]) {
+    let source = [0, 1, 2].map(|axis| (point[axis] + normal[axis] * range) as f32);
+    let direction = normal.map(|value| -value as f32);
+    (source, direction)
+}
+
+/// Signed height of the point over the surface from the hit distance of its `plane_cast`
+/// ray; positive in front, like `PlaneSample::distance`
+/// `plane_cast` のレイの交点までの距離から求めた、点の面からの符号付きの高さ。
+/// `PlaneSample::distance` と同じく手前側で正
+pub(super) fn plane_distance(hit_distance: f64, range: f64) -> f64 {
+    hit_distance - range
+}
+
+/// Sample a point over the plane z = 0 (normal +Z, u = x, v = y) the way
+/// `sample_overlay_plane` does, for the touch tests
+/// タッチのテスト用に、平面 z = 0 (法線 +Z、u = x、v = y) 上の点を
+/// `sample_overlay_plane` と同じ方法でサンプリングする
+#[cfg(test)]
+pub(super) fn sample_test_plane(point: [f64; 3], range: f64) -> Option<PlaneSample> {
+    let (source, direction) = plane_cast(point, [0.0, 0.0, 1.0], range);
+    // The ray hits z = 0 after source.z / -direction.z / レイは source.z / -direction.z 進んで z = 0 に当たる
+    let hit_distance = source[2] as f64 / -direction[2] as f64;
+    (hit_distance >= 0.0).then(|| PlaneSample {
+        u: point[0],
+        v: point[1],
+        distance: plane_distance(hit_distance, range),
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TouchTransition {
+    Hover,
+    Press,
+    Release,
+    HoverEnd,
+}
+
+/// Hover / press state of one fingertip / 1本の指先のホバー・押下状態
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct PokeTracker {
+    hovering: bool,
+    pressed: bool,
+    last: Option<PlaneSample>,
+}
+
+impl PokeTracker {
+    /// Advance with the latest sample (None when untracked or off the overlay) and
+    /// return the transitions in order, each with the sample it applies to.
+    /// 最新のサンプル (未トラッキングやオーバーレイ外なら None) で状態を進め、
+    /// 遷移を対象サンプルとともに順に返す。
+    pub(super) fn update(
+        &mut self,
+        params: &PokeParams,
+        sample: Option<PlaneSample>,
+    ) -> Vec<(TouchTransition, PlaneSample)> {
+        let mut transitions = Vec::new();
+        let in_range = sample.filter(|s| s.distance <= params.hover_range);
+        let Some(current) = in_range else {
+            if let Some(last) = self.last.take() {
+                if self.pressed {
+                    transitions.push((TouchTransition::Release, last));
+                }
+                if self.hovering {
+                    transitions.push((TouchTransition::HoverEnd, last));
+                }
+            }
+            self.hovering = false;
+            self.pressed = false;
+            return transitions;
+        };
+
+        self.hovering = true;
+        transitions.push((TouchTransition::Hover, current));
+        if !self.pressed && current.distance <= -params.press_depth {
+            self.pressed = true;
+            transitions.push((TouchTransition::Press, current));
+        } else if self.pressed && current.distance >= params.release_height {
+            self.pressed = false;
+            transitions.push((TouchTransition::Release, current));
+        }
+        self.last = Some(current);
+        transitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TouchTransition::{Hover, HoverEnd, Press, Release};
+
+    const PARAMS: PokeParams = PokeParams {
+        press_depth: 0.125,
+        release_height: 0.25,
+        hover_range: 1.0,
+    };
+
+    /// Feed fingertip distances, None when untracked, and collect each update's transitions
+    /// 指先の距離を入力し (未トラッキングなら None)、更新ごとの遷移を集める
+    fn pokes(distances: &[Option<f64>]) -> Vec<Vec<TouchTransition>> {
+        let mut tracker = PokeTracker::default();
+        distances
+            .iter()
+            .map(|&distance| {
+                let sample = distance.map(|distance| PlaneSample {
+                    u: 0.5,
+                    v: 0.5,
+                    distance,
+                });
+                tracker
+                    .update(&PARAMS, sample)
+                    .into_iter()
+                    .map(|(transition, _)| transition)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn presses_at_press_depth_and_not_again_until_release_height() {
+        let transitions = pokes(&[
+            Some(0.5),
+            Some(-0.0625),
+            Some(-0.125), // Exactly -press_depth / ちょうど -press_depth
+            Some(0.125),
+            Some(-0.5), // Still pressed / 押下のまま
+            Some(0.25), // Exactly release_height / ちょうど release_height
+            Some(-0.125),
+        ]);
+        assert_eq!(
+            transitions,
+            [
+                vec![Hover],
+                vec![Hover],
+                vec![Hover, Press],
+                vec![Hover],
+                vec![Hover],
+                vec![Hover, Release],
+                vec![Hover, Press],
+            ]
+        );
+    }
+
+    #[test]
+    fn leaving_hover_range_releases_then_ends_hover_at_the_last_sample() {
+        let mut tracker = PokeTracker::default();
+        let inside = |distance| {
+            Some(PlaneSample {
+                u: 0.25,
+                v: 0.75,
+                distance,
+            })
+        };
+        tracker.update(&PARAMS, inside(0.5));
+        tracker.update(&PARAMS, inside(-0.25));
+        let outside = Some(PlaneSample {
+            u: 0.5,
+            v: 0.5,
+            distance: 1.5,
+        });
+        assert_eq!(
+            tracker.update(&PARAMS, outside),
+            [
+                (Release, inside(-0.25).unwrap()),
+                (HoverEnd, inside(-0.25).unwrap()),
+            ]
+        );
+        assert_eq!(tracker.update(&PARAMS, outside), []);
+    }
+
+    #[test]
+    fn plane_samples_are_positive_in_front_and_negative_behind() {
+        // Binary-exact heights keep the f32 cast exact / 2進で正確な高さにして f32 のキャストを誤差なしにする
+        for (height, expected) in [(0.125, 0.125), (0.0, 0.0), (-0.0625, -0.0625)] {
+            let sample = sample_test_plane([0.25, 0.75, height], 0.5).unwrap();
+            assert_eq!(sample.distance, expected, "height {height}");
+            assert_eq!((sample.u, sample.v), (0.25, 0.75));
+        }
+        // A point in front hovers and one pushed behind presses
+        // 手前の点はホバー、奥に押し込んだ点は押下になる
+        let mut tracker = PokeTracker::default();
+        let hover = tracker.update(&PARAMS, sample_test_plane([0.5, 0.5, 0.5], 2.0));
+        assert_eq!(hover.len(), 1);
+        let press = tracker.update(&PARAMS, sample_test_plane([0.5, 0.5, -0.125], 2.0));
+        assert_eq!(press.last().map(|(transition, _)| *transition), Some(Press));
+    }
+
+    #[test]
+    fn plane_cast_starts_range_in_front_and_points_back() {
+        let (source, direction) = plane_cast([1.0, 2.0, 3.0], [0.0, 1.0, 0.0], 0.5);
+        assert_eq!(source, [1.0, 2.5, 3.0]);
+        assert_eq!(direction, [-0.0, -1.0, -0.0]);
+    }
+
+    #[test]
+    fn losing_tracking_while_hovering_only_ends_hover() {
+        assert_eq!(
+            pokes(&[Some(0.5), None, None]),
+            [vec![Hover], vec![HoverEnd], vec![]]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use napi_derive::napi;
+use openvr_sys as vr;
+
+use super::constants::{SKELETON_LEFT_ACTION, SKELETON_RIGHT_ACTION};
+use super::errors::require_fn;
+use super::handles::{overlay_handle, OverlayHandle};
+use super::input_ops::input_error_if_needed;
+use super::manager::OverlayManager;
+use super::math::{transform_point, unit_z_axis};
+use super::poke::{plane_cast, plane_distance, PlaneSample, PokeParams, TouchTransition};
+use super::types::{FingertipPokeParams, InputActionType, InputHand, TouchEvent, TouchEventType};
+
+/// OpenVR hand skeleton layout / OpenVR のハンドスケルトンの構成
+const HAND_BONE_COUNT: usize = 31;
+const INDEX_FINGER_TIP_BONE: usize = 10; // eBone_IndexFinger4
+
+pub(super) const TOUCH_HANDS: [InputHand; 2] = [InputHand::Left, InputHand::Right];
+
+/// Slot of a hand in per-hand arrays / 手ごとの配列でのスロット
+pub(super) fn hand_slot(hand: InputHand) -> usize {
+    match hand {
+        InputHand::Left | InputHand::Any => 0,
+        InputHand::Right => 1,
+    }
+}
+
+impl PokeParams {
+    fn from_napi(params: &FingertipPokeParams) -> napi::Result<Self> {
+        let params = Self {
+            press_depth: params.pressDepth,
+            release_height: params.releaseHeight,
+            hover_range: params.hoverRange,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn to_napi(self) -> FingertipPokeParams {
+        FingertipPokeParams {
+            pressDepth: self.press_depth,
+            releaseHeight: self.release_height,
+            hoverRange: self.hover_range,
+        }
+    }
+}
+
+impl TouchTransition {
+    pub(super) fn to_napi(self) -> TouchEventType {
+        match self {
+            TouchTransition::Hover => TouchEventType::Hover,
+            TouchTransition::Press => TouchEventType::Press,
+            TouchTransition::Release => TouchEventType::Release,
+            TouchTransition::HoverEnd => TouchEventType::HoverEnd,
+        }
+    }
+}
+
+pub(super) fn touch_event(
+    hand: InputHand,
+    transition: TouchTransition,
+    sample: PlaneSample,
+) -> TouchEvent {
+    TouchEvent {
+        eventType: transition.to_napi(),
+        hand,
+        u: sample.u,
+        v: sample.v,
+        distance: sample.distance,
+    }
+}
+
+fn skeleton_action(hand: InputHand) -> napi::Result<&'static str> {
+    match hand {
+        InputHand::Left => Ok(SKELETON_LEFT_ACTION),
+        InputHand::Right => Ok(SKELETON_RIGHT_ACTION),
+        InputHand::Any => Err(napi::Error::from_reason(
+            "fingertip hand must be Left or Right",
+        )),
+    }
+}
+
+impl OverlayManager {
+    /// Index fingertip in the configured universe, `None` while the hand is not tracked
+    /// 設定ユニバースでの人差し指の先端。手がトラッキングされていなければ `None`
+    pub(super) fn fingertip_position(&self, hand: InputHand) -> napi::Result<Option<[f64; 3]>> {
+        let action = self.borrow_input_cache()?.resolve(
+            skeleton_action(hand)?,
+            &[InputActionType::Skeleton],
+            Some(hand),
+        )?;
+        // Bones are relative to the skeleton action's own pose / ボーンはスケルトンアクション自身のポーズ基準
+        let pose = self.read_pose_action(action)?;
+        if !pose.bActive || !pose.pose.bPoseIsValid {
+            return Ok(None);
+        }
+
+        let input = self.input()?;
+        let get_bones_fn = require_fn(input.GetSkeletalBoneData, "GetSkeletalBoneData")?;
+        let mut bones: [vr::VRBoneTransform_t; HAND_BONE_COUNT] = unsafe { std::mem::zeroed() };
+        let err = unsafe {
+            get_bones_fn(
+                action.handle,
+                vr::EVRSkeletalTransformSpace_VRSkeletalTransformSpace_Model,
+                vr::EVRSkeletalMotionRange_VRSkeletalMotionRange_WithController,
+                bones.as_mut_ptr(),
+                HAND_BONE_COUNT as u32,
+            )
+        };
+        if err == vr::EVRInputError_VRInputError_NoData {
+            return Ok(None);
+        }
+        input_error_if_needed("GetSkeletalBoneData", err)?;
+
+        let tip = bones[INDEX_FINGER_TIP_BONE].position.v;
+        Ok(Some(transform_point(
+            &pose.pose.mDeviceToAbsoluteTracking,
+            [tip[0] as f64, tip[1] as f64, tip[2] as f64],
+        )))
+    }
+
+    /// Project `point` onto the overlay along its normal. Points up to `range` in front of
+    /// or behind the surface are found; `None` outside that or off the overlay's bounds.
+    /// `point` をオーバーレイの法線方向に投影する。面の前後 `range` 以内の点が対象で、
+    /// それより遠いかオーバーレイの範囲外なら `None`。
+    pub(super) fn sample_overlay_plane(
+        &self,
+        handle: OverlayHandle,
+        point: [f64; 3],
+        range: f64,
+    ) -> napi::Result<Option<PlaneSample>> {
+        let Some(normal) = self
+            .overlay_world_transform(handle)?
+            .as_ref()
+            .and_then(unit_z_axis)
+        else {
+            return Ok(None);
+        };
+        let (source, direction) = plane_cast(point, normal, range);
+        Ok(self
+            .intersect_overlay(handle, source, direction)?
+            .map(|results| PlaneSample {
+                u: results.vUVs.v[0] as f64,
+                v: results.vUVs.v[1] as f64,
+                distance: plane_distance(results.fDistance as f64, range),
+            }))
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    #[napi]
+    pub fn get_fingertip_position(&self, hand: InputHand) -> napi::Result<Option<Vec<f64>>> {
+        Ok(self.fingertip_position(hand)?.map(Vec::from))
+    }
+
+    /// Test both index fingertips against the overlay and return hover, press, release and
+    /// hover-end events. Hover events repeat every call with the height for visual feedback.
+    /// 両手の人差し指の先端をオーバーレイに対して判定し、ホバー・押下・解放・ホバー終了の
+    /// イベントを返す。ホバーイベントは視覚フィードバック用の高さ付きで毎回送られる。
+    #[napi]
+    pub fn poll_fingertip_poke(&self, handle: i64) -> napi::Result<Vec<TouchEvent>> {
+        let handle = overlay_handle(handle)?;
+        let params = self.poke_params();
+        let mut events = Vec::new();
+        for hand in TOUCH_HANDS {
+            let sample = match self.fingertip_position(hand)? {
+                // Twice the hover range keeps deep presses inside the cast
+                // ホバー範囲の2倍にして深い押し込みもキャスト内に収める
+                Some(tip) => self.sample_overlay_plane(handle, tip, params.hover_range * 2.0)?,
+                None => None,
+            };
+            let transitions =
+                self.borrow_poke_trackers_mut()?[hand_slot(hand)].update(&params, sample);
+            events.extend(
+                transitions
+                    .into_iter()
+                    .map(|(transition, sample)| touch_event(hand, transition, sample)),
+            );
+        }
+        Ok(events)
+    }
+
+    /// Forget hover / press state, e.g. after switching the target overlay
+    /// ホバー・押下状態を破棄する (対象オーバーレイの切り替え後など)
+    #[napi]
+    pub fn reset_fingertip_poke(&self) -> napi::Result<()> {
+        *self.borrow_poke_trackers_mut()? = Default::default();
+        Ok(())
+    }
+
+    #[napi]
+    pub fn set_fingertip_poke_params(&self, params: FingertipPokeParams) -> napi::Result<()> {
+        self.set_poke_params(PokeParams::from_napi(&params)?);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_fingertip_poke_params(&self) -> FingertipPokeParams {
+        self.poke_params().to_napi()
+    }
+}
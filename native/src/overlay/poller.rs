@@ -0,0 +1,596 @@
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::Status;
+use openvr_sys as vr;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::button_map::LegacyButtonMaps;
+use super::button_map_ops::legacy_axes;
+use super::device_ops::read_string_device_property;
+use super::errors::require_fn;
+use super::manager::create_poses_cache;
+use super::math::{mul_hmd_matrix34, pose_ray};
+use super::pose_ops::{prediction_seconds, PosePrediction};
+use super::stabilizer::{PointerStabilizer, StabilizerParams};
+use super::types::{InputEvent, InputEventType};
+
+pub(super) const DEFAULT_POLL_RATE_HZ: u32 = 120;
+pub(super) const MIN_POLL_RATE_HZ: u32 = 30;
+pub(super) const MAX_POLL_RATE_HZ: u32 = 1000;
+pub(super) const DEFAULT_SCROLL_DEADZONE: f64 = 0.2;
+pub(super) const DEFAULT_SCROLL_SPEED: f64 = 20.0;
+
+/// Same threshold as `CURSOR_MOVE_EPSILON` in the JS input loop
+/// JS 入力ループの `CURSOR_MOVE_EPSILON` と同じしきい値
+const HOVER_MOVE_EPSILON: f64 = 0.0005;
+
+/// JS callback receiving one batch of events per tick / 1ティック分のイベントをまとめて受け取る JS コールバック
+pub(super) type InputEventCallback =
+    ThreadsafeFunction<Vec<InputEvent>, (), Vec<InputEvent>, Status, false>;
+
+#[derive(Clone, Debug)]
+pub(super) struct PollerConfig {
+    pub overlay_handle: u64,
+    pub universe: vr::ETrackingUniverseOrigin,
+    pub interval: Duration,
+    pub scroll_deadzone: f64,
+    pub scroll_speed: f64,
+    pub stabilizer_default: StabilizerParams,
+    pub stabilizer_overrides: HashMap<u32, StabilizerParams>,
+    pub button_maps: LegacyButtonMaps,
+    pub prediction: PosePrediction,
+    /// Aim pose relative to the device pose per controller index, from `aim_offset`
+    /// コントローラーのインデックスごとの、デバイスポーズに対するエイムポーズ (`aim_offset` から)
+    pub aim_offsets: HashMap<u32, [[f32; 4]; 3]>,
+}
+
+/// Copies of the OpenVR function tables for the poller thread. The tables only hold
+/// function pointers, which stay valid until VR shutdown; the manager joins the
+/// thread before shutting down.
+/// ポーラースレッド用の OpenVR 関数テーブルのコピー。テーブルは関数ポインタのみを持ち、
+/// VR シャットダウンまで有効。マネージャーはシャットダウン前にスレッドを join する。
+#[derive(Clone, Copy)]
+pub(super) struct PollerTables {
+    pub system: vr::VR_IVRSystem_FnTable,
+    pub overlay: vr::VR_IVROverlay_FnTable,
+}
+
+impl PollerTables {
+    /// Fail on the JS thread instead of silently inside the poller
+    /// ポーラー内で黙って失敗するのではなく JS スレッド側で失敗させる
+    pub(super) fn check(&self) -> napi::Result<()> {
+        require_fn(
+            self.system.GetDeviceToAbsoluteTrackingPose,
+            "GetDeviceToAbsoluteTrackingPose",
+        )?;
+        require_fn(self.system.GetTrackedDeviceClass, "GetTrackedDeviceClass")?;
+        require_fn(self.system.GetControllerState, "GetControllerState")?;
+        require_fn(
+            self.overlay.ComputeOverlayIntersection,
+            "ComputeOverlayIntersection",
+        )?;
+        Ok(())
+    }
+}
+
+/// One controller reading taken by the poller / ポーラーが取得したコントローラー1台分の読み取り値
+#[derive(Clone, Copy, Debug, Default)]
+struct ControllerSample {
+    hit: Option<(f64, f64)>,
+    trigger: bool,
+    scroll_axis: f64,
+}
+
+/// Per-tick context shared by every controller / 全コントローラーで共有するティックごとの情報
+struct TickContext<'a> {
+    config: &'a PollerConfig,
+    seconds: f64,
+    dt: f64,
+    timestamp_ms: f64,
+}
+
+/// Edge detection state of one controller / コントローラー1台分のエッジ検出状態
+#[derive(Default)]
+struct ControllerTracker {
+    hovering: bool,
+    last_uv: Option<(f64, f64)>,
+    trigger: bool,
+    stabilizer: PointerStabilizer,
+}
+
+impl ControllerTracker {
+    fn push(
+        &self,
+        events: &mut Vec<InputEvent>,
+        event_type: InputEventType,
+        controller_index: u32,
+        scroll_delta: f64,
+        timestamp_ms: f64,
+    ) {
+        let (u, v) = self.last_uv.unwrap_or((0.0, 0.0));
+        events.push(InputEvent {
+            eventType: event_type,
+            controllerIndex: controller_index,
+            u,
+            v,
+            scrollDelta: scroll_delta,
+            timestampMs: timestamp_ms,
+        });
+    }
+
+    fn step(
+        &mut self,
+        index: u32,
+        sample: ControllerSample,
+        tick: &TickContext,
+        events: &mut Vec<InputEvent>,
+    ) {
+        let config = tick.config;
+        let params = config
+            .stabilizer_overrides
+            .get(&index)
+            .copied()
+            .unwrap_or(config.stabilizer_default);
+
+        if let Some((u, v)) = sample.hit {
+            let smoothed = self.stabilizer.update(&params, u, v, tick.seconds);
+            let moved = self.last_uv.is_none_or(|(last_u, last_v)| {
+                (smoothed.0 - last_u).abs() > HOVER_MOVE_EPSILON
+                    || (smoothed.1 - last_v).abs() > HOVER_MOVE_EPSILON
+            });
+            self.last_uv = Some(smoothed);
+            if !self.hovering || moved {
+                self.push(
+                    events,
+                    InputEventType::HoverMove,
+                    index,
+                    0.0,
+                    tick.timestamp_ms,
+                );
+            }
+            self.hovering = true;
+        } else if self.hovering {
+            self.push(
+                events,
+                InputEventType::HoverEnd,
+                index,
+                0.0,
+                tick.timestamp_ms,
+            );
+            self.hovering = false;
+            self.stabilizer = PointerStabilizer::default();
+        }
+
+        if sample.trigger != self.trigger {
+            self.trigger = sample.trigger;
+            let event_type = if sample.trigger {
+                InputEventType::Press
+            } else {
+                InputEventType::Release
+            };
+            self.push(events, event_type, index, 0.0, tick.timestamp_ms);
+        }
+
+        let magnitude = sample.scroll_axis.abs();
+        if self.hovering && magnitude > config.scroll_deadzone {
+            // Rescale past the deadzone so scrolling starts from zero
+            // デッドゾーンを超えた分を再スケールし、スクロールが0から始まるようにする
+            let scaled = (magnitude - config.scroll_deadzone) / (1.0 - config.scroll_deadzone);
+            let delta = sample.scroll_axis.signum() * scaled * config.scroll_speed * tick.dt;
+            self.push(
+                events,
+                InputEventType::Scroll,
+                index,
+                delta,
+                tick.timestamp_ms,
+            );
+        }
+    }
+
+    /// Close open hover/press states of a controller that went away
+    /// 消えたコントローラーのホバー/押下状態を閉じる
+    fn finish(&self, index: u32, timestamp_ms: f64, events: &mut Vec<InputEvent>) {
+        if self.hovering {
+            self.push(events, InputEventType::HoverEnd, index, 0.0, timestamp_ms);
+        }
+        if self.trigger {
+            self.push(events, InputEventType::Release, index, 0.0, timestamp_ms);
+        }
+    }
+}
+
+fn unix_timestamp_ms() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+        .unwrap_or(0.0)
+}
+
+/// Background thread polling poses and buttons at a fixed rate
+/// 一定レートでポーズとボタンをポーリングするバックグラウンドスレッド
+pub(super) struct InputPoller {
+    stop: Arc<AtomicBool>,
+    config: Arc<Mutex<PollerConfig>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl InputPoller {
+    pub(super) fn spawn(
+        tables: PollerTables,
+        config: PollerConfig,
+        callback: InputEventCallback,
+    ) -> napi::Result<Self> {
+        tables.check()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let config = Arc::new(Mutex::new(config));
+        let thread = {
+            let stop = Arc::clone(&stop);
+            let config = Arc::clone(&config);
+            std::thread::Builder::new()
+                .name("vr-input-poller".to_string())
+                .spawn(move || run(tables, &stop, &config, &callback))
+                .map_err(|e| {
+                    napi::Error::from_reason(format!("Failed to spawn input poller: {e}"))
+                })?
+        };
+        Ok(Self {
+            stop,
+            config,
+            thread: Some(thread),
+        })
+    }
+
+    pub(super) fn lock_config(&self) -> napi::Result<MutexGuard<'_, PollerConfig>> {
+        self.config
+            .lock()
+            .map_err(|_| napi::Error::from_reason("input poller config lock poisoned"))
+    }
+
+    pub(super) fn is_running(&self) -> bool {
+        self.thread
+            .as_ref()
+            .is_some_and(|thread| !thread.is_finished())
+    }
+}
+
+impl Drop for InputPoller {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Source and direction of the pointer ray: the aim pose when its offset is known, like
+/// `get_aim_ray`, else the device's -Z axis
+/// ポインターレイの始点と方向。オフセットが分かっていれば `get_aim_ray` と同じエイムポーズ、
+/// なければデバイスの -Z 軸
+fn pointer_ray(
+    device: &vr::HmdMatrix34_t,
+    aim_offset: Option<&[[f32; 4]; 3]>,
+) -> (vr::HmdVector3_t, vr::HmdVector3_t) {
+    let aim = aim_offset.map_or(*device, |offset| {
+        mul_hmd_matrix34(device, &vr::HmdMatrix34_t { m: *offset })
+    });
+    let (origin, direction) = pose_ray(&aim.m);
+    let vector = |values: Vec<f64>| vr::HmdVector3_t {
+        v: [values[0] as f32, values[1] as f32, values[2] as f32],
+    };
+    (vector(origin), vector(direction))
+}
+
+fn run(
+    tables: PollerTables,
+    stop: &AtomicBool,
+    config: &Mutex<PollerConfig>,
+    callback: &InputEventCallback,
+) {
+    let (
+        Some(get_poses_fn),
+        Some(get_class_fn),
+        Some(get_controller_state_fn),
+        Some(compute_intersection_fn),
+    ) = (
+        tables.system.GetDeviceToAbsoluteTrackingPose,
+        tables.system.GetTrackedDeviceClass,
+        tables.system.GetControllerState,
+        tables.overlay.ComputeOverlayIntersection,
+    )
+    else {
+        return;
+    };
+
+    let mut poses = create_poses_cache();
+    let mut trackers: HashMap<u32, ControllerTracker> = HashMap::new();
+    // Read once per connection; the type of a connected device does not change. A missing
+    // type is retried, as SteamVR may not report it right after the device connects
+    // 接続ごとに1回だけ読む。接続中のデバイスの種別は変わらない。種別が無ければ再試行する
+    // (接続直後は SteamVR がまだ報告しないことがある)
+    let mut controller_types: HashMap<u32, String> = HashMap::new();
+    let epoch = Instant::now();
+    let mut last_tick = epoch;
+
+    while !stop.load(Ordering::Acquire) {
+        let tick_start = Instant::now();
+        let Ok(config) = config.lock().map(|config| config.clone()) else {
+            break;
+        };
+        let tick = TickContext {
+            config: &config,
+            seconds: (tick_start - epoch).as_secs_f64(),
+            dt: (tick_start - last_tick).as_secs_f64(),
+            timestamp_ms: unix_timestamp_ms(),
+        };
+        last_tick = tick_start;
+
+        let mut events = Vec::new();
+        let mut connected = Vec::new();
+        // Same prediction as the manager's poses; none if it cannot be computed
+        // マネージャーのポーズと同じ予測。計算できなければ予測しない
+        let predict_seconds = prediction_seconds(&tables.system, config.prediction).unwrap_or(0.0);
+        unsafe {
+            get_poses_fn(
+                config.universe,
+                predict_seconds,
+                poses.as_mut_ptr(),
+                poses.len() as u32,
+            );
+        }
+
+        for (index, pose) in poses.iter().enumerate() {
+            let index = index as u32;
+            if !pose.bDeviceIsConnected
+                || unsafe { get_class_fn(index) }
+                    != vr::ETrackedDeviceClass_TrackedDeviceClass_Controller
+            {
+                continue;
+            }
+            connected.push(index);
+
+            let mut sample = ControllerSample::default();
+            if pose.bPoseIsValid && config.overlay_handle != vr::k_ulOverlayHandleInvalid {
+                let (source, direction) = pointer_ray(
+                    &pose.mDeviceToAbsoluteTracking,
+                    config.aim_offsets.get(&index),
+                );
+                let mut params = vr::VROverlayIntersectionParams_t {
+                    vSource: source,
+                    vDirection: direction,
+                    eOrigin: config.universe,
+                };
+                let mut results: vr::VROverlayIntersectionResults_t = unsafe { std::mem::zeroed() };
+                if unsafe {
+                    compute_intersection_fn(config.overlay_handle, &mut params, &mut results)
+                } {
+                    sample.hit = Some((results.vUVs.v[0] as f64, results.vUVs.v[1] as f64));
+                }
+            }
+
+            let mut state: vr::VRControllerState_t = unsafe { std::mem::zeroed() };
+            if unsafe {
+                get_controller_state_fn(
+                    index,
+                    &mut state,
+                    std::mem::size_of::<vr::VRControllerState_t>() as u32,
+                )
+            } {
+                if let Entry::Vacant(entry) = controller_types.entry(index) {
+                    if let Ok(Some(name)) = read_string_device_property(
+                        &tables.system,
+                        index,
+                        vr::ETrackedDeviceProperty_Prop_ControllerType_String,
+                    ) {
+                        entry.insert(name);
+                    }
+                }
+                // SteamVR Input may only be updated on the main thread, so the trigger comes
+                // from the legacy state through the button map; no type selects the generic map
+                // SteamVR Input はメインスレッドでしか更新できないため、トリガーはボタンマップを
+                // 通したレガシーの状態から読む。種別が無ければ汎用のマップを使う
+                let reading = config
+                    .button_maps
+                    .resolve(controller_types.get(&index).map(String::as_str))
+                    .map
+                    .read(state.ulButtonPressed, &legacy_axes(&state));
+                sample.trigger = reading.trigger_pressed;
+                let joystick_y = reading.joystick.1;
+                let touchpad_y = reading.touchpad.1;
+                sample.scroll_axis = if joystick_y.abs() >= touchpad_y.abs() {
+                    joystick_y
+                } else {
+                    touchpad_y
+                };
+            }
+
+            trackers
+                .entry(index)
+                .or_default()
+                .step(index, sample, &tick, &mut events);
+        }
+
+        controller_types.retain(|index, _| connected.contains(index));
+        trackers.retain(|index, tracker| {
+            let keep = connected.contains(index);
+            if !keep {
+                tracker.finish(*index, tick.timestamp_ms, &mut events);
+            }
+            keep
+        });
+
+        if !events.is_empty()
+            && callback.call(events, ThreadsafeFunctionCallMode::NonBlocking) == Status::Closing
+        {
+            break;
+        }
+        if let Some(remaining) = config.interval.checked_sub(tick_start.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> PollerConfig {
+        PollerConfig {
+            overlay_handle: 1,
+            universe: vr::ETrackingUniverseOrigin_TrackingUniverseStanding,
+            interval: Duration::from_millis(8),
+            scroll_deadzone: 0.5,
+            scroll_speed: 20.0,
+            stabilizer_default: StabilizerParams {
+                enabled: false,
+                ..StabilizerParams::DEFAULT
+            },
+            stabilizer_overrides: HashMap::new(),
+            button_maps: LegacyButtonMaps::default(),
+            prediction: PosePrediction::Disabled,
+            aim_offsets: HashMap::new(),
+        }
+    }
+
+    fn sample(hit: Option<(f64, f64)>, trigger: bool, scroll_axis: f64) -> ControllerSample {
+        ControllerSample {
+            hit,
+            trigger,
+            scroll_axis,
+        }
+    }
+
+    /// Step one tracker through samples taken 0.25 s apart and collect
+    /// (event type, u, v, scroll delta) per tick
+    /// 0.25 秒間隔のサンプルで1台分のトラッカーを進め、ティックごとに
+    /// (イベント種別, u, v, スクロール量) を集める
+    fn steps(samples: &[ControllerSample]) -> Vec<Vec<(InputEventType, f64, f64, f64)>> {
+        let config = config();
+        let mut tracker = ControllerTracker::default();
+        samples
+            .iter()
+            .enumerate()
+            .map(|(i, &sample)| {
+                let tick = TickContext {
+                    config: &config,
+                    seconds: i as f64 * 0.25,
+                    dt: 0.25,
+                    timestamp_ms: i as f64 * 250.0,
+                };
+                let mut events = Vec::new();
+                tracker.step(3, sample, &tick, &mut events);
+                assert!(events.iter().all(|event| event.controllerIndex == 3));
+                events
+                    .into_iter()
+                    .map(|event| (event.eventType, event.u, event.v, event.scrollDelta))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hover_moves_only_past_the_epsilon_and_ends_when_the_ray_leaves() {
+        let events = steps(&[
+            sample(Some((0.5, 0.5)), false, 0.0),
+            sample(Some((0.5001, 0.5)), false, 0.0),
+            sample(Some((0.25, 0.75)), false, 0.0),
+            sample(None, false, 0.0),
+            sample(None, false, 0.0),
+        ]);
+        assert_eq!(
+            events,
+            [
+                vec![(InputEventType::HoverMove, 0.5, 0.5, 0.0)],
+                vec![],
+                vec![(InputEventType::HoverMove, 0.25, 0.75, 0.0)],
+                vec![(InputEventType::HoverEnd, 0.25, 0.75, 0.0)],
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn trigger_edges_press_and_release_at_the_last_point() {
+        let events = steps(&[
+            sample(Some((0.5, 0.5)), false, 0.0),
+            sample(Some((0.5, 0.5)), true, 0.0),
+            sample(Some((0.5, 0.5)), true, 0.0),
+            sample(None, true, 0.0),
+            sample(None, false, 0.0),
+        ]);
+        assert_eq!(
+            events,
+            [
+                vec![(InputEventType::HoverMove, 0.5, 0.5, 0.0)],
+                vec![(InputEventType::Press, 0.5, 0.5, 0.0)],
+                vec![],
+                vec![(InputEventType::HoverEnd, 0.5, 0.5, 0.0)],
+                vec![(InputEventType::Release, 0.5, 0.5, 0.0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn scroll_is_rescaled_past_the_deadzone_while_hovering() {
+        let events = steps(&[
+            sample(Some((0.5, 0.5)), false, 0.3),
+            sample(Some((0.5, 0.5)), false, 0.75),
+            sample(Some((0.5, 0.5)), false, -1.0),
+            sample(None, false, 1.0),
+        ]);
+        assert_eq!(events[0], [(InputEventType::HoverMove, 0.5, 0.5, 0.0)]);
+        // (0.75 - 0.5) / 0.5 * 20 * 0.25
+        assert_eq!(events[1], [(InputEventType::Scroll, 0.5, 0.5, 2.5)]);
+        assert_eq!(events[2], [(InputEventType::Scroll, 0.5, 0.5, -5.0)]);
+        assert_eq!(events[3], [(InputEventType::HoverEnd, 0.5, 0.5, 0.0)]);
+    }
+
+    #[test]
+    fn pointer_ray_follows_the_aim_offset_or_the_device_minus_z() {
+        // Device at (1, 2, 3) turned 90 degrees about Y, so its -Z points along -X
+        // (1, 2, 3) にあり Y 軸まわりに90度回ったデバイス。-Z は -X を向く
+        let device = vr::HmdMatrix34_t {
+            m: [
+                [0.0, 0.0, 1.0, 1.0],
+                [0.0, 1.0, 0.0, 2.0],
+                [-1.0, 0.0, 0.0, 3.0],
+            ],
+        };
+        let (source, direction) = pointer_ray(&device, None);
+        assert_eq!(source.v, [1.0, 2.0, 3.0]);
+        assert_eq!(direction.v, [-1.0, 0.0, 0.0]);
+
+        // Aim 0.5 forward of the grip and pitched down 90 degrees about X
+        // グリップの 0.5 前方で X 軸まわりに90度下を向いたエイム
+        let offset = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, -1.0, 0.0, -0.5],
+        ];
+        let (source, direction) = pointer_ray(&device, Some(&offset));
+        assert_eq!(source.v, [0.5, 2.0, 3.0]);
+        assert_eq!(direction.v, [0.0, -1.0, 0.0]);
+    }
+
+    #[test]
+    fn finish_closes_an_open_hover_and_press() {
+        let config = config();
+        let tick = TickContext {
+            config: &config,
+            seconds: 0.0,
+            dt: 0.0,
+            timestamp_ms: 1000.0,
+        };
+        let mut tracker = ControllerTracker::default();
+        let mut events = Vec::new();
+        tracker.step(1, sample(Some((0.5, 0.5)), true, 0.0), &tick, &mut events);
+        events.clear();
+        tracker.finish(1, 2000.0, &mut events);
+        let types: Vec<_> = events.iter().map(|event| event.eventType).collect();
+        assert_eq!(types, [InputEventType::HoverEnd, InputEventType::Release]);
+        assert!(events.iter().all(|event| event.timestampMs == 2000.0));
+    }
+}
@@ -0,0 +1,112 @@
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::handles::overlay_handle;
+use super::manager::OverlayManager;
+use super::poller::{
+    InputEventCallback, InputPoller, PollerConfig, PollerTables, DEFAULT_POLL_RATE_HZ,
+    DEFAULT_SCROLL_DEADZONE, DEFAULT_SCROLL_SPEED, MAX_POLL_RATE_HZ, MIN_POLL_RATE_HZ,
+};
+use super::types::{InputHand, InputPollerOptions};
+
+impl OverlayManager {
+    /// Aim offsets of both hands that can be read now; `get_aim_ray` keeps them current
+    /// 現在読める両手のエイムオフセット。`get_aim_ray` が最新に保つ
+    fn poller_aim_offsets(&self) -> napi::Result<HashMap<u32, [[f32; 4]; 3]>> {
+        if !self.borrow_input_cache()?.initialized {
+            return Ok(HashMap::new());
+        }
+        Ok([InputHand::Left, InputHand::Right]
+            .into_iter()
+            .filter_map(|hand| self.aim_offset(hand).ok().flatten())
+            .collect())
+    }
+
+    fn input_poller_config(
+        &self,
+        options: &InputPollerOptions,
+        handle: u64,
+    ) -> napi::Result<PollerConfig> {
+        let rate_hz = options.rateHz.unwrap_or(DEFAULT_POLL_RATE_HZ);
+        if !(MIN_POLL_RATE_HZ..=MAX_POLL_RATE_HZ).contains(&rate_hz) {
+            return Err(napi::Error::from_reason(format!(
+                "rateHz must be within {MIN_POLL_RATE_HZ}..={MAX_POLL_RATE_HZ}"
+            )));
+        }
+        let scroll_deadzone = options.scrollDeadzone.unwrap_or(DEFAULT_SCROLL_DEADZONE);
+        if !(0.0..1.0).contains(&scroll_deadzone) {
+            return Err(napi::Error::from_reason(
+                "scrollDeadzone must be within 0..1",
+            ));
+        }
+        let scroll_speed = options.scrollSpeed.unwrap_or(DEFAULT_SCROLL_SPEED);
+        if !scroll_speed.is_finite() {
+            return Err(napi::Error::from_reason("scrollSpeed must be finite"));
+        }
+        Ok(PollerConfig {
+            overlay_handle: handle,
+            universe: self.tracking_universe(),
+            interval: Duration::from_secs_f64(1.0 / rate_hz as f64),
+            scroll_deadzone,
+            scroll_speed,
+            stabilizer_default: self.default_stabilizer_params(),
+            stabilizer_overrides: self.borrow_stabilizer_params()?.clone(),
+            button_maps: self.borrow_legacy_button_maps()?.clone(),
+            prediction: self.pose_prediction(),
+            aim_offsets: self.poller_aim_offsets()?,
+        })
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Poll controllers on a native thread and push hover, press, release and scroll
+    /// events to `callback` in batches, independent of the renderer's frame rate. Rays
+    /// follow the aim pose seen by `get_aim_ray` with the configured pose prediction.
+    /// Presses come from the legacy trigger through the button map, as SteamVR Input may
+    /// only be updated on the main thread; the overlay toggle stays with
+    /// `poll_toggle_gesture`. Replaces a poller that is already running.
+    /// ネイティブスレッドでコントローラーをポーリングし、ホバー・押下・解放・スクロールの
+    /// イベントをまとめて `callback` に送る。レンダラーのフレームレートに依存しない。レイは
+    /// `get_aim_ray` と同じエイムポーズに設定済みのポーズ予測を適用して求める。SteamVR Input は
+    /// メインスレッドでしか更新できないため、押下はボタンマップを通したレガシーのトリガーから
+    /// 読む。オーバーレイのトグルは `poll_toggle_gesture` が扱う。実行中のポーラーがあれば置き換える。
+    #[napi]
+    pub fn start_input_poller(
+        &self,
+        options: InputPollerOptions,
+        callback: InputEventCallback,
+    ) -> napi::Result<()> {
+        let handle = overlay_handle(options.overlayHandle)?;
+        let config = self.input_poller_config(&options, handle.as_u64())?;
+
+        let tables = PollerTables {
+            system: *self.system()?,
+            overlay: *self.overlay()?,
+        };
+        // Join the previous thread before starting a new one / 新しいスレッドの開始前に前のスレッドを join する
+        drop(self.replace_input_poller(None)?);
+        let poller = InputPoller::spawn(tables, config, callback)?;
+        self.replace_input_poller(Some(poller))?;
+        Ok(())
+    }
+
+    #[napi]
+    pub fn stop_input_poller(&self) -> napi::Result<()> {
+        drop(self.replace_input_poller(None)?);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn is_input_poller_running(&self) -> napi::Result<bool> {
+        self.input_poller_running()
+    }
+
+    /// Point the running poller at another overlay / 実行中のポーラーの対象オーバーレイを変更する
+    #[napi]
+    pub fn set_input_poller_target(&self, handle: i64) -> napi::Result<()> {
+        let handle = overlay_handle(handle)?;
+        self.update_input_poller(|config| config.overlay_handle = handle.as_u64())
+    }
+}
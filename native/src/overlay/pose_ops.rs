@@ -0,0 +1,230 @@
+use napi_derive::napi;
+use openvr_sys as vr;
+use std::time::Duration;
+
+use super::constants::{HMD_DEVICE_INDEX, MAX_PREDICTED_SECONDS};
+use super::device_ops::read_float_device_property;
+use super::errors::require_fn;
+use super::manager::{create_poses_cache, OverlayManager};
+use super::math::hmd_matrix34_to_vec;
+use super::types::{DevicePose, PosePredictionMode, TrackingResult};
+
+/// How far ahead poses are predicted / ポーズを何秒先まで予測するか
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum PosePrediction {
+    Disabled,
+    Fixed(f32),
+    /// Next photon time from display frequency and vsync-to-photons latency
+    /// ディスプレイ周波数と vsync からフォトンまでの遅延から次のフォトン時刻を求める
+    Auto,
+}
+
+impl TrackingResult {
+    pub(super) fn from_vr(result: vr::ETrackingResult) -> Self {
+        match result {
+            vr::ETrackingResult_TrackingResult_Calibrating_InProgress => {
+                Self::CalibratingInProgress
+            }
+            vr::ETrackingResult_TrackingResult_Calibrating_OutOfRange => {
+                Self::CalibratingOutOfRange
+            }
+            vr::ETrackingResult_TrackingResult_Running_OK => Self::RunningOk,
+            vr::ETrackingResult_TrackingResult_Running_OutOfRange => Self::RunningOutOfRange,
+            vr::ETrackingResult_TrackingResult_Fallback_RotationOnly => Self::FallbackRotationOnly,
+            _ => Self::Uninitialized,
+        }
+    }
+}
+
+fn validate_predict_seconds(seconds: f64) -> napi::Result<f32> {
+    if !seconds.is_finite() || !(0.0..=MAX_PREDICTED_SECONDS).contains(&seconds) {
+        return Err(napi::Error::from_reason(format!(
+            "predict seconds must be within 0..={MAX_PREDICTED_SECONDS}"
+        )));
+    }
+    Ok(seconds as f32)
+}
+
+fn device_pose_from_vr(index: u32, pose: &vr::TrackedDevicePose_t) -> DevicePose {
+    DevicePose {
+        deviceIndex: index,
+        poseIsValid: pose.bPoseIsValid,
+        trackingResult: TrackingResult::from_vr(pose.eTrackingResult),
+        matrix: hmd_matrix34_to_vec(&pose.mDeviceToAbsoluteTracking.m),
+        velocity: pose.vVelocity.v.iter().map(|&v| v as f64).collect(),
+        angularVelocity: pose.vAngularVelocity.v.iter().map(|&v| v as f64).collect(),
+    }
+}
+
+/// Seconds to predict ahead for `prediction`. Takes the function table so the input
+/// poller thread predicts like the manager does.
+/// `prediction` で予測する秒数。入力ポーラーのスレッドもマネージャーと同じように予測できるよう
+/// 関数テーブルを受け取る。
+pub(super) fn prediction_seconds(
+    system: &vr::VR_IVRSystem_FnTable,
+    prediction: PosePrediction,
+) -> napi::Result<f32> {
+    match prediction {
+        PosePrediction::Disabled => Ok(0.0),
+        PosePrediction::Fixed(seconds) => Ok(seconds),
+        PosePrediction::Auto => auto_predicted_seconds(system),
+    }
+}
+
+fn auto_predicted_seconds(system: &vr::VR_IVRSystem_FnTable) -> napi::Result<f32> {
+    let get_vsync_fn = require_fn(system.GetTimeSinceLastVsync, "GetTimeSinceLastVsync")?;
+
+    let display_frequency = read_float_device_property(
+        system,
+        HMD_DEVICE_INDEX,
+        vr::ETrackedDeviceProperty_Prop_DisplayFrequency_Float,
+    )?
+    .filter(|hz| *hz > 0.0);
+    let Some(display_frequency) = display_frequency else {
+        return Ok(0.0);
+    };
+    let vsync_to_photons = read_float_device_property(
+        system,
+        HMD_DEVICE_INDEX,
+        vr::ETrackedDeviceProperty_Prop_SecondsFromVsyncToPhotons_Float,
+    )?
+    .unwrap_or(0.0);
+
+    let mut seconds_since_vsync = 0.0f32;
+    let mut frame_counter = 0u64;
+    if !unsafe { get_vsync_fn(&mut seconds_since_vsync, &mut frame_counter) } {
+        return Ok(vsync_to_photons.clamp(0.0, MAX_PREDICTED_SECONDS as f32));
+    }
+
+    // Same formula as the OpenVR docs for GetDeviceToAbsoluteTrackingPose
+    // OpenVR の GetDeviceToAbsoluteTrackingPose のドキュメントと同じ式
+    let frame_duration = 1.0 / display_frequency;
+    let predicted = frame_duration - seconds_since_vsync + vsync_to_photons;
+    Ok(predicted.clamp(0.0, MAX_PREDICTED_SECONDS as f32))
+}
+
+impl OverlayManager {
+    /// Seconds to predict ahead for the configured mode / 設定モードで予測する秒数
+    pub(super) fn predicted_seconds(&self) -> napi::Result<f32> {
+        prediction_seconds(self.system()?, self.pose_prediction())
+    }
+
+    /// Fill `poses` straight from OpenVR / OpenVR から `poses` を直接埋める
+    pub(super) fn fetch_poses(
+        &self,
+        predict_seconds: f32,
+        poses: &mut [vr::TrackedDevicePose_t],
+    ) -> napi::Result<()> {
+        let system = self.system()?;
+        let get_pose_fn = require_fn(
+            system.GetDeviceToAbsoluteTrackingPose,
+            "GetDeviceToAbsoluteTrackingPose",
+        )?;
+        unsafe {
+            get_pose_fn(
+                self.tracking_universe(),
+                predict_seconds,
+                poses.as_mut_ptr(),
+                poses.len() as u32,
+            );
+        }
+        Ok(())
+    }
+
+    /// Borrow the pose cache, refreshing it first when it is older than the TTL
+    /// ポーズキャッシュを借用する。TTL より古ければ先に更新する
+    pub(super) fn fresh_poses(
+        &self,
+    ) -> napi::Result<std::cell::RefMut<'_, Vec<vr::TrackedDevicePose_t>>> {
+        // Check if cached poses are still fresh (within TTL) / キャッシュされたポーズがまだ新鮮か確認 (TTL以内)
+        let cache_hit = self.poses_cache_valid();
+        let mut poses = self.borrow_poses_cache()?;
+        let pose_count = vr::k_unMaxTrackedDeviceCount as usize;
+        debug_assert_eq!(poses.len(), pose_count);
+        if poses.len() != pose_count {
+            poses.resize_with(pose_count, || unsafe { std::mem::zeroed() });
+        }
+
+        if !cache_hit {
+            // Cache miss: fetch all poses from OpenVR API / キャッシュミス: OpenVR APIから全ポーズを取得
+            let predict_seconds = self.predicted_seconds()?;
+            self.fetch_poses(predict_seconds, &mut poses)?;
+            self.mark_poses_cache();
+        }
+        Ok(poses)
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Matrix, velocities and tracking result of every connected device in one call.
+    /// `predictSeconds` overrides the configured prediction and bypasses the pose cache.
+    /// 接続中の全デバイスの行列・速度・トラッキング結果を1回の呼び出しで返す。
+    /// `predictSeconds` は設定された予測を上書きし、ポーズキャッシュを経由しない。
+    #[napi]
+    pub fn get_all_poses(&self, predict_seconds: Option<f64>) -> napi::Result<Vec<DevicePose>> {
+        let collect = |poses: &[vr::TrackedDevicePose_t]| {
+            poses
+                .iter()
+                .enumerate()
+                .filter(|(_, pose)| pose.bDeviceIsConnected)
+                .map(|(index, pose)| device_pose_from_vr(index as u32, pose))
+                .collect()
+        };
+
+        match predict_seconds {
+            Some(seconds) => {
+                let seconds = validate_predict_seconds(seconds)?;
+                let mut poses = create_poses_cache();
+                self.fetch_poses(seconds, &mut poses)?;
+                Ok(collect(&poses))
+            }
+            None => Ok(collect(&self.fresh_poses()?)),
+        }
+    }
+
+    #[napi]
+    pub fn set_pose_prediction(
+        &self,
+        mode: PosePredictionMode,
+        fixed_seconds: Option<f64>,
+    ) -> napi::Result<()> {
+        let prediction = match mode {
+            PosePredictionMode::Disabled => PosePrediction::Disabled,
+            PosePredictionMode::Auto => PosePrediction::Auto,
+            PosePredictionMode::Fixed => {
+                let seconds = fixed_seconds.ok_or_else(|| {
+                    napi::Error::from_reason("fixed prediction requires fixedSeconds")
+                })?;
+                PosePrediction::Fixed(validate_predict_seconds(seconds)?)
+            }
+        };
+        self.set_pose_prediction_mode(prediction);
+        self.update_input_poller(|config| config.prediction = prediction)
+    }
+
+    /// Seconds the next pose query will predict ahead / 次のポーズ取得で予測する秒数
+    #[napi]
+    pub fn get_predicted_seconds(&self) -> napi::Result<f64> {
+        Ok(self.predicted_seconds()? as f64)
+    }
+
+    #[napi]
+    pub fn set_pose_cache_ttl(&self, ttl_ms: f64) -> napi::Result<()> {
+        if !ttl_ms.is_finite() || ttl_ms < 0.0 {
+            return Err(napi::Error::from_reason(
+                "pose cache TTL must be a non-negative number",
+            ));
+        }
+        // Finite values can still overflow a Duration / 有限値でも Duration をオーバーフローしうる
+        let ttl = Duration::try_from_secs_f64(ttl_ms / 1000.0)
+            .map_err(|e| napi::Error::from_reason(format!("invalid pose cache TTL: {e}")))?;
+        self.set_poses_cache_ttl(ttl);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_pose_cache_ttl(&self) -> f64 {
+        self.poses_cache_ttl().as_secs_f64() * 1000.0
+    }
+}
@@ -0,0 +1,207 @@
+use napi_derive::napi;
+use openvr_sys as vr;
+use std::time::{Duration, Instant};
+
+use super::constants::HMD_DEVICE_INDEX;
+use super::errors::require_fn;
+use super::manager::OverlayManager;
+use super::types::{DeviceActivityLevel, PresenceState, TextureThrottleOptions};
+
+/// Upload interval while the user is away (1 fps) / 不在時のアップロード間隔 (1 fps)
+const DEFAULT_IDLE_UPLOAD_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// HMD presence as last reported by the event queue / イベントキューが最後に通知した HMD の在席状態
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct PresenceFlags {
+    pub standby: bool,
+    /// `None` until the first HMD interaction event arrives / 最初の HMD 操作イベントまでは `None`
+    pub hmd_interacting: Option<bool>,
+}
+
+impl PresenceFlags {
+    pub(super) fn new() -> Self {
+        Self {
+            standby: false,
+            hmd_interacting: None,
+        }
+    }
+}
+
+/// Texture submission throttle settings / テクスチャ送信スロットルの設定
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct TextureThrottle {
+    pub enabled: bool,
+    pub idle_interval: Duration,
+    pub skip_when_hidden: bool,
+}
+
+impl TextureThrottle {
+    pub(super) fn new() -> Self {
+        Self {
+            enabled: true,
+            idle_interval: DEFAULT_IDLE_UPLOAD_INTERVAL,
+            skip_when_hidden: true,
+        }
+    }
+}
+
+impl DeviceActivityLevel {
+    fn from_vr(level: vr::EDeviceActivityLevel) -> Self {
+        match level {
+            vr::EDeviceActivityLevel_k_EDeviceActivityLevel_Idle => Self::Idle,
+            vr::EDeviceActivityLevel_k_EDeviceActivityLevel_UserInteraction => {
+                Self::UserInteraction
+            }
+            vr::EDeviceActivityLevel_k_EDeviceActivityLevel_UserInteraction_Timeout => {
+                Self::UserInteractionTimeout
+            }
+            vr::EDeviceActivityLevel_k_EDeviceActivityLevel_Standby => Self::Standby,
+            vr::EDeviceActivityLevel_k_EDeviceActivityLevel_Idle_Timeout => Self::IdleTimeout,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Presence from the activity level, falling back to events when it is unknown. The
+/// interaction timeout only means the HMD sat still for a few seconds, as it does while
+/// the user reads or types, so it still counts as present; only idle and standby are away.
+/// アクティビティレベルから在席を判定し、不明な場合はイベントにフォールバックする。
+/// 操作タイムアウトは読んだり入力したりしている間のように HMD が数秒静止しただけなので
+/// 在席とみなし、不在はアイドルとスタンバイのみとする。
+fn user_present(level: DeviceActivityLevel, flags: PresenceFlags) -> bool {
+    if flags.standby {
+        return false;
+    }
+    match level {
+        DeviceActivityLevel::UserInteraction | DeviceActivityLevel::UserInteractionTimeout => true,
+        DeviceActivityLevel::Idle
+        | DeviceActivityLevel::IdleTimeout
+        | DeviceActivityLevel::Standby => false,
+        DeviceActivityLevel::Unknown => flags.hmd_interacting.unwrap_or(true),
+    }
+}
+
+impl OverlayManager {
+    /// Fold a system event into the presence flags / システムイベントを在席フラグに反映する
+    pub(super) fn note_presence_event(&self, event: &vr::VREvent_t) {
+        let mut flags = self.presence_flags();
+        match event.eventType {
+            vr::EVREventType_VREvent_EnterStandbyMode => flags.standby = true,
+            vr::EVREventType_VREvent_LeaveStandbyMode => flags.standby = false,
+            vr::EVREventType_VREvent_TrackedDeviceUserInteractionStarted
+                if event.trackedDeviceIndex == HMD_DEVICE_INDEX =>
+            {
+                flags.standby = false;
+                flags.hmd_interacting = Some(true);
+            }
+            vr::EVREventType_VREvent_TrackedDeviceUserInteractionEnded
+                if event.trackedDeviceIndex == HMD_DEVICE_INDEX =>
+            {
+                flags.hmd_interacting = Some(false);
+            }
+            _ => return,
+        }
+        self.set_presence_flags(flags);
+    }
+
+    fn hmd_activity_level(&self) -> napi::Result<DeviceActivityLevel> {
+        let system = self.system()?;
+        let get_activity_fn = require_fn(
+            system.GetTrackedDeviceActivityLevel,
+            "GetTrackedDeviceActivityLevel",
+        )?;
+        Ok(DeviceActivityLevel::from_vr(unsafe {
+            get_activity_fn(HMD_DEVICE_INDEX)
+        }))
+    }
+
+    /// Whether any owned overlay is currently shown / 所有中のオーバーレイが1つでも表示中か
+    fn any_owned_overlay_visible(&self) -> napi::Result<bool> {
+        let overlay = self.overlay()?;
+        let is_visible_fn = require_fn(overlay.IsOverlayVisible, "IsOverlayVisible")?;
+        let owned = self.borrow_owned_overlays()?;
+        if owned.is_empty() {
+            return Ok(true);
+        }
+        Ok(owned.iter().any(|&handle| unsafe { is_visible_fn(handle) }))
+    }
+
+    /// Whether a texture upload should be skipped right now / 今テクスチャのアップロードを省略すべきか
+    fn texture_upload_throttled(&self) -> napi::Result<bool> {
+        let throttle = self.texture_throttle();
+        if !throttle.enabled {
+            return Ok(false);
+        }
+        if throttle.skip_when_hidden && !self.any_owned_overlay_visible()? {
+            return Ok(true);
+        }
+        if user_present(self.hmd_activity_level()?, self.presence_flags()) {
+            return Ok(false);
+        }
+        Ok(self
+            .last_texture_upload()
+            .is_some_and(|at| at.elapsed() < throttle.idle_interval))
+    }
+
+    /// Gate for texture uploads; records the upload when it is allowed
+    /// テクスチャアップロードの可否判定。許可した場合はアップロード時刻を記録する
+    pub(super) fn begin_texture_upload(&self) -> napi::Result<bool> {
+        if self.texture_upload_throttled()? {
+            return Ok(false);
+        }
+        self.set_last_texture_upload(Instant::now());
+        Ok(true)
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    #[napi]
+    pub fn is_user_present(&self) -> napi::Result<bool> {
+        Ok(user_present(
+            self.hmd_activity_level()?,
+            self.presence_flags(),
+        ))
+    }
+
+    #[napi]
+    pub fn get_presence_state(&self) -> napi::Result<PresenceState> {
+        let level = self.hmd_activity_level()?;
+        let flags = self.presence_flags();
+        Ok(PresenceState {
+            userPresent: user_present(level, flags),
+            activityLevel: level,
+            standby: flags.standby,
+            textureUploadsThrottled: self.texture_upload_throttled()?,
+        })
+    }
+
+    /// Lets the capture loop skip work the native side would throw away anyway
+    /// ネイティブ側で破棄されるキャプチャ処理をキャプチャループ側で省略できるようにする
+    #[napi]
+    pub fn should_submit_texture(&self) -> napi::Result<bool> {
+        Ok(!self.texture_upload_throttled()?)
+    }
+
+    #[napi]
+    pub fn set_texture_throttle(&self, options: TextureThrottleOptions) -> napi::Result<()> {
+        let mut throttle = self.texture_throttle();
+        if let Some(enabled) = options.enabled {
+            throttle.enabled = enabled;
+        }
+        if let Some(interval_ms) = options.idleIntervalMs {
+            if !interval_ms.is_finite() || interval_ms < 0.0 {
+                return Err(napi::Error::from_reason(
+                    "idleIntervalMs must be a non-negative number",
+                ));
+            }
+            throttle.idle_interval = Duration::try_from_secs_f64(interval_ms / 1000.0)
+                .map_err(|e| napi::Error::from_reason(format!("invalid idleIntervalMs: {e}")))?;
+        }
+        if let Some(skip_when_hidden) = options.skipWhenHidden {
+            throttle.skip_when_hidden = skip_when_hidden;
+        }
+        self.set_texture_throttle_config(throttle);
+        Ok(())
+    }
+}
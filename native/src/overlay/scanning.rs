@@ -0,0 +1,305 @@
+use super::constants::TRIGGER_ACTION;
+
+/// Timing of switch-access scanning / スイッチスキャンのタイミングの設定
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct ScanParams {
+    /// Time each row or key stays highlighted / 各行・各キーを強調表示しておく時間
+    pub step_seconds: f64,
+    /// Longer hold on the first item after entering a row or returning to rows, so the
+    /// user has time to react / 行に入った直後や行に戻った直後の最初の項目だけ長く待ち、反応する余裕を作る
+    pub first_step_seconds: f64,
+    /// Passes over a row's keys without a selection before scanning rows again
+    /// 選択なしで行のキーを何周したら行のスキャンに戻るか
+    pub key_loops: u32,
+}
+
+impl ScanParams {
+    pub(super) const DEFAULT: Self = Self {
+        step_seconds: 1.0,
+        first_step_seconds: 1.5,
+        key_loops: 2,
+    };
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+        if !positive(self.step_seconds) || !positive(self.first_step_seconds) {
+            return Err(napi::Error::from_reason(
+                "stepMs and firstStepMs must be positive numbers",
+            ));
+        }
+        if self.key_loops == 0 {
+            return Err(napi::Error::from_reason("keyLoops must be at least 1"));
+        }
+        Ok(())
+    }
+}
+
+/// The switch action and its timing / スイッチのアクションとタイミング
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct ScanConfig {
+    pub action: String,
+    pub params: ScanParams,
+}
+
+impl ScanConfig {
+    pub(super) fn new() -> Self {
+        Self {
+            action: TRIGGER_ACTION.to_string(),
+            params: ScanParams::DEFAULT,
+        }
+    }
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        if self.action.is_empty() {
+            return Err(napi::Error::from_reason("scan action must not be empty"));
+        }
+        self.params.validate()
+    }
+}
+
+/// What the scan highlights / スキャンが強調表示しているもの
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ScanFocus {
+    Row(usize),
+    Key {
+        row: usize,
+        position: usize,
+        loops: u32,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum ScanEvent {
+    /// A whole row (`key` is None) or one key of it is now highlighted
+    /// 行全体 (`key` が None) またはその中のキー1つが強調表示された
+    Highlight {
+        row: usize,
+        key: Option<usize>,
+    },
+    Select {
+        key: usize,
+    },
+}
+
+/// Row-column switch scanning. Rows are highlighted in turn; a switch press enters the
+/// highlighted row, whose keys are then highlighted in turn, and a second press selects
+/// the highlighted key and goes back to the first row. A row left alone for `key_loops`
+/// passes hands back to row scanning at the same row.
+/// 行・列のスイッチスキャン。行を順に強調表示し、スイッチを押すとその行に入って
+/// キーを順に強調表示する。もう一度押すと強調表示中のキーを選択し、先頭の行に戻る。
+/// `key_loops` 周の間選択がなければ、同じ行から行のスキャンに戻る。
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct SwitchScanner {
+    focus: Option<ScanFocus>,
+    next_step_at: f64,
+    /// Switch state on the previous update, so only a fresh press counts
+    /// 前回の更新時のスイッチの状態。新たな押下のみを数える
+    pressed: bool,
+}
+
+impl SwitchScanner {
+    /// `rows` holds key indices per row, as from `KeyLayout::rows`. The first update
+    /// starts the scan, and a switch already held then does not select anything.
+    /// `rows` は行ごとのキーのインデックス (`KeyLayout::rows` の形)。最初の更新でスキャンを
+    /// 始め、その時点で押されているスイッチでは何も選択しない。
+    pub(super) fn update(
+        &mut self,
+        params: &ScanParams,
+        rows: &[Vec<usize>],
+        pressed: bool,
+        seconds: f64,
+    ) -> Vec<ScanEvent> {
+        let press = pressed && !self.pressed;
+        self.pressed = pressed;
+        let focus = match self.focus {
+            Some(focus) if Self::in_layout(focus, rows) => focus,
+            _ if rows.is_empty() => {
+                self.focus = None;
+                return Vec::new();
+            }
+            _ => return vec![self.enter(params, rows, ScanFocus::Row(0), seconds)],
+        };
+
+        if press {
+            return match focus {
+                ScanFocus::Row(row) => {
+                    let focus = ScanFocus::Key {
+                        row,
+                        position: 0,
+                        loops: 0,
+                    };
+                    vec![self.enter(params, rows, focus, seconds)]
+                }
+                ScanFocus::Key { row, position, .. } => vec![
+                    ScanEvent::Select {
+                        key: rows[row][position],
+                    },
+                    self.enter(params, rows, ScanFocus::Row(0), seconds),
+                ],
+            };
+        }
+
+        if seconds < self.next_step_at {
+            return Vec::new();
+        }
+        let next = match focus {
+            ScanFocus::Row(row) => ScanFocus::Row((row + 1) % rows.len()),
+            ScanFocus::Key {
+                row,
+                position,
+                loops,
+            } if position + 1 < rows[row].len() => ScanFocus::Key {
+                row,
+                position: position + 1,
+                loops,
+            },
+            ScanFocus::Key { row, loops, .. } if loops + 1 < params.key_loops => ScanFocus::Key {
+                row,
+                position: 0,
+                loops: loops + 1,
+            },
+            ScanFocus::Key { row, .. } => ScanFocus::Row(row),
+        };
+        self.focus = Some(next);
+        // Keep the cadence, but do not replay steps missed while updates were late
+        // 刻みは保つが、更新が遅れた間に逃したステップは再生しない
+        self.next_step_at += params.step_seconds;
+        if self.next_step_at <= seconds {
+            self.next_step_at = seconds + params.step_seconds;
+        }
+        vec![Self::highlight(next, rows)]
+    }
+
+    fn enter(
+        &mut self,
+        params: &ScanParams,
+        rows: &[Vec<usize>],
+        focus: ScanFocus,
+        seconds: f64,
+    ) -> ScanEvent {
+        self.focus = Some(focus);
+        self.next_step_at = seconds + params.first_step_seconds;
+        Self::highlight(focus, rows)
+    }
+
+    fn highlight(focus: ScanFocus, rows: &[Vec<usize>]) -> ScanEvent {
+        match focus {
+            ScanFocus::Row(row) => ScanEvent::Highlight { row, key: None },
+            ScanFocus::Key { row, position, .. } => ScanEvent::Highlight {
+                row,
+                key: Some(rows[row][position]),
+            },
+        }
+    }
+
+    fn in_layout(focus: ScanFocus, rows: &[Vec<usize>]) -> bool {
+        match focus {
+            ScanFocus::Row(row) => row < rows.len(),
+            ScanFocus::Key { row, position, .. } => {
+                rows.get(row).is_some_and(|keys| position < keys.len())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::key_layout::test_layout;
+    use super::*;
+
+    const PARAMS: ScanParams = ScanParams {
+        step_seconds: 1.0,
+        first_step_seconds: 2.0,
+        key_loops: 2,
+    };
+
+    /// Feed (pressed, seconds) pairs over the test layout and collect every event
+    /// テスト用レイアウトに (押下, 秒) の組を入力し、すべてのイベントを集める
+    fn events(reads: &[(bool, f64)]) -> Vec<(f64, ScanEvent)> {
+        let rows = test_layout().rows();
+        let mut scanner = SwitchScanner::default();
+        reads
+            .iter()
+            .flat_map(|&(pressed, seconds)| {
+                scanner
+                    .update(&PARAMS, &rows, pressed, seconds)
+                    .into_iter()
+                    .map(move |event| (seconds, event))
+            })
+            .collect()
+    }
+
+    fn row(row: usize) -> ScanEvent {
+        ScanEvent::Highlight { row, key: None }
+    }
+
+    fn key(row: usize, key: usize) -> ScanEvent {
+        ScanEvent::Highlight {
+            row,
+            key: Some(key),
+        }
+    }
+
+    #[test]
+    fn rows_cycle_with_a_longer_first_step() {
+        let reads: Vec<_> = (0..=5).map(|s| (false, s as f64)).collect();
+        assert_eq!(
+            events(&reads),
+            [
+                (0.0, row(0)),
+                (2.0, row(1)),
+                (3.0, row(0)),
+                (4.0, row(1)),
+                (5.0, row(0))
+            ]
+        );
+    }
+
+    #[test]
+    fn two_presses_select_a_key_and_return_to_the_first_row() {
+        let reads = [
+            (false, 0.0),
+            (false, 2.0),
+            (true, 2.5), // enter row 1 / 行1に入る
+            (false, 3.0),
+            (false, 4.5), // "s"
+            (true, 4.8),  // select / 選択
+        ];
+        assert_eq!(
+            events(&reads),
+            [
+                (0.0, row(0)),
+                (2.0, row(1)),
+                (2.5, key(1, 3)),
+                (4.5, key(1, 4)),
+                (4.8, ScanEvent::Select { key: 4 }),
+                (4.8, row(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn unused_row_falls_back_to_row_scanning() {
+        let mut reads = vec![(true, 0.0), (false, 0.5), (true, 1.0), (false, 1.5)];
+        reads.extend((3..=8).map(|s| (false, s as f64)));
+        let events = events(&reads);
+        // Held at start: no press. The press at 1.0 enters row 0, then two passes of
+        // three keys run before row 0 is scanned again
+        // 開始時に押されていたものは押下にならない。1.0 の押下で行0に入り、3キーを2周してから
+        // 行0のスキャンに戻る
+        assert_eq!(events[1], (1.0, key(0, 0)));
+        assert_eq!(events.last(), Some(&(8.0, row(0))));
+        assert!(!events
+            .iter()
+            .any(|(_, event)| matches!(event, ScanEvent::Select { .. })));
+    }
+
+    #[test]
+    fn late_updates_do_not_replay_missed_steps() {
+        let reads = [(false, 0.0), (false, 10.0), (false, 10.5), (false, 11.0)];
+        assert_eq!(
+            events(&reads),
+            [(0.0, row(0)), (10.0, row(1)), (11.0, row(0))]
+        );
+    }
+}
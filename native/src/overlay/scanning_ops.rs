@@ -0,0 +1,135 @@
+use napi_derive::napi;
+
+use super::errors::timestamp_seconds;
+use super::manager::OverlayManager;
+use super::scanning::{ScanConfig, ScanEvent, ScanParams, SwitchScanner};
+use super::toggle::ToggleHand;
+use super::types::{SwitchScanConfig, SwitchScanEvent, SwitchScanEventType};
+
+impl ScanConfig {
+    fn from_napi(config: SwitchScanConfig) -> napi::Result<Self> {
+        let config = Self {
+            action: config.action.unwrap_or_else(|| Self::new().action),
+            params: ScanParams {
+                step_seconds: config.stepMs / 1000.0,
+                first_step_seconds: config.firstStepMs / 1000.0,
+                key_loops: config.keyLoops,
+            },
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn to_napi(&self) -> SwitchScanConfig {
+        SwitchScanConfig {
+            action: Some(self.action.clone()),
+            stepMs: self.params.step_seconds * 1000.0,
+            firstStepMs: self.params.first_step_seconds * 1000.0,
+            keyLoops: self.params.key_loops,
+        }
+    }
+}
+
+impl OverlayManager {
+    fn advance_switch_scan(
+        &self,
+        pressed: bool,
+        seconds: f64,
+    ) -> napi::Result<Vec<SwitchScanEvent>> {
+        let params = self.borrow_scan_config()?.params;
+        let layout = self.borrow_key_layout()?;
+        let rows = layout.rows();
+        let mut scanner = self.switch_scanner();
+        let events = scanner.update(&params, &rows, pressed, seconds);
+        self.set_switch_scanner(scanner);
+
+        let ids = |row: usize| -> Vec<String> {
+            rows[row]
+                .iter()
+                .map(|&key| layout.key(key).id.clone())
+                .collect()
+        };
+        Ok(events
+            .into_iter()
+            .map(|event| {
+                let (event_type, row, key) = match event {
+                    ScanEvent::Highlight { row, key } => (SwitchScanEventType::Highlight, row, key),
+                    ScanEvent::Select { key } => {
+                        let row = rows.iter().position(|keys| keys.contains(&key));
+                        (
+                            SwitchScanEventType::Select,
+                            row.unwrap_or_default(),
+                            Some(key),
+                        )
+                    }
+                };
+                SwitchScanEvent {
+                    eventType: event_type,
+                    // The layout's own row number, not the position in the scan
+                    // スキャン内の位置ではなく、レイアウト上の行番号
+                    row: layout.key(rows[row][0]).row,
+                    keys: ids(row),
+                    key: key.map(|key| layout.key(key).id.clone()),
+                }
+            })
+            .collect())
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Step switch-access scanning over the keys set by `setKeyLayout`, reading the
+    /// configured action as the switch. Call once per frame; the first call starts the
+    /// scan at the first row. Returns the highlight and select events of this update.
+    /// `setKeyLayout` で設定したキーに対するスイッチスキャンを進め、設定したアクションを
+    /// スイッチとして読む。毎フレーム1回呼ぶ。最初の呼び出しで先頭の行からスキャンを始める。
+    /// この更新での強調表示と選択のイベントを返す。
+    #[napi]
+    pub fn update_switch_scan(
+        &self,
+        timestamp_ms: Option<f64>,
+    ) -> napi::Result<Vec<SwitchScanEvent>> {
+        let pressed = {
+            let cache = self.borrow_input_cache()?;
+            if !cache.initialized {
+                return Err(napi::Error::from_reason("SteamVR input is not initialized"));
+            }
+            self.update_action_state(&cache)?;
+            let config = self.borrow_scan_config()?;
+            self.read_held_button(&cache, &config.action, ToggleHand::Any)?
+        };
+        let seconds = self.input_seconds(timestamp_ms)?;
+        self.advance_switch_scan(pressed, seconds)
+    }
+
+    /// Feed a recorded switch state instead of reading the action
+    /// アクションを読む代わりに記録済みのスイッチの状態を入力する
+    #[napi]
+    pub fn feed_switch_scan(
+        &self,
+        pressed: bool,
+        timestamp_ms: f64,
+    ) -> napi::Result<Vec<SwitchScanEvent>> {
+        let seconds = timestamp_seconds(timestamp_ms)?;
+        self.advance_switch_scan(pressed, seconds)
+    }
+
+    /// Stop scanning; the next update starts again from the first row
+    /// スキャンを止める。次の更新で先頭の行から再開する
+    #[napi]
+    pub fn reset_switch_scan(&self) {
+        self.set_switch_scanner(SwitchScanner::default());
+    }
+
+    #[napi]
+    pub fn set_switch_scan_config(&self, config: SwitchScanConfig) -> napi::Result<()> {
+        *self.borrow_scan_config_mut()? = ScanConfig::from_napi(config)?;
+        self.set_switch_scanner(SwitchScanner::default());
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_switch_scan_config(&self) -> napi::Result<SwitchScanConfig> {
+        Ok(self.borrow_scan_config()?.to_napi())
+    }
+}
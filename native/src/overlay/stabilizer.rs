@@ -0,0 +1,172 @@
+use std::f64::consts::PI;
+
+/// 1€ filter parameters / 1€ フィルターのパラメータ
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct StabilizerParams {
+    pub enabled: bool,
+    pub min_cutoff: f64, // Min cutoff frequency (Hz) / 最小カットオフ周波数 (Hz)
+    pub beta: f64,       // Speed coefficient / 速度係数
+    pub dcutoff: f64,    // Derivative cutoff (Hz) / 微分カットオフ (Hz)
+}
+
+impl StabilizerParams {
+    /// Same tuning the JS input loop used: strong smoothing at rest, quick at speed
+    /// JS の入力ループと同じ調整値: 静止時は強く平滑化し、高速時は素早く追従する
+    pub(super) const DEFAULT: Self = Self {
+        enabled: true,
+        min_cutoff: 0.1,
+        beta: 5.0,
+        dcutoff: 1.0,
+    };
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+        if !positive(self.min_cutoff) || !positive(self.dcutoff) {
+            return Err(napi::Error::from_reason(
+                "minCutoff and dcutoff must be positive numbers",
+            ));
+        }
+        if !self.beta.is_finite() || self.beta < 0.0 {
+            return Err(napi::Error::from_reason(
+                "beta must be a non-negative number",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct FilterState {
+    x: f64,
+    y: f64,
+    dx: f64,
+    dy: f64,
+    last_seconds: f64,
+}
+
+/// Per-controller 1€ filter over overlay UV coordinates, a port of the JS `PointerStabilizer`
+/// オーバーレイ UV 座標に対するコントローラーごとの 1€ フィルター (JS の `PointerStabilizer` の移植)
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct PointerStabilizer {
+    state: Option<FilterState>,
+}
+
+fn alpha(cutoff: f64, dt: f64) -> f64 {
+    let tau = 1.0 / (2.0 * PI * cutoff);
+    1.0 / (1.0 + tau / dt)
+}
+
+impl PointerStabilizer {
+    /// Feed a raw sample taken at `seconds` and return the smoothed position
+    /// `seconds` 時点の生サンプルを入力し、平滑化した位置を返す
+    pub(super) fn update(
+        &mut self,
+        params: &StabilizerParams,
+        x: f64,
+        y: f64,
+        seconds: f64,
+    ) -> (f64, f64) {
+        if !params.enabled {
+            self.state = None;
+            return (x, y);
+        }
+        let Some(state) = self.state.as_mut() else {
+            self.state = Some(FilterState {
+                x,
+                y,
+                dx: 0.0,
+                dy: 0.0,
+                last_seconds: seconds,
+            });
+            return (x, y);
+        };
+
+        let dt = seconds - state.last_seconds;
+        state.last_seconds = seconds;
+        if dt <= 0.0 {
+            return (state.x, state.y);
+        }
+
+        // Smooth velocity / 速度を平滑化する
+        let lambda_d = alpha(params.dcutoff, dt);
+        let dx = state.dx + lambda_d * ((x - state.x) / dt - state.dx);
+        let dy = state.dy + lambda_d * ((y - state.y) / dt - state.dy);
+
+        // Dynamic cutoff based on speed / 速度に基づく動的なカットオフ
+        let cutoff = params.min_cutoff + params.beta * dx.hypot(dy);
+        let lambda = alpha(cutoff, dt);
+        state.x += lambda * (x - state.x);
+        state.y += lambda * (y - state.y);
+        state.dx = dx;
+        state.dy = dy;
+        (state.x, state.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (u, v, timestamp ms) fed to the JS `PointerStabilizer(0.1, 5.0, 1.0)` in
+    /// electron/input/smoothing.js, and the (u, v) it returned. Includes a repeated and
+    /// a backwards timestamp (dt <= 0).
+    /// electron/input/smoothing.js の JS `PointerStabilizer(0.1, 5.0, 1.0)` に入力した
+    /// (u, v, タイムスタンプ ms) と、その出力 (u, v)。同じタイムスタンプと逆行する
+    /// タイムスタンプ (dt <= 0) を含む。
+    const JS_TRACE: [(f64, f64, f64, f64, f64); 9] = [
+        (0.5, 0.5, 1000.0, 0.5, 0.5),
+        (0.52, 0.49, 1008.0, 0.5007441826916522, 0.4996279086541739),
+        (0.55, 0.47, 1016.0, 0.5061543748618931, 0.49637361935027363),
+        (0.55, 0.47, 1016.0, 0.5061543748618931, 0.49637361935027363),
+        (0.6, 0.45, 1012.0, 0.5061543748618931, 0.49637361935027363),
+        (0.61, 0.44, 1030.0, 0.546201246912308, 0.4746337808338529),
+        (0.3, 0.8, 1040.0, 0.465519427287874, 0.5812584991724943),
+        (0.31, 0.79, 1140.0, 0.33184557844018514, 0.7606783974865833),
+        (0.31, 0.79, 1148.0, 0.324363554866311, 0.7707209295179435),
+    ];
+
+    fn assert_close(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-12 && (actual.1 - expected.1).abs() < 1e-12,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn matches_the_js_filter_on_a_recorded_trace() {
+        let mut stabilizer = PointerStabilizer::default();
+        for (u, v, ms, expected_u, expected_v) in JS_TRACE {
+            let smoothed = stabilizer.update(&StabilizerParams::DEFAULT, u, v, ms / 1000.0);
+            assert_close(smoothed, (expected_u, expected_v));
+        }
+    }
+
+    #[test]
+    fn disabled_filter_passes_samples_through_and_forgets_history() {
+        let disabled = StabilizerParams {
+            enabled: false,
+            ..StabilizerParams::DEFAULT
+        };
+        let mut stabilizer = PointerStabilizer::default();
+        stabilizer.update(&StabilizerParams::DEFAULT, 0.5, 0.5, 1.0);
+        assert_eq!(stabilizer.update(&disabled, 0.75, 0.25, 1.5), (0.75, 0.25));
+        // Re-enabled, the next sample starts a fresh filter / 再び有効にすると次のサンプルから新しいフィルター
+        assert_eq!(
+            stabilizer.update(&StabilizerParams::DEFAULT, 0.25, 0.75, 2.0),
+            (0.25, 0.75)
+        );
+    }
+
+    #[test]
+    fn reset_filter_restarts_from_the_next_sample_like_js_reset() {
+        let mut stabilizer = PointerStabilizer::default();
+        for (u, v, ms, ..) in JS_TRACE {
+            stabilizer.update(&StabilizerParams::DEFAULT, u, v, ms / 1000.0);
+        }
+        stabilizer = PointerStabilizer::default();
+        assert_eq!(
+            stabilizer.update(&StabilizerParams::DEFAULT, 0.9, 0.1, 1.2),
+            (0.9, 0.1)
+        );
+    }
+}
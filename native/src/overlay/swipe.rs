@@ -0,0 +1,391 @@
+use std::collections::{HashMap, HashSet};
+
+use super::key_layout::KeyLayout;
+
+/// Swipe decoding thresholds; distances in overlay UV / スワイプ解読のしきい値 (距離はオーバーレイ UV)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct SwipeParams {
+    /// Points both the path and each word template are resampled to
+    /// 軌跡と各単語のテンプレートを再サンプリングする点数
+    pub samples: usize,
+    /// Shorter paths are taps, not swipes / これより短い軌跡はスワイプではなくタップ
+    pub min_length: f64,
+    /// A word is only considered when its first and last keys are this close to where
+    /// the path starts and ends / 最初と最後のキーが軌跡の始点・終点にこの距離以内の単語だけを候補にする
+    pub endpoint_radius: f64,
+    /// Spread of the shape channel (scale-free path shape) / 形状チャンネル (大きさに依らない軌跡の形) の広がり
+    pub shape_sigma: f64,
+    /// Spread of the location channel (where on the keyboard) / 位置チャンネル (キーボード上の位置) の広がり
+    pub location_sigma: f64,
+    pub max_candidates: usize,
+}
+
+impl SwipeParams {
+    pub(super) const DEFAULT: Self = Self {
+        samples: 32,
+        min_length: 0.05,
+        endpoint_radius: 0.12,
+        shape_sigma: 0.1,
+        location_sigma: 0.05,
+        max_candidates: 5,
+    };
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        if self.samples < 2 {
+            return Err(napi::Error::from_reason("samples must be at least 2"));
+        }
+        if self.max_candidates == 0 {
+            return Err(napi::Error::from_reason("maxCandidates must be at least 1"));
+        }
+        if !(self.min_length.is_finite() && self.min_length >= 0.0) {
+            return Err(napi::Error::from_reason(
+                "minLength must be a non-negative number",
+            ));
+        }
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+        if ![self.endpoint_radius, self.shape_sigma, self.location_sigma]
+            .into_iter()
+            .all(positive)
+        {
+            return Err(napi::Error::from_reason(
+                "endpointRadius, shapeSigma and locationSigma must be positive numbers",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct WordEntry {
+    word: String,
+    frequency: f64,
+}
+
+/// Local word list for the swipe decoder. One word per line, optionally followed by a
+/// frequency count; blank lines and `#` comments are skipped and words are lowercased.
+/// Any script works as long as each character is the id of a key in the layout, so a
+/// kana list decodes over a kana layout.
+/// スワイプ解読用のローカル単語リスト。1行に1語で、後ろに頻度を付けてもよい。空行と `#` の
+/// コメントは読み飛ばし、単語は小文字にする。各文字がレイアウト内のキー ID であれば文字種は
+/// 問わないため、かなのリストはかなのレイアウト上で解読できる。
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct Lexicon {
+    entries: Vec<WordEntry>,
+}
+
+impl Lexicon {
+    pub(super) fn parse(text: &str) -> Result<Self, String> {
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let word = fields.next().unwrap_or_default().to_lowercase();
+            let frequency = match fields.next() {
+                None => 1.0,
+                Some(count) => match count.parse::<f64>() {
+                    Ok(count) if count.is_finite() && count > 0.0 => count,
+                    _ => {
+                        return Err(format!(
+                            "line {}: frequency must be a positive number",
+                            number + 1
+                        ))
+                    }
+                },
+            };
+            if seen.insert(word.clone()) {
+                entries.push(WordEntry { word, frequency });
+            }
+        }
+        Ok(Self { entries })
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// A decoded word; scores of one decode sum to 1 / 解読した単語。1回の解読のスコアの合計は 1
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct WordCandidate {
+    pub word: String,
+    pub score: f64,
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+fn path_length(points: &[(f64, f64)]) -> f64 {
+    points
+        .windows(2)
+        .map(|pair| distance(pair[0], pair[1]))
+        .sum()
+}
+
+/// `count` points evenly spaced along a polyline / 折れ線に沿って等間隔に並ぶ `count` 個の点
+fn resample(points: &[(f64, f64)], count: usize) -> Vec<(f64, f64)> {
+    let total = path_length(points);
+    if total <= f64::EPSILON {
+        return vec![points[0]; count];
+    }
+    let step = total / (count - 1) as f64;
+    let mut result = Vec::with_capacity(count);
+    result.push(points[0]);
+    let mut walked = 0.0;
+    let mut segment = 0;
+    for i in 1..count - 1 {
+        let target = step * i as f64;
+        while segment + 1 < points.len() - 1
+            && walked + distance(points[segment], points[segment + 1]) < target
+        {
+            walked += distance(points[segment], points[segment + 1]);
+            segment += 1;
+        }
+        let (a, b) = (points[segment], points[segment + 1]);
+        let length = distance(a, b);
+        let t = if length > 0.0 {
+            ((target - walked) / length).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        result.push((a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t));
+    }
+    result.push(points[points.len() - 1]);
+    result
+}
+
+/// Centered on the centroid and scaled so the larger bounding box side is 1
+/// 重心を原点にし、外接矩形の長い辺が 1 になるように拡縮する
+fn normalize(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let count = points.len() as f64;
+    let cu = points.iter().map(|p| p.0).sum::<f64>() / count;
+    let cv = points.iter().map(|p| p.1).sum::<f64>() / count;
+    let extent = |axis: fn(&(f64, f64)) -> f64| {
+        let values = points.iter().map(axis);
+        values.clone().fold(f64::MIN, f64::max) - values.fold(f64::MAX, f64::min)
+    };
+    let scale = extent(|p| p.0).max(extent(|p| p.1));
+    let scale = if scale > f64::EPSILON { scale } else { 1.0 };
+    points
+        .iter()
+        .map(|p| ((p.0 - cu) / scale, (p.1 - cv) / scale))
+        .collect()
+}
+
+fn mean_distance(a: &[(f64, f64)], b: &[(f64, f64)]) -> f64 {
+    a.iter().zip(b).map(|(&a, &b)| distance(a, b)).sum::<f64>() / a.len() as f64
+}
+
+/// Decode a swipe path by template matching. Each word's template is the polyline through
+/// the centers of its keys (repeated letters collapse). After pruning by start and end
+/// key, a word is scored by a shape channel (mean distance between the normalized path
+/// and template) and a location channel (mean distance in UV), each a Gaussian, weighted
+/// by the word's frequency.
+/// テンプレート照合でスワイプの軌跡を解読する。各単語のテンプレートはキーの中心を結ぶ
+/// 折れ線 (連続する同じ文字はまとめる)。始点と終点のキーで絞り込んだ後、形状チャンネル
+/// (正規化した軌跡とテンプレートの平均距離) と位置チャンネル (UV での平均距離) をそれぞれ
+/// ガウス分布で評価し、単語の頻度で重み付けする。
+pub(super) fn decode(
+    params: &SwipeParams,
+    layout: &KeyLayout,
+    lexicon: &Lexicon,
+    path: &[(f64, f64)],
+) -> Vec<WordCandidate> {
+    if path.len() < 2 || path_length(path) < params.min_length {
+        return Vec::new();
+    }
+    let char_keys: HashMap<char, (f64, f64)> = layout
+        .keys()
+        .iter()
+        .filter_map(|key| {
+            let mut chars = key.id.chars().flat_map(char::to_lowercase);
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some((c, key.center())),
+                _ => None,
+            }
+        })
+        .collect();
+    let sampled = resample(path, params.samples);
+    let shape = normalize(&sampled);
+    let (start, end) = (path[0], path[path.len() - 1]);
+
+    let mut scored: Vec<(f64, &str)> = Vec::new();
+    for entry in &lexicon.entries {
+        let mut centers: Vec<(f64, f64)> = Vec::new();
+        for c in entry.word.chars() {
+            let Some(&center) = char_keys.get(&c) else {
+                centers.clear();
+                break;
+            };
+            if centers.last() != Some(&center) {
+                centers.push(center);
+            }
+        }
+        // Single-key words are taps / キー1つの単語はタップで入力する
+        if centers.len() < 2
+            || distance(centers[0], start) > params.endpoint_radius
+            || distance(centers[centers.len() - 1], end) > params.endpoint_radius
+        {
+            continue;
+        }
+        let template = resample(&centers, params.samples);
+        let location = mean_distance(&sampled, &template) / params.location_sigma;
+        let form = mean_distance(&shape, &normalize(&template)) / params.shape_sigma;
+        let log_likelihood = -0.5 * (location * location + form * form) + entry.frequency.ln();
+        scored.push((log_likelihood, &entry.word));
+    }
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.truncate(params.max_candidates);
+
+    let best = scored.first().map(|&(score, _)| score).unwrap_or_default();
+    let total: f64 = scored.iter().map(|&(score, _)| (score - best).exp()).sum();
+    scored
+        .into_iter()
+        .map(|(score, word)| WordCandidate {
+            word: word.to_string(),
+            score: (score - best).exp() / total,
+        })
+        .collect()
+}
+
+/// Longest path kept for one swipe / 1回のスワイプで保持する軌跡の最大点数
+const MAX_PATH_POINTS: usize = 4096;
+
+/// Records the pointer path while the trigger is held over the keyboard. Like the trigger
+/// gesture recognizer, a press that starts off the overlay begins recording when the
+/// pointer reaches it; samples off the overlay mid-swipe are skipped.
+/// トリガーを押している間のポインターの軌跡をキーボード上で記録する。トリガージェスチャー
+/// 認識器と同様に、オーバーレイ外で始まった押下はポインターがオーバーレイに入った時点で
+/// 記録を始める。スワイプ中にオーバーレイ外にあるサンプルは読み飛ばす。
+#[derive(Clone, Debug, Default)]
+pub(super) struct SwipeRecorder {
+    path: Option<Vec<(f64, f64)>>,
+}
+
+impl SwipeRecorder {
+    /// Returns the finished path on release / 離したときに完成した軌跡を返す
+    pub(super) fn update(
+        &mut self,
+        pressed: bool,
+        hit: Option<(f64, f64)>,
+    ) -> Option<Vec<(f64, f64)>> {
+        if !pressed {
+            return self.path.take();
+        }
+        let point = hit?;
+        let path = self.path.get_or_insert_with(Vec::new);
+        let moved = path.last().is_none_or(|&last| distance(last, point) > 1e-4);
+        if moved && path.len() < MAX_PATH_POINTS {
+            path.push(point);
+        }
+        None
+    }
+
+    pub(super) fn point_count(&self) -> Option<usize> {
+        self.path.as_ref().map(Vec::len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::key_layout::{key, test_layout};
+    use super::*;
+
+    const S: (f64, f64) = (0.5, 0.75);
+    const A: (f64, f64) = (1.0 / 6.0, 0.75);
+    const D: (f64, f64) = (5.0 / 6.0, 0.75);
+    const Q: (f64, f64) = (1.0 / 6.0, 0.25);
+    const E: (f64, f64) = (5.0 / 6.0, 0.25);
+
+    fn words(candidates: &[WordCandidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.word.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_words_and_frequencies() {
+        let lexicon = Lexicon::parse("# comment\nSad 20\n\nwed\nsad 3\n").unwrap();
+        assert_eq!(
+            lexicon.entries,
+            [
+                WordEntry {
+                    word: "sad".into(),
+                    frequency: 20.0
+                },
+                WordEntry {
+                    word: "wed".into(),
+                    frequency: 1.0
+                },
+            ]
+        );
+        assert!(Lexicon::parse("sad -1").is_err());
+    }
+
+    #[test]
+    fn ranks_the_word_whose_keys_the_path_passes() {
+        let lexicon = Lexicon::parse("dew\nads\nsad\nsd\nwed\nxyz").unwrap();
+        let candidates = decode(&SwipeParams::DEFAULT, &test_layout(), &lexicon, &[S, A, D]);
+        // "dew", "ads" and "wed" start or end elsewhere; "xyz" has no keys
+        // "dew"・"ads"・"wed" は始点か終点が異なり、"xyz" にはキーがない
+        assert_eq!(words(&candidates), ["sad", "sd"]);
+        assert!(candidates[0].score > 0.99);
+    }
+
+    #[test]
+    fn frequency_breaks_ties_between_identical_shapes() {
+        // q-w-e and q-e trace the same straight line / q-w-e と q-e は同じ直線になる
+        let lexicon = Lexicon::parse("qe 1\nqwe 10").unwrap();
+        let candidates = decode(&SwipeParams::DEFAULT, &test_layout(), &lexicon, &[Q, E]);
+        assert_eq!(words(&candidates), ["qwe", "qe"]);
+        assert!((candidates[0].score - 10.0 / 11.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn decodes_kana_words_over_a_kana_layout() {
+        // "あ い う" / "か き く" in the same grid as the test layout
+        // テスト用レイアウトと同じグリッドに "あ い う" / "か き く" を並べる
+        let third = 1.0 / 3.0;
+        let mut keys = Vec::new();
+        for (row, ids) in [["あ", "い", "う"], ["か", "き", "く"]].iter().enumerate() {
+            for (column, id) in ids.iter().enumerate() {
+                keys.push(key(
+                    id,
+                    row as u32,
+                    column as f64 * third,
+                    row as f64 * 0.5,
+                    third,
+                    0.5,
+                ));
+            }
+        }
+        let layout = KeyLayout::new(keys).unwrap();
+        let lexicon = Lexicon::parse("かく 5\nかいく 2\nあき\nくか").unwrap();
+        // か -> き -> く along the bottom row / 下の行を か -> き -> く となぞる
+        let candidates = decode(&SwipeParams::DEFAULT, &layout, &lexicon, &[A, S, D]);
+        assert_eq!(words(&candidates), ["かく", "かいく"]);
+    }
+
+    #[test]
+    fn recorder_yields_the_path_on_release_and_taps_do_not_decode() {
+        let mut recorder = SwipeRecorder::default();
+        assert_eq!(recorder.update(true, None), None);
+        assert_eq!(
+            recorder.point_count(),
+            None,
+            "nothing recorded off the overlay"
+        );
+        recorder.update(true, Some(S));
+        recorder.update(true, None);
+        recorder.update(true, Some(A));
+        let path = recorder.update(false, None).unwrap();
+        assert_eq!(path, [S, A]);
+        assert_eq!(recorder.point_count(), None);
+
+        let lexicon = Lexicon::parse("sa").unwrap();
+        let tap = [S, (S.0 + 0.01, S.1)];
+        assert!(decode(&SwipeParams::DEFAULT, &test_layout(), &lexicon, &tap).is_empty());
+    }
+}
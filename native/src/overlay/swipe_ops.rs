@@ -0,0 +1,132 @@
+use napi_derive::napi;
+
+use super::key_layout::layout_point;
+use super::manager::OverlayManager;
+use super::swipe::{decode, Lexicon, SwipeParams, SwipeRecorder, WordCandidate};
+use super::types::{OverlayPoint, SwipeCandidate, SwipeTypingParams, SwipeTypingState};
+
+impl SwipeParams {
+    fn from_napi(params: &SwipeTypingParams) -> napi::Result<Self> {
+        let params = Self {
+            samples: params.samples as usize,
+            min_length: params.minLength,
+            endpoint_radius: params.endpointRadius,
+            shape_sigma: params.shapeSigma,
+            location_sigma: params.locationSigma,
+            max_candidates: params.maxCandidates as usize,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn to_napi(self) -> SwipeTypingParams {
+        SwipeTypingParams {
+            samples: self.samples as u32,
+            minLength: self.min_length,
+            endpointRadius: self.endpoint_radius,
+            shapeSigma: self.shape_sigma,
+            locationSigma: self.location_sigma,
+            maxCandidates: self.max_candidates as u32,
+        }
+    }
+}
+
+impl WordCandidate {
+    fn into_napi(self) -> SwipeCandidate {
+        SwipeCandidate {
+            word: self.word,
+            score: self.score,
+        }
+    }
+}
+
+impl OverlayManager {
+    fn decode_swipe(&self, path: &[(f64, f64)]) -> napi::Result<Vec<SwipeCandidate>> {
+        let layout = self.borrow_key_layout()?;
+        let lexicon = self.borrow_swipe_lexicon()?;
+        Ok(decode(&self.swipe_params(), &layout, &lexicon, path)
+            .into_iter()
+            .map(WordCandidate::into_napi)
+            .collect())
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Load the local word list for swipe typing, replacing the current one. Returns the
+    /// number of words; a parse error leaves the current list in place.
+    /// スワイプ入力用のローカル単語リストを読み込み、現在のリストを置き換える。単語数を返す。
+    /// 解析エラー時は現在のリストをそのまま保つ。
+    #[napi]
+    pub fn load_swipe_word_list(&self, path: String) -> napi::Result<u32> {
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| napi::Error::from_reason(format!("Failed to read {path}: {e}")))?;
+        let lexicon =
+            Lexicon::parse(&text).map_err(|e| napi::Error::from_reason(format!("{path}: {e}")))?;
+        let count = lexicon.len() as u32;
+        *self.borrow_swipe_lexicon_mut()? = lexicon;
+        Ok(count)
+    }
+
+    /// Feed the primary pointer's trigger state and keyboard hit (the intersection UV, null
+    /// when off the overlay) once per frame. The path is recorded in layout space while the
+    /// trigger is held and decoded against the word list and the keys set by `setKeyLayout`
+    /// on release.
+    /// 主ポインターのトリガー状態とキーボードのヒット (交点の UV、オーバーレイ外なら null) を
+    /// フレームごとに入力する。トリガーを押している間の軌跡をレイアウト空間で記録し、離したときに
+    /// 単語リストと `setKeyLayout` で設定したキーに対して解読する。
+    #[napi]
+    pub fn update_swipe_typing(
+        &self,
+        pressed: bool,
+        hit: Option<OverlayPoint>,
+    ) -> napi::Result<SwipeTypingState> {
+        let (finished, count) = {
+            let mut recorder = self.borrow_swipe_recorder_mut()?;
+            let finished = recorder.update(pressed, hit.map(|hit| layout_point(hit.u, hit.v)));
+            (finished, recorder.point_count())
+        };
+        let candidates = match &finished {
+            Some(path) => self.decode_swipe(path)?,
+            None => Vec::new(),
+        };
+        Ok(SwipeTypingState {
+            recording: count.is_some(),
+            pointCount: count
+                .or(finished.map(|path| path.len()))
+                .unwrap_or_default() as u32,
+            candidates,
+        })
+    }
+
+    /// Decode a complete recorded path of intersection UVs / 記録済みの交点の UV の完全な軌跡を解読する
+    #[napi]
+    pub fn decode_swipe_path(
+        &self,
+        points: Vec<OverlayPoint>,
+    ) -> napi::Result<Vec<SwipeCandidate>> {
+        let path: Vec<_> = points
+            .iter()
+            .map(|point| layout_point(point.u, point.v))
+            .collect();
+        self.decode_swipe(&path)
+    }
+
+    /// Drop the swipe in progress without decoding it / 進行中のスワイプを解読せずに破棄する
+    #[napi]
+    pub fn reset_swipe_typing(&self) -> napi::Result<()> {
+        *self.borrow_swipe_recorder_mut()? = SwipeRecorder::default();
+        Ok(())
+    }
+
+    #[napi]
+    pub fn set_swipe_typing_params(&self, params: SwipeTypingParams) -> napi::Result<()> {
+        self.set_swipe_params(SwipeParams::from_napi(&params)?);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_swipe_typing_params(&self) -> SwipeTypingParams {
+        self.swipe_params().to_napi()
+    }
+}
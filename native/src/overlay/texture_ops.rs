@@ -55,6 +55,11 @@ impl OverlayManager {
         if width == 0 || height == 0 {
             return Ok(());
         }
+        // Skipped while the user is away or every owned overlay is hidden
+        // ユーザー不在時や所有中のオーバーレイがすべて非表示の間は省略する
+        if !self.begin_texture_upload()? {
+            return Ok(());
+        }
         let overlay = self.overlay()?;
         let set_raw_fn = require_fn(overlay.SetOverlayRaw, "SetOverlayRaw")?;
         let handle = overlay_handle(handle)?;
@@ -100,6 +105,11 @@ impl OverlayManager {
         if width == 0 || height == 0 {
             return Ok(());
         }
+        // Skipped while the user is away or every owned overlay is hidden
+        // ユーザー不在時や所有中のオーバーレイがすべて非表示の間は省略する
+        if !self.begin_texture_upload()? {
+            return Ok(());
+        }
         let overlay_ptr = self.overlay_ptr()?;
         let set_texture_fn = {
             let overlay = unsafe { overlay_ptr.as_ref() };
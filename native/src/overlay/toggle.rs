@@ -0,0 +1,351 @@
+use super::constants::{TOGGLE_ACTION, TOGGLE_RELEASE_STREAK_TO_UNLOCK};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ButtonEdge {
+    None,
+    Pressed,
+    Released,
+}
+
+/// Boolean state latched while held: it only counts as released after
+/// `TOGGLE_RELEASE_STREAK_TO_UNLOCK` consecutive false reads, so SteamVR briefly dropping
+/// action activity/state during overlay visibility changes does not re-trigger.
+/// 押している間ラッチされるブール状態。`TOGGLE_RELEASE_STREAK_TO_UNLOCK` 回連続で false を
+/// 読むまで解放とみなさないため、オーバーレイの可視性が変わる間に SteamVR がアクションの
+/// アクティビティ/状態を一時的に落としても再発火しない。
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct LatchedButton {
+    held: bool,
+    release_streak: u8,
+}
+
+impl LatchedButton {
+    pub(super) fn update(&mut self, raw: bool) -> ButtonEdge {
+        if raw {
+            self.release_streak = 0;
+            if self.held {
+                return ButtonEdge::None;
+            }
+            self.held = true;
+            return ButtonEdge::Pressed;
+        }
+        if !self.held {
+            return ButtonEdge::None;
+        }
+        self.release_streak = self.release_streak.saturating_add(1);
+        if self.release_streak < TOGGLE_RELEASE_STREAK_TO_UNLOCK {
+            return ButtonEdge::None;
+        }
+        self.held = false;
+        self.release_streak = 0;
+        ButtonEdge::Released
+    }
+
+    pub(super) fn held(&self) -> bool {
+        self.held
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum ToggleMode {
+    Click,
+    LongPress { seconds: f64 },
+    DoublePress { window_seconds: f64 },
+    Chord,
+    HoldToShow,
+}
+
+/// Hand an action is read from; `Any` reads every source
+/// アクションを読む手。`Any` はすべてのソースを読む
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ToggleHand {
+    Any,
+    Left,
+    Right,
+}
+
+/// Which boolean actions drive the toggle and how / どのブールアクションでどのように切り替えるか
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct ToggleConfig {
+    pub mode: ToggleMode,
+    pub action: String,
+    pub hand: ToggleHand,
+    pub secondary_action: Option<String>, // Chord only / Chord のみ
+    pub secondary_hand: ToggleHand,       // Chord only / Chord のみ
+}
+
+impl ToggleConfig {
+    pub(super) fn new() -> Self {
+        Self {
+            mode: ToggleMode::Click,
+            action: TOGGLE_ACTION.to_string(),
+            hand: ToggleHand::Any,
+            secondary_action: None,
+            secondary_hand: ToggleHand::Any,
+        }
+    }
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        let seconds = match self.mode {
+            ToggleMode::LongPress { seconds } => Some(seconds),
+            ToggleMode::DoublePress { window_seconds } => Some(window_seconds),
+            _ => None,
+        };
+        if seconds.is_some_and(|seconds| !seconds.is_finite() || seconds <= 0.0) {
+            return Err(napi::Error::from_reason(
+                "toggle gesture duration must be a positive number",
+            ));
+        }
+        if self.action.is_empty() {
+            return Err(napi::Error::from_reason("toggle action must not be empty"));
+        }
+        // One action may form a chord across both hands, e.g. both grips
+        // 1つのアクションでも両手にまたがればコードにできる (例: 両手のグリップ)
+        let both_hands = matches!(
+            (self.hand, self.secondary_hand),
+            (ToggleHand::Left, ToggleHand::Right) | (ToggleHand::Right, ToggleHand::Left)
+        );
+        match (&self.mode, &self.secondary_action) {
+            (ToggleMode::Chord, None) => Err(napi::Error::from_reason(
+                "chord toggle requires a secondaryAction",
+            )),
+            (ToggleMode::Chord, Some(secondary)) if *secondary == self.action && !both_hands => {
+                Err(napi::Error::from_reason(
+                    "chord actions must be different or read from the left and right hands",
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ToggleOutput {
+    None,
+    Toggle,
+    Show,
+    Hide,
+}
+
+/// Overlay toggle recognizer over latched boolean actions; `secondary` is only read by Chord
+/// ラッチしたブールアクションに対するオーバーレイ切替の認識器。`secondary` は Chord のみが読む
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct ToggleRecognizer {
+    primary: LatchedButton,
+    secondary: LatchedButton,
+    chord_held: bool,
+    pressed_at: Option<f64>,
+    long_press_fired: bool,
+    last_press_at: Option<f64>,
+}
+
+impl ToggleRecognizer {
+    pub(super) fn update(
+        &mut self,
+        mode: ToggleMode,
+        primary: bool,
+        secondary: bool,
+        seconds: f64,
+    ) -> ToggleOutput {
+        let edge = self.primary.update(primary);
+        match mode {
+            ToggleMode::Click => match edge {
+                ButtonEdge::Pressed => ToggleOutput::Toggle,
+                _ => ToggleOutput::None,
+            },
+            ToggleMode::LongPress { seconds: duration } => {
+                match edge {
+                    ButtonEdge::Pressed => {
+                        self.pressed_at = Some(seconds);
+                        self.long_press_fired = false;
+                    }
+                    ButtonEdge::Released => self.pressed_at = None,
+                    ButtonEdge::None => {}
+                }
+                match self.pressed_at {
+                    Some(at) if !self.long_press_fired && seconds - at >= duration => {
+                        self.long_press_fired = true;
+                        ToggleOutput::Toggle
+                    }
+                    _ => ToggleOutput::None,
+                }
+            }
+            ToggleMode::DoublePress { window_seconds } => {
+                if edge != ButtonEdge::Pressed {
+                    return ToggleOutput::None;
+                }
+                match self.last_press_at.take() {
+                    Some(at) if seconds - at <= window_seconds => ToggleOutput::Toggle,
+                    _ => {
+                        self.last_press_at = Some(seconds);
+                        ToggleOutput::None
+                    }
+                }
+            }
+            ToggleMode::Chord => {
+                self.secondary.update(secondary);
+                let chord = self.primary.held() && self.secondary.held();
+                let started = chord && !self.chord_held;
+                self.chord_held = chord;
+                if started {
+                    ToggleOutput::Toggle
+                } else {
+                    ToggleOutput::None
+                }
+            }
+            ToggleMode::HoldToShow => match edge {
+                ButtonEdge::Pressed => ToggleOutput::Show,
+                ButtonEdge::Released => ToggleOutput::Hide,
+                ButtonEdge::None => ToggleOutput::None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(mode: ToggleMode, reads: &[(bool, bool, f64)]) -> Vec<ToggleOutput> {
+        let mut recognizer = ToggleRecognizer::default();
+        reads
+            .iter()
+            .map(|&(primary, secondary, seconds)| {
+                recognizer.update(mode, primary, secondary, seconds)
+            })
+            .filter(|&output| output != ToggleOutput::None)
+            .collect()
+    }
+
+    #[test]
+    fn latch_ignores_brief_dropouts() {
+        let mut button = LatchedButton::default();
+        let edges: Vec<ButtonEdge> = [true, false, false, true, false, false, false]
+            .into_iter()
+            .map(|raw| button.update(raw))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                ButtonEdge::Pressed,
+                ButtonEdge::None,
+                ButtonEdge::None,
+                ButtonEdge::None,
+                ButtonEdge::None,
+                ButtonEdge::None,
+                ButtonEdge::Released,
+            ]
+        );
+    }
+
+    #[test]
+    fn click_toggles_once_per_latched_press() {
+        let reads = [
+            (true, false, 0.0),
+            (false, false, 0.1),
+            (true, false, 0.2),
+            (false, false, 0.3),
+            (false, false, 0.4),
+            (false, false, 0.5),
+            (true, false, 0.6),
+        ];
+        assert_eq!(
+            run(ToggleMode::Click, &reads),
+            vec![ToggleOutput::Toggle, ToggleOutput::Toggle]
+        );
+    }
+
+    #[test]
+    fn long_press_toggles_once_after_duration() {
+        let mode = ToggleMode::LongPress { seconds: 0.5 };
+        let reads = [
+            (true, false, 0.0),
+            (true, false, 0.4),
+            (true, false, 0.5),
+            (true, false, 1.5),
+        ];
+        assert_eq!(run(mode, &reads), vec![ToggleOutput::Toggle]);
+        let short = [
+            (true, false, 0.0),
+            (false, false, 0.1),
+            (false, false, 0.2),
+            (false, false, 0.3),
+            (false, false, 1.0),
+        ];
+        assert!(run(mode, &short).is_empty());
+    }
+
+    #[test]
+    fn double_press_needs_second_press_within_window() {
+        let mode = ToggleMode::DoublePress {
+            window_seconds: 0.4,
+        };
+        let release = |at: f64| [(false, false, at), (false, false, at), (false, false, at)];
+        let mut reads = vec![(true, false, 0.0)];
+        reads.extend(release(0.05));
+        reads.push((true, false, 0.3));
+        assert_eq!(run(mode, &reads), vec![ToggleOutput::Toggle]);
+
+        let mut slow = vec![(true, false, 0.0)];
+        slow.extend(release(0.05));
+        slow.push((true, false, 0.6));
+        assert!(run(mode, &slow).is_empty());
+    }
+
+    #[test]
+    fn chord_toggles_when_both_are_held() {
+        let reads = [
+            (true, false, 0.0),
+            (true, true, 0.1),
+            (true, true, 0.2),
+            (false, true, 0.3),
+            (true, true, 0.4),
+        ];
+        assert_eq!(run(ToggleMode::Chord, &reads), vec![ToggleOutput::Toggle]);
+    }
+
+    #[test]
+    fn chord_accepts_one_action_only_across_both_hands() {
+        let chord = |secondary: &str, hand: ToggleHand, secondary_hand: ToggleHand| ToggleConfig {
+            mode: ToggleMode::Chord,
+            action: "grip".to_string(),
+            hand,
+            secondary_action: Some(secondary.to_string()),
+            secondary_hand,
+        };
+        assert!(chord("grip", ToggleHand::Left, ToggleHand::Right)
+            .validate()
+            .is_ok());
+        assert!(chord("grip", ToggleHand::Right, ToggleHand::Left)
+            .validate()
+            .is_ok());
+        assert!(chord("grip", ToggleHand::Any, ToggleHand::Any)
+            .validate()
+            .is_err());
+        assert!(chord("grip", ToggleHand::Left, ToggleHand::Left)
+            .validate()
+            .is_err());
+        assert!(chord("grip", ToggleHand::Left, ToggleHand::Any)
+            .validate()
+            .is_err());
+        assert!(chord("trigger", ToggleHand::Any, ToggleHand::Any)
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn hold_to_show_follows_latched_state() {
+        let reads = [
+            (true, false, 0.0),
+            (false, false, 0.1),
+            (true, false, 0.2),
+            (false, false, 0.3),
+            (false, false, 0.4),
+            (false, false, 0.5),
+        ];
+        assert_eq!(
+            run(ToggleMode::HoldToShow, &reads),
+            vec![ToggleOutput::Show, ToggleOutput::Hide]
+        );
+    }
+}
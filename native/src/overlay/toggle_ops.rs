@@ -0,0 +1,180 @@
+use napi_derive::napi;
+
+use super::manager::{InputActionCache, OverlayManager};
+use super::toggle::{ToggleConfig, ToggleHand, ToggleMode, ToggleOutput};
+use super::types::{
+    InputActionType, InputHand, ToggleGestureConfig, ToggleGestureEvent, ToggleGestureMode,
+};
+
+impl ToggleHand {
+    fn from_napi(hand: Option<InputHand>) -> Self {
+        match hand {
+            None | Some(InputHand::Any) => Self::Any,
+            Some(InputHand::Left) => Self::Left,
+            Some(InputHand::Right) => Self::Right,
+        }
+    }
+
+    fn to_napi(self) -> InputHand {
+        match self {
+            Self::Any => InputHand::Any,
+            Self::Left => InputHand::Left,
+            Self::Right => InputHand::Right,
+        }
+    }
+}
+
+impl ToggleConfig {
+    fn from_napi(config: ToggleGestureConfig) -> napi::Result<Self> {
+        let seconds = |ms: Option<f64>, name: &str| {
+            ms.map(|ms| ms / 1000.0).ok_or_else(|| {
+                napi::Error::from_reason(format!("{name} is required for this toggle mode"))
+            })
+        };
+        let mode = match config.mode {
+            ToggleGestureMode::Click => ToggleMode::Click,
+            ToggleGestureMode::LongPress => ToggleMode::LongPress {
+                seconds: seconds(config.durationMs, "durationMs")?,
+            },
+            ToggleGestureMode::DoublePress => ToggleMode::DoublePress {
+                window_seconds: seconds(config.windowMs, "windowMs")?,
+            },
+            ToggleGestureMode::Chord => ToggleMode::Chord,
+            ToggleGestureMode::HoldToShow => ToggleMode::HoldToShow,
+        };
+        let default = Self::new();
+        let config = Self {
+            mode,
+            action: config.action.unwrap_or(default.action),
+            hand: ToggleHand::from_napi(config.hand),
+            secondary_action: config.secondaryAction,
+            secondary_hand: ToggleHand::from_napi(config.secondaryHand),
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn to_napi(&self) -> ToggleGestureConfig {
+        let (mode, duration_ms, window_ms) = match self.mode {
+            ToggleMode::Click => (ToggleGestureMode::Click, None, None),
+            ToggleMode::LongPress { seconds } => {
+                (ToggleGestureMode::LongPress, Some(seconds * 1000.0), None)
+            }
+            ToggleMode::DoublePress { window_seconds } => (
+                ToggleGestureMode::DoublePress,
+                None,
+                Some(window_seconds * 1000.0),
+            ),
+            ToggleMode::Chord => (ToggleGestureMode::Chord, None, None),
+            ToggleMode::HoldToShow => (ToggleGestureMode::HoldToShow, None, None),
+        };
+        ToggleGestureConfig {
+            mode,
+            action: Some(self.action.clone()),
+            hand: Some(self.hand.to_napi()),
+            secondaryAction: self.secondary_action.clone(),
+            secondaryHand: Some(self.secondary_hand.to_napi()),
+            durationMs: duration_ms,
+            windowMs: window_ms,
+        }
+    }
+}
+
+impl ToggleOutput {
+    fn to_napi(self) -> ToggleGestureEvent {
+        match self {
+            ToggleOutput::None => ToggleGestureEvent::None,
+            ToggleOutput::Toggle => ToggleGestureEvent::Toggle,
+            ToggleOutput::Show => ToggleGestureEvent::Show,
+            ToggleOutput::Hide => ToggleGestureEvent::Hide,
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Held state of a boolean action on `hand`; an action missing from the set reads as
+    /// released / `hand` でのブールアクションの押下状態。セットにないアクションは離した状態として読む
+    pub(super) fn read_held_button(
+        &self,
+        cache: &InputActionCache,
+        name: &str,
+        hand: ToggleHand,
+    ) -> napi::Result<bool> {
+        if cache.action(name).is_none() {
+            return Ok(false);
+        }
+        let hand = (hand != ToggleHand::Any).then(|| hand.to_napi());
+        let action = cache.resolve(name, &[InputActionType::Boolean], hand)?;
+        let digital = self.read_digital_action(action)?;
+        Ok(digital.bActive && digital.bState)
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Poll the configured toggle gesture once per frame. Every mode keeps the held-state
+    /// latch, so SteamVR briefly dropping action state while the overlay is shown or hidden
+    /// does not re-trigger it.
+    /// 設定されたトグルジェスチャーをフレームごとに1回ポーリングする。どのモードでも押下状態の
+    /// ラッチを保つため、オーバーレイの表示・非表示の間に SteamVR がアクション状態を一時的に
+    /// 落としても再発火しない。
+    #[napi]
+    pub fn poll_toggle_gesture(&self) -> napi::Result<ToggleGestureEvent> {
+        let mut cache = self.borrow_input_cache_mut()?;
+        if !cache.initialized {
+            return Err(napi::Error::from_reason("SteamVR input is not initialized"));
+        }
+        self.update_action_state(&cache)?;
+
+        let config = self.borrow_toggle_config()?;
+        let primary = self.read_held_button(&cache, &config.action, config.hand)?;
+        let secondary = match (&config.mode, &config.secondary_action) {
+            (ToggleMode::Chord, Some(name)) => {
+                self.read_held_button(&cache, name, config.secondary_hand)?
+            }
+            _ => false,
+        };
+        let seconds = self.input_seconds(None)?;
+        Ok(cache
+            .toggle
+            .update(config.mode, primary, secondary, seconds)
+            .to_napi())
+    }
+
+    /// True whenever the toggle gesture fires; with HoldToShow both the show and the hide count
+    /// トグルジェスチャーが発火したら true。HoldToShow では表示・非表示の両方が該当する
+    #[napi]
+    pub fn poll_toggle_clicked(&self) -> napi::Result<bool> {
+        Ok(self.poll_toggle_gesture()? != ToggleGestureEvent::None)
+    }
+
+    /// Replace the toggle gesture; recognition restarts from released. Every action must be
+    /// a boolean action of the initialized set that can be read from its hand.
+    /// トグルジェスチャーを置き換える。認識は離した状態からやり直す。各アクションは初期化済みの
+    /// セットにあり、指定した手から読めるブールアクションでなければならない。
+    #[napi]
+    pub fn set_toggle_gesture(&self, config: ToggleGestureConfig) -> napi::Result<()> {
+        let config = ToggleConfig::from_napi(config)?;
+        let mut cache = self.borrow_input_cache_mut()?;
+        let secondary = match config.mode {
+            ToggleMode::Chord => config
+                .secondary_action
+                .as_deref()
+                .zip(Some(config.secondary_hand)),
+            _ => None,
+        };
+        for (name, hand) in std::iter::once((config.action.as_str(), config.hand)).chain(secondary)
+        {
+            let hand = (hand != ToggleHand::Any).then(|| hand.to_napi());
+            cache.resolve(name, &[InputActionType::Boolean], hand)?;
+        }
+        *self.borrow_toggle_config_mut()? = config;
+        cache.toggle = Default::default();
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_toggle_gesture(&self) -> napi::Result<ToggleGestureConfig> {
+        Ok(self.borrow_toggle_config()?.to_napi())
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::BTreeMap;
+
+use super::json::Json;
+use super::key_layout::{KeyLayout, KeyRect};
+
+/// Model used for presses from a device without a controller type
+/// コントローラー種別のないデバイスからの押下に使うモデル
+pub(super) const GENERIC_TOUCH_MODEL: &str = "generic";
+
+/// Offset spread assumed for a key before it has presses, in key sizes
+/// 押下がまだないキーに仮定するオフセットの分散 (キーの大きさ単位)
+const PRIOR_VARIANCE: f64 = 0.09;
+
+/// Learning and resolution settings / 学習と判定の設定
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct TouchParams {
+    /// Presses a key needs before it biases resolution; also the weight of the prior
+    /// 判定に影響するまでにキーが必要とする押下数。事前分布の重みも兼ねる
+    pub min_samples: u32,
+    /// Presses remembered per key; beyond it older presses fade out
+    /// キーごとに覚える押下数。超えると古い押下から薄れていく
+    pub max_samples: u32,
+    /// A retype this soon after a backspace attributes the erased press to the retyped key
+    /// バックスペースからこの時間内の打ち直しは、消した押下を打ち直したキーのものとみなす
+    pub correction_seconds: f64,
+    /// How far outside its rectangle a press can still go to a key, in key sizes
+    /// キーの矩形の外でもそのキーとみなせる距離 (キーの大きさ単位)
+    pub reach: f64,
+}
+
+impl TouchParams {
+    pub(super) const DEFAULT: Self = Self {
+        min_samples: 5,
+        max_samples: 100,
+        correction_seconds: 3.0,
+        reach: 0.5,
+    };
+
+    pub(super) fn validate(&self) -> napi::Result<()> {
+        if self.min_samples == 0 || self.max_samples < self.min_samples {
+            return Err(napi::Error::from_reason(
+                "minSamples must be at least 1 and no more than maxSamples",
+            ));
+        }
+        if !(self.correction_seconds.is_finite() && self.correction_seconds > 0.0) {
+            return Err(napi::Error::from_reason(
+                "correctionMs must be a positive number",
+            ));
+        }
+        if !(self.reach.is_finite() && (0.0..=2.0).contains(&self.reach)) {
+            return Err(napi::Error::from_reason("reach must be between 0 and 2"));
+        }
+        Ok(())
+    }
+}
+
+/// Where a point sits relative to a key's center, in key widths and heights
+/// キーの中心から見た点の位置 (キーの幅・高さ単位)
+fn key_offset(key: &KeyRect, u: f64, v: f64) -> (f64, f64) {
+    let (center_u, center_v) = key.center();
+    (
+        (u - center_u) / (key.right - key.left),
+        (v - center_v) / (key.bottom - key.top),
+    )
+}
+
+/// Learned press distribution of one key, in key sizes so it survives layout scaling
+/// 1キー分の学習済みの押下分布。レイアウトの拡縮に影響されないようキーの大きさ単位で持つ
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(super) struct KeyOffset {
+    pub samples: f64,
+    pub mean: (f64, f64),
+    pub variance: (f64, f64),
+}
+
+impl KeyOffset {
+    /// Running mean and variance; once `max_samples` is reached every press gets the same
+    /// weight, so the estimate follows a drifting posture
+    /// 平均と分散の逐次更新。`max_samples` に達した後は押下ごとの重みが一定になり、
+    /// 姿勢の変化に追従する
+    fn add(&mut self, params: &TouchParams, (du, dv): (f64, f64)) {
+        self.samples = (self.samples + 1.0).min(params.max_samples as f64);
+        let alpha = 1.0 / self.samples;
+        let update = |mean: &mut f64, variance: &mut f64, value: f64| {
+            let delta = value - *mean;
+            *mean += alpha * delta;
+            *variance = (1.0 - alpha) * (*variance + alpha * delta * delta);
+        };
+        update(&mut self.mean.0, &mut self.variance.0, du);
+        update(&mut self.mean.1, &mut self.variance.1, dv);
+    }
+
+    /// Negative log-likelihood (up to a constant) of a press offset, with the learned
+    /// statistics shrunk toward the key center by a prior worth `min_samples` presses
+    /// 押下オフセットの負の対数尤度 (定数を除く)。学習した統計は `min_samples` 回分の
+    /// 事前分布によってキーの中心へ引き寄せる
+    fn cost(&self, params: &TouchParams, (du, dv): (f64, f64)) -> f64 {
+        let prior = params.min_samples as f64;
+        let weight = self.samples / (self.samples + prior);
+        let axis = |offset: f64, mean: f64, variance: f64| {
+            let variance = weight * variance + (1.0 - weight) * PRIOR_VARIANCE;
+            let delta = offset - mean * weight;
+            delta * delta / variance + variance.ln()
+        };
+        axis(du, self.mean.0, self.variance.0) + axis(dv, self.mean.1, self.variance.1)
+    }
+}
+
+/// Per-key hit offsets for one controller type, keyed by key id
+/// コントローラー種別1つ分のキーごとのヒットのずれ (キー ID で引く)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct TouchModel {
+    keys: BTreeMap<String, KeyOffset>,
+}
+
+impl TouchModel {
+    pub(super) fn keys(&self) -> &BTreeMap<String, KeyOffset> {
+        &self.keys
+    }
+
+    fn learn(&mut self, params: &TouchParams, key: &KeyRect, (u, v): (f64, f64)) {
+        self.keys
+            .entry(key.id.clone())
+            .or_default()
+            .add(params, key_offset(key, u, v));
+    }
+
+    /// Key for a press, biased toward where this controller type actually lands on each
+    /// key. Until a nearby key has `min_samples` presses this is plain hit-testing.
+    /// 押下に対するキー。このコントローラー種別が各キーで実際に当たる位置に寄せて判定する。
+    /// 近くのキーの押下が `min_samples` に達するまでは通常のヒット判定と同じ。
+    pub(super) fn resolve(
+        &self,
+        params: &TouchParams,
+        layout: &KeyLayout,
+        u: f64,
+        v: f64,
+    ) -> Option<usize> {
+        let stats = |key: &KeyRect| self.keys.get(&key.id).copied().unwrap_or_default();
+        let candidates: Vec<usize> = (0..layout.keys().len())
+            .filter(|&index| {
+                let (du, dv) = key_offset(layout.key(index), u, v);
+                du.abs() <= 0.5 + params.reach && dv.abs() <= 0.5 + params.reach
+            })
+            .collect();
+        let trained = candidates
+            .iter()
+            .any(|&index| stats(layout.key(index)).samples >= params.min_samples as f64);
+        if !trained {
+            return layout.key_at(u, v);
+        }
+        let cost = |index: usize| {
+            let key = layout.key(index);
+            stats(key).cost(params, key_offset(key, u, v))
+        };
+        candidates
+            .into_iter()
+            .min_by(|&a, &b| cost(a).total_cmp(&cost(b)))
+    }
+}
+
+/// Touch models per controller type, persisted as JSON
+/// コントローラー種別ごとのタッチモデル。JSON で保存する
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct TouchModels {
+    models: BTreeMap<String, TouchModel>,
+}
+
+impl TouchModels {
+    pub(super) fn model(&self, controller_type: &str) -> Option<&TouchModel> {
+        self.models.get(controller_type)
+    }
+
+    pub(super) fn controller_types(&self) -> Vec<String> {
+        self.models.keys().cloned().collect()
+    }
+
+    pub(super) fn learn(&mut self, params: &TouchParams, layout: &KeyLayout, sample: LearnedPress) {
+        self.models
+            .entry(sample.controller_type)
+            .or_default()
+            .learn(params, layout.key(sample.key), sample.point);
+    }
+
+    /// Forget one controller type, or every type / 1つまたはすべてのコントローラー種別を忘れる
+    pub(super) fn reset(&mut self, controller_type: Option<&str>) {
+        match controller_type {
+            Some(controller_type) => {
+                self.models.remove(controller_type);
+            }
+            None => self.models.clear(),
+        }
+    }
+
+    pub(super) fn to_json(&self) -> Json {
+        let pair = |(a, b): (f64, f64)| Json::Array(vec![Json::Number(a), Json::Number(b)]);
+        let models = self.models.iter().map(|(controller_type, model)| {
+            let keys = model.keys.iter().map(|(id, offset)| {
+                let entry = Json::object([
+                    ("samples", Json::Number(offset.samples)),
+                    ("mean", pair(offset.mean)),
+                    ("variance", pair(offset.variance)),
+                ]);
+                (id.clone(), entry)
+            });
+            (controller_type.clone(), Json::Object(keys.collect()))
+        });
+        Json::object([
+            ("version", Json::Number(1.0)),
+            ("controllers", Json::Object(models.collect())),
+        ])
+    }
+
+    pub(super) fn parse(text: &str) -> Result<Self, String> {
+        let root = Json::parse(text)?;
+        let Some(Json::Object(controllers)) = root.get("controllers") else {
+            return Err("touch model file must have a controllers object".to_string());
+        };
+        let mut models = BTreeMap::new();
+        for (controller_type, keys) in controllers {
+            let Json::Object(keys) = keys else {
+                return Err(format!("{controller_type}: expected an object"));
+            };
+            let mut model = TouchModel::default();
+            for (id, entry) in keys {
+                let context = format!("{controller_type}.{id}");
+                let number = |value: &Json| match value {
+                    Json::Number(n) if n.is_finite() => Ok(*n),
+                    _ => Err(format!("{context}: expected finite numbers")),
+                };
+                let pair = |field: &str| match entry.get(field).map(Json::as_array) {
+                    Some([a, b]) => Ok((number(a)?, number(b)?)),
+                    _ => Err(format!("{context}.{field}: expected a pair of numbers")),
+                };
+                let samples = number(entry.get("samples").unwrap_or(&Json::Null))?;
+                let variance = pair("variance")?;
+                if samples < 0.0 || variance.0 < 0.0 || variance.1 < 0.0 {
+                    return Err(format!(
+                        "{context}: samples and variance must not be negative"
+                    ));
+                }
+                let offset = KeyOffset {
+                    samples,
+                    mean: pair("mean")?,
+                    variance,
+                };
+                model.keys.insert(id.clone(), offset);
+            }
+            models.insert(controller_type.clone(), model);
+        }
+        Ok(Self { models })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct LearnedPress {
+    pub controller_type: String,
+    pub key: usize,
+    pub point: (f64, f64),
+}
+
+/// Decides which committed presses to learn from. A press is learned for its own key
+/// once the next key is committed; a press erased with backspace is not, but when a
+/// different key is typed within `correction_seconds` and the erased point was within
+/// its reach, the erased point is learned for that key instead. Several backspaces in a
+/// row are ambiguous and learn nothing.
+/// どの確定済みの押下から学習するかを決める。押下は次のキーが確定した時点でそのキーとして
+/// 学習する。バックスペースで消した押下はそのキーとしては学習せず、`correction_seconds` 以内に
+/// 別のキーが入力され、消した点がそのキーの届く範囲にあれば、代わりにそのキーとして学習する。
+/// 連続したバックスペースはあいまいなため何も学習しない。
+#[derive(Clone, Debug, Default)]
+pub(super) struct CorrectionTracker {
+    pending: Option<LearnedPress>,
+    erased: Option<(LearnedPress, f64)>,
+}
+
+impl CorrectionTracker {
+    pub(super) fn commit(
+        &mut self,
+        params: &TouchParams,
+        layout: &KeyLayout,
+        press: LearnedPress,
+        seconds: f64,
+    ) -> Vec<LearnedPress> {
+        let mut learned: Vec<LearnedPress> = self.pending.take().into_iter().collect();
+        if let Some((erased, erased_at)) = self.erased.take() {
+            let (du, dv) = key_offset(layout.key(press.key), erased.point.0, erased.point.1);
+            let reachable = du.abs() <= 0.5 + params.reach && dv.abs() <= 0.5 + params.reach;
+            if erased.key != press.key
+                && reachable
+                && seconds - erased_at <= params.correction_seconds
+            {
+                learned.push(LearnedPress {
+                    key: press.key,
+                    ..erased
+                });
+            }
+        }
+        self.pending = Some(press);
+        learned
+    }
+
+    pub(super) fn backspace(&mut self, seconds: f64) {
+        self.erased = self.pending.take().map(|press| (press, seconds));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::key_layout::test_layout;
+    use super::*;
+
+    const PARAMS: TouchParams = TouchParams::DEFAULT;
+
+    fn press(key: usize, point: (f64, f64)) -> LearnedPress {
+        LearnedPress {
+            controller_type: "knuckles".to_string(),
+            key,
+            point,
+        }
+    }
+
+    /// Id of the key the knuckles model resolves a point to / knuckles のモデルが点を判定したキーの ID
+    fn resolve<'a>(
+        models: &TouchModels,
+        layout: &'a KeyLayout,
+        point: (f64, f64),
+    ) -> Option<&'a str> {
+        let model = models.model("knuckles").cloned().unwrap_or_default();
+        let index = model.resolve(&PARAMS, layout, point.0, point.1);
+        index.map(|index| layout.key(index).id.as_str())
+    }
+
+    #[test]
+    fn learned_offsets_pull_presses_to_the_intended_key() {
+        let layout = test_layout();
+        // Aiming at "w" (center v 0.25) but landing at v 0.53, just inside "s"
+        // "w" (中心 v 0.25) を狙って v 0.53、"s" の少し内側に当たる
+        let low = (0.5, 0.53);
+        let mut models = TouchModels::default();
+        assert_eq!(resolve(&models, &layout, low), Some("s"));
+        for i in 0..5 {
+            let point = (0.5 + i as f64 * 0.01, 0.52 + i as f64 * 0.005);
+            models.learn(&PARAMS, &layout, press(1, point));
+        }
+        assert_eq!(resolve(&models, &layout, low), Some("w"));
+        // Well inside "s" it is still "s" / "s" の十分内側なら "s" のまま
+        assert_eq!(resolve(&models, &layout, (0.5, 0.75)), Some("s"));
+    }
+
+    #[test]
+    fn corrections_relabel_the_erased_press() {
+        let layout = test_layout();
+        let mut tracker = CorrectionTracker::default();
+        let near_w = (0.5, 0.55);
+        assert!(tracker
+            .commit(&PARAMS, &layout, press(4, near_w), 0.0)
+            .is_empty());
+        tracker.backspace(0.5);
+        // Retyping "w" learns the erased point for "w", not "s"
+        // "w" を打ち直すと、消した点を "s" ではなく "w" として学習する
+        let learned = tracker.commit(&PARAMS, &layout, press(1, (0.5, 0.25)), 1.0);
+        assert_eq!(learned, [press(1, near_w)]);
+        // The retyped press itself is learned once the next key is committed
+        // 打ち直した押下自体は次のキーが確定した時点で学習する
+        let learned = tracker.commit(&PARAMS, &layout, press(2, (0.8, 0.25)), 2.0);
+        assert_eq!(learned, [press(1, (0.5, 0.25))]);
+
+        tracker.backspace(3.0);
+        tracker.backspace(3.1);
+        assert!(tracker
+            .commit(&PARAMS, &layout, press(1, (0.5, 0.25)), 3.5)
+            .is_empty());
+    }
+
+    #[test]
+    fn models_round_trip_through_json_and_reset_per_type() {
+        let layout = test_layout();
+        let mut models = TouchModels::default();
+        for _ in 0..150 {
+            models.learn(&PARAMS, &layout, press(0, (0.2, 0.3)));
+        }
+        let offset = models.model("knuckles").unwrap().keys()["q"];
+        assert_eq!(offset.samples, PARAMS.max_samples as f64);
+
+        let parsed = TouchModels::parse(&models.to_json().to_pretty()).unwrap();
+        assert_eq!(parsed, models);
+        assert!(TouchModels::parse("{}").is_err());
+
+        models.reset(Some("knuckles"));
+        assert_eq!(models.controller_types(), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,189 @@
+use napi_derive::napi;
+use openvr_sys as vr;
+
+use super::key_layout::layout_point;
+use super::manager::OverlayManager;
+use super::touch_model::{
+    CorrectionTracker, LearnedPress, TouchModels, TouchParams, GENERIC_TOUCH_MODEL,
+};
+use super::types::{TouchKeyOffset, TouchModelParams};
+
+impl TouchParams {
+    fn from_napi(params: &TouchModelParams) -> napi::Result<Self> {
+        let params = Self {
+            min_samples: params.minSamples,
+            max_samples: params.maxSamples,
+            correction_seconds: params.correctionMs / 1000.0,
+            reach: params.reach,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn to_napi(self) -> TouchModelParams {
+        TouchModelParams {
+            minSamples: self.min_samples,
+            maxSamples: self.max_samples,
+            correctionMs: self.correction_seconds * 1000.0,
+            reach: self.reach,
+        }
+    }
+}
+
+impl OverlayManager {
+    /// Touch model for the device's `Prop_ControllerType_String`
+    /// デバイスの `Prop_ControllerType_String` に対応するタッチモデル
+    fn touch_model_type(&self, controller_index: u32) -> napi::Result<String> {
+        let controller_type = self.string_device_property(
+            controller_index,
+            vr::ETrackedDeviceProperty_Prop_ControllerType_String,
+        )?;
+        Ok(controller_type.unwrap_or_else(|| GENERIC_TOUCH_MODEL.to_string()))
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    /// Key under a controller's press on the keyboard (intersection UV), biased by what
+    /// that controller type has learned about where the user actually lands on each key.
+    /// Falls back to plain hit-testing over `setKeyLayout` until enough presses have been
+    /// committed.
+    /// キーボード上のコントローラーの押下 (交点の UV) に対するキー。そのコントローラー種別で学習した、
+    /// ユーザーが各キーで実際に当たる位置に寄せて判定する。十分な押下が確定するまでは
+    /// `setKeyLayout` に対する通常のヒット判定と同じ。
+    #[napi]
+    pub fn resolve_touch_key(
+        &self,
+        controller_index: u32,
+        u: f64,
+        v: f64,
+    ) -> napi::Result<Option<String>> {
+        let controller_type = self.touch_model_type(controller_index)?;
+        let (u, v) = layout_point(u, v);
+        let index = {
+            let layout = self.borrow_key_layout()?;
+            let models = self.borrow_touch_models()?;
+            match models.model(&controller_type) {
+                Some(model) => model.resolve(&self.touch_params(), &layout, u, v),
+                None => layout.key_at(u, v),
+            }
+        };
+        self.key_id(index)
+    }
+
+    /// Report a key the user typed and where the press landed (intersection UV). Presses
+    /// are learned once the next key confirms them; see `recordTouchBackspace` for
+    /// corrections.
+    /// ユーザーが入力したキーと押下の位置 (交点の UV) を報告する。押下は次のキーで確定した時点で学習する。
+    /// 訂正は `recordTouchBackspace` を参照。
+    #[napi]
+    pub fn commit_touch_key(
+        &self,
+        controller_index: u32,
+        key_id: String,
+        u: f64,
+        v: f64,
+        timestamp_ms: Option<f64>,
+    ) -> napi::Result<()> {
+        let seconds = self.input_seconds(timestamp_ms)?;
+        let controller_type = self.touch_model_type(controller_index)?;
+        let layout = self.borrow_key_layout()?;
+        let key = layout
+            .keys()
+            .iter()
+            .position(|key| key.id == key_id)
+            .ok_or_else(|| napi::Error::from_reason(format!("unknown key id: {key_id}")))?;
+        let params = self.touch_params();
+        let press = LearnedPress {
+            controller_type,
+            key,
+            point: layout_point(u, v),
+        };
+        let learned = self
+            .borrow_correction_tracker_mut()?
+            .commit(&params, &layout, press, seconds);
+        let mut models = self.borrow_touch_models_mut()?;
+        for press in learned {
+            models.learn(&params, &layout, press);
+        }
+        Ok(())
+    }
+
+    /// Report a backspace: the last committed press is not learned for its key, and a
+    /// retype of a different key soon after relabels it
+    /// バックスペースを報告する。直前に確定した押下はそのキーとしては学習せず、
+    /// 直後に別のキーを打ち直すとそのキーの押下として付け替える
+    #[napi]
+    pub fn record_touch_backspace(&self, timestamp_ms: Option<f64>) -> napi::Result<()> {
+        let seconds = self.input_seconds(timestamp_ms)?;
+        self.borrow_correction_tracker_mut()?.backspace(seconds);
+        Ok(())
+    }
+
+    /// Forget what was learned for one controller type, or for every type when omitted
+    /// 1つのコントローラー種別 (省略時はすべて) について学習した内容を忘れる
+    #[napi]
+    pub fn reset_touch_model(&self, controller_type: Option<String>) -> napi::Result<()> {
+        self.borrow_touch_models_mut()?
+            .reset(controller_type.as_deref());
+        *self.borrow_correction_tracker_mut()? = CorrectionTracker::default();
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_touch_key_offsets(
+        &self,
+        controller_type: String,
+    ) -> napi::Result<Vec<TouchKeyOffset>> {
+        let models = self.borrow_touch_models()?;
+        let Some(model) = models.model(&controller_type) else {
+            return Ok(Vec::new());
+        };
+        Ok(model
+            .keys()
+            .iter()
+            .map(|(id, offset)| TouchKeyOffset {
+                id: id.clone(),
+                samples: offset.samples,
+                offsetX: offset.mean.0,
+                offsetY: offset.mean.1,
+                spreadX: offset.variance.0.sqrt(),
+                spreadY: offset.variance.1.sqrt(),
+            })
+            .collect())
+    }
+
+    /// Replace the touch models with those saved in a file. Returns the controller types
+    /// it holds; a parse error leaves the current models in place.
+    /// ファイルに保存したタッチモデルで置き換える。含まれるコントローラー種別を返す。
+    /// 解析エラー時は現在のモデルをそのまま保つ。
+    #[napi]
+    pub fn load_touch_models(&self, path: String) -> napi::Result<Vec<String>> {
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| napi::Error::from_reason(format!("Failed to read {path}: {e}")))?;
+        let models = TouchModels::parse(&text)
+            .map_err(|e| napi::Error::from_reason(format!("{path}: {e}")))?;
+        let types = models.controller_types();
+        *self.borrow_touch_models_mut()? = models;
+        Ok(types)
+    }
+
+    #[napi]
+    pub fn save_touch_models(&self, path: String) -> napi::Result<()> {
+        let mut text = self.borrow_touch_models()?.to_json().to_pretty();
+        text.push('\n');
+        std::fs::write(&path, text)
+            .map_err(|e| napi::Error::from_reason(format!("Failed to write {path}: {e}")))
+    }
+
+    #[napi]
+    pub fn set_touch_model_params(&self, params: TouchModelParams) -> napi::Result<()> {
+        self.set_touch_params(TouchParams::from_napi(&params)?);
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_touch_model_params(&self) -> TouchModelParams {
+        self.touch_params().to_napi()
+    }
+}
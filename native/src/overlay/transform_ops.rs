@@ -2,35 +2,27 @@ use napi_derive::napi;
 use openvr_sys as vr;
 
 use super::constants::HMD_DEVICE_INDEX;
+use super::device_ops::DeviceSelector;
 use super::errors::{overlay_error, require_fn};
-use super::handles::overlay_handle;
-use super::manager::OverlayManager;
-use super::math::{hmd_matrix34_to_vec, validate_matrix};
-use super::types::OverlayRelativeTransform;
+use super::handles::{overlay_handle, OverlayHandle};
+use super::manager::{DeviceAttachment, OverlayManager, OverlayParentLink};
+use super::math::{hmd_matrix34_to_vec, mul_hmd_matrix34, validate_matrix, vec_to_hmd_matrix34};
+use super::types::{OverlayParentTransform, OverlayRelativeTransform, TrackedDeviceSelector};
 
-#[napi]
 impl OverlayManager {
-    #[napi]
-    pub fn set_overlay_transform_hmd(&self, handle: i64, distance: f64) -> napi::Result<()> {
+    fn apply_tracked_device_transform(
+        &self,
+        handle: OverlayHandle,
+        device_index: u32,
+        transform: &mut vr::HmdMatrix34_t,
+    ) -> napi::Result<()> {
         let overlay = self.overlay()?;
         let set_transform_fn = require_fn(
             overlay.SetOverlayTransformTrackedDeviceRelative,
             "SetOverlayTransformTrackedDeviceRelative",
         )?;
-        let handle = overlay_handle(handle)?;
         unsafe {
-            // Create transform matrix: position overlay `distance` meters in front of HMD
-            // 変換行列を作成: HMDの前方`distance`メートルにオーバーレイを配置
-            let mut transform = vr::HmdMatrix34_t {
-                m: [
-                    [1.0, 0.0, 0.0, 0.0],                // X axis
-                    [0.0, 1.0, 0.0, 0.0],                // Y axis
-                    [0.0, 0.0, 1.0, -(distance as f32)], // Z axis (negative = in front)
-                ],
-            };
-
-            // k_unTrackedDeviceIndex_Hmd = 0 (HMD device index) / HMDのデバイスインデックス
-            let err = set_transform_fn(handle.as_u64(), HMD_DEVICE_INDEX, &mut transform);
+            let err = set_transform_fn(handle.as_u64(), device_index, transform);
             if err != vr::EVROverlayError_VROverlayError_None {
                 return Err(overlay_error(
                     "SetOverlayTransformTrackedDeviceRelative",
@@ -42,6 +34,394 @@ impl OverlayManager {
         Ok(())
     }
 
+    pub(super) fn apply_absolute_transform(
+        &self,
+        handle: OverlayHandle,
+        origin: vr::ETrackingUniverseOrigin,
+        transform: &mut vr::HmdMatrix34_t,
+    ) -> napi::Result<()> {
+        let overlay = self.overlay()?;
+        let set_transform_fn = require_fn(
+            overlay.SetOverlayTransformAbsolute,
+            "SetOverlayTransformAbsolute",
+        )?;
+        unsafe {
+            let err = set_transform_fn(handle.as_u64(), origin, transform);
+            if err != vr::EVROverlayError_VROverlayError_None {
+                return Err(overlay_error("SetOverlayTransformAbsolute", overlay, err));
+            }
+        }
+        if origin == vr::ETrackingUniverseOrigin_TrackingUniverseSeated {
+            // Seated overlays are re-anchored against this baseline / シーテッド配置はこの基準で再配置される
+            self.capture_seated_zero_pose();
+        }
+        Ok(())
+    }
+
+    /// Read where an overlay currently sits / オーバーレイの現在の配置を読み取る
+    pub(super) fn read_overlay_placement(
+        &self,
+        handle: OverlayHandle,
+    ) -> napi::Result<OverlayPlacement> {
+        let overlay = self.overlay()?;
+        let get_transform_type_fn =
+            require_fn(overlay.GetOverlayTransformType, "GetOverlayTransformType")?;
+        unsafe {
+            let mut transform_type = vr::VROverlayTransformType_VROverlayTransform_Absolute;
+            let err = get_transform_type_fn(handle.as_u64(), &mut transform_type);
+            if err != vr::EVROverlayError_VROverlayError_None {
+                return Err(overlay_error("GetOverlayTransformType", overlay, err));
+            }
+
+            let mut transform = vr::HmdMatrix34_t { m: [[0.0; 4]; 3] };
+            match transform_type {
+                vr::VROverlayTransformType_VROverlayTransform_Absolute => {
+                    let get_transform_fn = require_fn(
+                        overlay.GetOverlayTransformAbsolute,
+                        "GetOverlayTransformAbsolute",
+                    )?;
+                    let mut origin = vr::ETrackingUniverseOrigin_TrackingUniverseStanding;
+                    let err = get_transform_fn(handle.as_u64(), &mut origin, &mut transform);
+                    if err != vr::EVROverlayError_VROverlayError_None {
+                        return Err(overlay_error("GetOverlayTransformAbsolute", overlay, err));
+                    }
+                    Ok(OverlayPlacement::Absolute(origin, transform))
+                }
+                vr::VROverlayTransformType_VROverlayTransform_TrackedDeviceRelative => {
+                    let get_transform_fn = require_fn(
+                        overlay.GetOverlayTransformTrackedDeviceRelative,
+                        "GetOverlayTransformTrackedDeviceRelative",
+                    )?;
+                    let mut device_index = 0;
+                    let err = get_transform_fn(handle.as_u64(), &mut device_index, &mut transform);
+                    if err != vr::EVROverlayError_VROverlayError_None {
+                        return Err(overlay_error(
+                            "GetOverlayTransformTrackedDeviceRelative",
+                            overlay,
+                            err,
+                        ));
+                    }
+                    Ok(OverlayPlacement::TrackedDevice(device_index, transform))
+                }
+                _ => Ok(OverlayPlacement::Unsupported),
+            }
+        }
+    }
+
+    /// Overlay transform in the configured universe, `None` when it cannot be resolved
+    /// (an untracked parent device or an unsupported transform type)
+    /// 設定ユニバースでのオーバーレイの変換。解決できない場合は `None`
+    /// (親デバイスが未トラッキング、または未対応の変換タイプ)
+    pub(super) fn overlay_world_transform(
+        &self,
+        handle: OverlayHandle,
+    ) -> napi::Result<Option<vr::HmdMatrix34_t>> {
+        match self.read_overlay_placement(handle)? {
+            OverlayPlacement::Absolute(origin, transform) => Ok(Some(
+                self.convert_universe_transform(&transform, origin, self.tracking_universe())?,
+            )),
+            OverlayPlacement::TrackedDevice(device_index, transform) => {
+                let poses = self.fresh_poses()?;
+                Ok(poses
+                    .get(device_index as usize)
+                    .filter(|pose| pose.bPoseIsValid && pose.bDeviceIsConnected)
+                    .map(|pose| mul_hmd_matrix34(&pose.mDeviceToAbsoluteTracking, &transform)))
+            }
+            OverlayPlacement::Unsupported => Ok(None),
+        }
+    }
+
+    /// Place `handle` at `offset` in its parent's space, then its own children
+    /// `handle` を親の空間内の `offset` に配置し、続けて自身の子を配置する
+    fn apply_overlay_relative_transform(
+        &self,
+        handle: OverlayHandle,
+        parent: OverlayHandle,
+        offset: &vr::HmdMatrix34_t,
+    ) -> napi::Result<()> {
+        match self.read_overlay_placement(parent)? {
+            OverlayPlacement::Absolute(origin, parent_transform) => {
+                let mut transform = mul_hmd_matrix34(&parent_transform, offset);
+                self.apply_absolute_transform(handle, origin, &mut transform)?;
+            }
+            OverlayPlacement::TrackedDevice(device_index, parent_transform) => {
+                let mut transform = mul_hmd_matrix34(&parent_transform, offset);
+                self.apply_tracked_device_transform(handle, device_index, &mut transform)?;
+            }
+            // Parent is not placed in a way we can follow yet / 親がまだ追従可能な配置になっていない
+            OverlayPlacement::Unsupported => return Ok(()),
+        }
+        self.sync_overlay_children(handle)
+    }
+
+    /// Re-place every child after `parent` moved / `parent` の移動後にすべての子を再配置
+    pub(super) fn sync_overlay_children(&self, parent: OverlayHandle) -> napi::Result<()> {
+        let children: Vec<(u64, vr::HmdMatrix34_t)> = self
+            .borrow_overlay_parents()?
+            .iter()
+            .filter(|(_, link)| link.parent == parent.as_u64())
+            .map(|(&child, link)| (child, link.transform))
+            .collect();
+        for (child, offset) in children {
+            self.apply_overlay_relative_transform(OverlayHandle::from_u64(child), parent, &offset)?;
+        }
+        Ok(())
+    }
+
+    /// Explicit placement replaces any device attachment or parent link
+    /// 明示的な配置はデバイスアタッチメントや親リンクを置き換える
+    pub(super) fn clear_overlay_placement(&self, handle: OverlayHandle) -> napi::Result<()> {
+        self.borrow_device_attachments_mut()?
+            .remove(&handle.as_u64());
+        self.borrow_overlay_parents_mut()?.remove(&handle.as_u64());
+        Ok(())
+    }
+
+    /// Drop all placement state of a destroyed overlay, including links to its children
+    /// 破棄されたオーバーレイの配置状態を、子へのリンクも含めてすべて破棄する
+    pub(super) fn forget_overlay_placement(&self, handle: OverlayHandle) -> napi::Result<()> {
+        self.clear_overlay_placement(handle)?;
+        self.borrow_overlay_parents_mut()?
+            .retain(|_, link| link.parent != handle.as_u64());
+        Ok(())
+    }
+}
+
+pub(super) enum OverlayPlacement {
+    Absolute(vr::ETrackingUniverseOrigin, vr::HmdMatrix34_t),
+    TrackedDevice(u32, vr::HmdMatrix34_t),
+    Unsupported,
+}
+
+#[napi]
+impl OverlayManager {
+    #[napi]
+    pub fn set_overlay_transform_hmd(&self, handle: i64, distance: f64) -> napi::Result<()> {
+        let handle = overlay_handle(handle)?;
+        // Create transform matrix: position overlay `distance` meters in front of HMD
+        // 変換行列を作成: HMDの前方`distance`メートルにオーバーレイを配置
+        let mut transform = vr::HmdMatrix34_t {
+            m: [
+                [1.0, 0.0, 0.0, 0.0],                // X axis
+                [0.0, 1.0, 0.0, 0.0],                // Y axis
+                [0.0, 0.0, 1.0, -(distance as f32)], // Z axis (negative = in front)
+            ],
+        };
+
+        // k_unTrackedDeviceIndex_Hmd = 0 (HMD device index) / HMDのデバイスインデックス
+        self.clear_overlay_placement(handle)?;
+        self.apply_tracked_device_transform(handle, HMD_DEVICE_INDEX, &mut transform)?;
+        self.sync_overlay_children(handle)
+    }
+
+    #[napi]
+    pub fn set_overlay_transform_tracked_device(
+        &self,
+        handle: i64,
+        device_index: u32,
+        pose: Vec<f64>,
+    ) -> napi::Result<()> {
+        validate_matrix(&pose, "pose matrix")?;
+        if device_index >= vr::k_unMaxTrackedDeviceCount {
+            return Err(napi::Error::from_reason("Invalid device index"));
+        }
+        let handle = overlay_handle(handle)?;
+        let mut transform = vec_to_hmd_matrix34(&pose);
+        self.clear_overlay_placement(handle)?;
+        self.apply_tracked_device_transform(handle, device_index, &mut transform)?;
+        self.sync_overlay_children(handle)
+    }
+
+    /// Attach an overlay to a device chosen by `selector` with an offset `pose`.
+    /// Returns the resolved device index, or `null` while the device is absent;
+    /// the attachment is kept and re-applied by `refreshOverlayAttachments`.
+    /// `selector` で選んだデバイスに `pose` のオフセットでオーバーレイを取り付ける。
+    /// 解決したデバイスインデックスを返し、デバイスが無い間は `null` を返す。
+    /// アタッチメントは保持され、`refreshOverlayAttachments` で再適用される。
+    #[napi]
+    pub fn attach_overlay_to_tracked_device(
+        &self,
+        handle: i64,
+        selector: TrackedDeviceSelector,
+        pose: Vec<f64>,
+    ) -> napi::Result<Option<u32>> {
+        validate_matrix(&pose, "pose matrix")?;
+        let handle = overlay_handle(handle)?;
+        let selector = DeviceSelector::from_napi(&selector)?;
+        let mut transform = vec_to_hmd_matrix34(&pose);
+
+        self.clear_overlay_placement(handle)?;
+        let device_index = self.resolve_device_selector(selector)?;
+        if let Some(index) = device_index {
+            self.apply_tracked_device_transform(handle, index, &mut transform)?;
+            self.sync_overlay_children(handle)?;
+        }
+
+        self.borrow_device_attachments_mut()?.insert(
+            handle.as_u64(),
+            DeviceAttachment {
+                selector,
+                device_index,
+                transform,
+            },
+        );
+        Ok(device_index)
+    }
+
+    /// Stop following the device; the current transform is left as is
+    /// デバイスへの追従を止める。現在の変換はそのまま残る
+    #[napi]
+    pub fn detach_overlay_from_tracked_device(&self, handle: i64) -> napi::Result<bool> {
+        let handle = overlay_handle(handle)?;
+        Ok(self
+            .borrow_device_attachments_mut()?
+            .remove(&handle.as_u64())
+            .is_some())
+    }
+
+    /// Re-resolve every attachment and re-apply those whose device index changed
+    /// (e.g. after a controller reconnect). Returns the re-attached overlay handles.
+    /// すべてのアタッチメントを再解決し、デバイスインデックスが変わったものを再適用する
+    /// (例: コントローラー再接続後)。再アタッチしたオーバーレイハンドルを返す。
+    #[napi]
+    pub fn refresh_overlay_attachments(&self) -> napi::Result<Vec<i64>> {
+        let mut reattached = Vec::new();
+        {
+            let mut attachments = self.borrow_device_attachments_mut()?;
+            // One failing overlay must not keep the others detached; failures are logged
+            // and retried on the next refresh
+            // 1 つの失敗で他のオーバーレイが外れたままにならないよう、失敗はログに残し次回再試行する
+            for (&raw_handle, attachment) in attachments.iter_mut() {
+                let device_index = match self.resolve_device_selector(attachment.selector) {
+                    Ok(index) => index,
+                    Err(e) => {
+                        eprintln!(
+                            "[vr-overlay-native] failed to resolve attachment of overlay {}: {}",
+                            raw_handle, e
+                        );
+                        continue;
+                    }
+                };
+                if device_index == attachment.device_index {
+                    continue;
+                }
+                let Some(index) = device_index else {
+                    attachment.device_index = None;
+                    continue;
+                };
+                let handle = OverlayHandle::from_u64(raw_handle);
+                match self.apply_tracked_device_transform(handle, index, &mut attachment.transform)
+                {
+                    Ok(()) => {
+                        attachment.device_index = Some(index);
+                        reattached.push(handle);
+                    }
+                    Err(e) => eprintln!(
+                        "[vr-overlay-native] failed to re-attach overlay {}: {}",
+                        raw_handle, e
+                    ),
+                }
+            }
+        }
+        // Children are synced after the attachments borrow is released
+        // 子の同期はアタッチメントの借用を解放してから行う
+        for handle in &reattached {
+            if let Err(e) = self.sync_overlay_children(*handle) {
+                eprintln!(
+                    "[vr-overlay-native] failed to sync children of overlay {}: {}",
+                    handle.as_u64(),
+                    e
+                );
+            }
+        }
+        reattached.into_iter().map(OverlayHandle::to_i64).collect()
+    }
+
+    /// Place `handle` at `pose` in the space of `parent_handle` and keep following it.
+    /// IVROverlay_028 no longer exposes SetOverlayTransformOverlayRelative, so the
+    /// manager composes the transforms itself and re-places children whenever the
+    /// parent is moved through this manager (drag, respawn, device attachment).
+    /// `handle` を `parent_handle` の空間内の `pose` に配置し、以後追従させる。
+    /// IVROverlay_028 は SetOverlayTransformOverlayRelative を提供しないため、
+    /// マネージャーが変換を合成し、親がこのマネージャー経由で移動される度
+    /// (ドラッグ、リスポーン、デバイスへの取り付け) に子を再配置する。
+    #[napi]
+    pub fn set_overlay_transform_overlay_relative(
+        &self,
+        handle: i64,
+        parent_handle: i64,
+        pose: Vec<f64>,
+    ) -> napi::Result<()> {
+        validate_matrix(&pose, "pose matrix")?;
+        let handle = overlay_handle(handle)?;
+        let parent = overlay_handle(parent_handle)?;
+        if handle.as_u64() == parent.as_u64() {
+            return Err(napi::Error::from_reason("Overlay cannot be its own parent"));
+        }
+
+        // Reject cycles: walk up from the new parent / 循環を拒否: 新しい親から上へ辿る
+        {
+            let parents = self.borrow_overlay_parents()?;
+            let mut current = parent.as_u64();
+            while let Some(link) = parents.get(&current) {
+                if link.parent == handle.as_u64() {
+                    return Err(napi::Error::from_reason(
+                        "Overlay parent link would create a cycle",
+                    ));
+                }
+                current = link.parent;
+            }
+        }
+
+        let transform = vec_to_hmd_matrix34(&pose);
+        self.clear_overlay_placement(handle)?;
+        self.borrow_overlay_parents_mut()?.insert(
+            handle.as_u64(),
+            OverlayParentLink {
+                parent: parent.as_u64(),
+                transform,
+            },
+        );
+        self.apply_overlay_relative_transform(handle, parent, &transform)
+    }
+
+    #[napi]
+    pub fn get_overlay_transform_overlay_relative(
+        &self,
+        handle: i64,
+    ) -> napi::Result<OverlayParentTransform> {
+        let handle = overlay_handle(handle)?;
+        let parents = self.borrow_overlay_parents()?;
+        let link = parents
+            .get(&handle.as_u64())
+            .ok_or_else(|| napi::Error::from_reason("Overlay has no parent overlay"))?;
+        Ok(OverlayParentTransform {
+            parentHandle: OverlayHandle::from_u64(link.parent).to_i64()?,
+            transform: hmd_matrix34_to_vec(&link.transform.m),
+        })
+    }
+
+    /// Stop following the parent; the current transform is left as is
+    /// 親への追従を止める。現在の変換はそのまま残る
+    #[napi]
+    pub fn clear_overlay_parent(&self, handle: i64) -> napi::Result<bool> {
+        let handle = overlay_handle(handle)?;
+        Ok(self
+            .borrow_overlay_parents_mut()?
+            .remove(&handle.as_u64())
+            .is_some())
+    }
+
+    #[napi]
+    pub fn get_overlay_children(&self, parent_handle: i64) -> napi::Result<Vec<i64>> {
+        let parent = overlay_handle(parent_handle)?;
+        self.borrow_overlay_parents()?
+            .iter()
+            .filter(|(_, link)| link.parent == parent.as_u64())
+            .map(|(&child, _)| OverlayHandle::from_u64(child).to_i64())
+            .collect()
+    }
+
     #[napi]
     pub fn get_overlay_transform_absolute(&self, handle: i64) -> napi::Result<Vec<f64>> {
         let overlay = self.overlay()?;
@@ -50,7 +430,7 @@ impl OverlayManager {
             "GetOverlayTransformAbsolute",
         )?;
         let handle = overlay_handle(handle)?;
-        unsafe {
+        let (origin, transform) = unsafe {
             let mut origin = vr::ETrackingUniverseOrigin_TrackingUniverseStanding;
             let mut transform = vr::HmdMatrix34_t { m: [[0.0; 4]; 3] };
 
@@ -59,9 +439,13 @@ impl OverlayManager {
             if err != vr::EVROverlayError_VROverlayError_None {
                 return Err(overlay_error("GetOverlayTransformAbsolute", overlay, err));
             }
+            (origin, transform)
+        };
 
-            Ok(hmd_matrix34_to_vec(&transform.m))
-        }
+        // Report in the configured universe / 設定されたユニバースで返す
+        let transform =
+            self.convert_universe_transform(&transform, origin, self.tracking_universe())?;
+        Ok(hmd_matrix34_to_vec(&transform.m))
     }
 
     #[napi]
@@ -72,54 +456,18 @@ impl OverlayManager {
     ) -> napi::Result<()> {
         validate_matrix(&matrix, "transform matrix")?;
 
-        let overlay = self.overlay()?;
-        let set_transform_fn = require_fn(
-            overlay.SetOverlayTransformAbsolute,
-            "SetOverlayTransformAbsolute",
-        )?;
         let handle = overlay_handle(handle)?;
-        unsafe {
-            let mut transform = vr::HmdMatrix34_t {
-                m: [
-                    [
-                        matrix[0] as f32,
-                        matrix[1] as f32,
-                        matrix[2] as f32,
-                        matrix[3] as f32,
-                    ],
-                    [
-                        matrix[4] as f32,
-                        matrix[5] as f32,
-                        matrix[6] as f32,
-                        matrix[7] as f32,
-                    ],
-                    [
-                        matrix[8] as f32,
-                        matrix[9] as f32,
-                        matrix[10] as f32,
-                        matrix[11] as f32,
-                    ],
-                ],
-            };
-
-            // Calculate inverse to correct OpenVR's expectation?
-            // Actually SetOverlayTransformAbsolute takes the transform from TrackingOrigin to Overlay.
-            // If the matrix provided is the world transform of the overlay, it should be correct directly.
-            // OpenVRの期待値に合わせるために逆行列を計算するか？
-            // 実際には SetOverlayTransformAbsolute は TrackingOrigin からの Overlay の変換を取る。
-            // 提供された行列がオーバーレイのワールド変換であれば、直接そのままで正しいはず。
-
-            let err = set_transform_fn(
-                handle.as_u64(),
-                vr::ETrackingUniverseOrigin_TrackingUniverseStanding,
-                &mut transform,
-            );
+        let mut transform = vec_to_hmd_matrix34(&matrix);
 
-            if err != vr::EVROverlayError_VROverlayError_None {
-                return Err(overlay_error("SetOverlayTransformAbsolute", overlay, err));
-            }
-        }
-        Ok(())
+        // Calculate inverse to correct OpenVR's expectation?
+        // Actually SetOverlayTransformAbsolute takes the transform from TrackingOrigin to Overlay.
+        // If the matrix provided is the world transform of the overlay, it should be correct directly.
+        // OpenVRの期待値に合わせるために逆行列を計算するか？
+        // 実際には SetOverlayTransformAbsolute は TrackingOrigin からの Overlay の変換を取る。
+        // 提供された行列がオーバーレイのワールド変換であれば、直接そのままで正しいはず。
+        self.clear_overlay_placement(handle)?;
+        self.apply_absolute_transform(handle, self.tracking_universe(), &mut transform)?;
+        self.sync_overlay_children(handle)
     }
 
     #[napi]
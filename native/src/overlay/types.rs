@@ -22,6 +22,12 @@ pub struct ControllerState {
     pub joystickPressed: bool,
     pub joystickX: f64,
     pub joystickY: f64,
+    pub scrollX: f64,
+    pub scrollY: f64,
+    /// Legacy button map applied to the raw state: a controller type or "generic"
+    /// 生の状態に適用したレガシーボタンマップ。コントローラー種別名または "generic"
+    pub buttonMap: String,
+    pub buttonMapSource: LegacyButtonMapSource,
 }
 
 #[napi(object)]
@@ -30,6 +36,14 @@ pub struct OverlayRelativeTransform {
     pub transform: Vec<f64>, // 4x4 flattened / 4x4平坦化済み
 }
 
+#[napi(object)]
+pub struct OverlayParentTransform {
+    pub parentHandle: i64,
+    pub transform: Vec<f64>, // 4x4 flattened / 4x4平坦化済み
+}
+
+/// Label lists are localized names (falling back to raw paths); `actions` covers every action
+/// ラベル一覧はローカライズ名 (なければ生のパス)。`actions` はすべてのアクションを含む
 #[napi(object)]
 pub struct CurrentBindings {
     pub initialized: bool,
@@ -38,4 +52,698 @@ pub struct CurrentBindings {
     pub gripBindings: Vec<String>,
     pub triggerBound: bool,
     pub gripBound: bool,
+    pub actions: Vec<ActionBindings>,
+}
+
+/// One binding of an action from `GetActionBindingInfo`
+/// `GetActionBindingInfo` によるアクションのバインディング1件
+#[napi(object)]
+pub struct BindingInfo {
+    pub devicePath: String, // e.g. /user/hand/right
+    pub inputPath: String,  // e.g. /input/trigger
+    pub mode: String,
+    pub slot: String,
+    pub sourceType: String,
+    pub localizedName: Option<String>,
+}
+
+/// Physical source currently able to drive an action, from `GetActionOrigins`
+/// 現在アクションを駆動できる物理ソース (`GetActionOrigins` による)
+#[napi(object)]
+pub struct ActionOrigin {
+    pub localizedName: String,
+    pub trackedDeviceIndex: Option<u32>,
+    pub renderModelComponent: String,
+}
+
+#[napi(object)]
+pub struct ActionBindings {
+    pub name: String,
+    pub actionType: InputActionType,
+    pub bindings: Vec<BindingInfo>,
+    pub origins: Vec<ActionOrigin>,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackedDeviceClass {
+    Invalid = 0,
+    Hmd = 1,
+    Controller = 2,
+    GenericTracker = 3,
+    TrackingReference = 4,
+    DisplayRedirect = 5,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackedControllerRole {
+    Invalid = 0,
+    LeftHand = 1,
+    RightHand = 2,
+    OptOut = 3,
+    Treadmill = 4,
+    Stylus = 5,
+}
+
+/// Known `Prop_ControllerType_String` values / 既知の `Prop_ControllerType_String` の値
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ControllerType {
+    Unknown = 0,
+    ViveController = 1,
+    Knuckles = 2,
+    OculusTouch = 3,
+    HolographicController = 4,
+    ViveCosmosController = 5,
+    PicoController = 6,
+    ViveTracker = 7,
+    Gamepad = 8,
+}
+
+#[napi(object)]
+pub struct DeviceInfo {
+    pub deviceIndex: u32,
+    pub deviceClass: TrackedDeviceClass,
+    pub role: TrackedControllerRole,
+    pub controllerType: ControllerType,
+    pub controllerTypeName: Option<String>, // raw Prop_ControllerType_String / 生の値
+    pub modelNumber: Option<String>,
+    pub serialNumber: Option<String>,
+    pub manufacturerName: Option<String>,
+    pub renderModelName: Option<String>,
+    pub providesBatteryStatus: bool,
+    pub batteryPercentage: Option<f64>, // 0.0 - 1.0
+    pub isCharging: Option<bool>,
+}
+
+/// Device events from the IVRSystem event queue / IVRSystem のイベントキューからのデバイスイベント
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeviceEventType {
+    Activated = 0,
+    Deactivated = 1,
+    Updated = 2,
+    RoleChanged = 3,
+    UserInteractionStarted = 4,
+    UserInteractionEnded = 5,
+}
+
+#[napi(object)]
+pub struct DeviceEvent {
+    pub eventType: DeviceEventType,
+    pub deviceIndex: u32,
+    pub deviceClass: TrackedDeviceClass,
+    pub role: TrackedControllerRole,
+    pub eventAgeSeconds: f64,
+}
+
+/// Any other IVRSystem event; `eventType` is the raw `EVREventType` value
+/// その他の IVRSystem イベント。`eventType` は `EVREventType` の生の値
+#[napi(object)]
+pub struct VrEvent {
+    pub eventType: u32,
+    pub deviceIndex: u32,
+    pub eventAgeSeconds: f64,
+}
+
+/// Every event drained by one poll, device events separated out
+/// 1回のポーリングで取り出した全イベント。デバイスイベントは分けて返す
+#[napi(object)]
+pub struct VrEventBatch {
+    pub deviceEvents: Vec<DeviceEvent>,
+    pub otherEvents: Vec<VrEvent>,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputActionType {
+    Boolean = 0,
+    Vector1 = 1,
+    Vector2 = 2,
+    Pose = 3,
+    Vibration = 4,
+    Skeleton = 5,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputHand {
+    Any = 0,
+    Left = 1,
+    Right = 2,
+}
+
+/// `name` is relative to the action set (`toggle_overlay`) or a full `/actions/...` path
+/// `name` はアクションセットからの相対名 (`toggle_overlay`) または完全な `/actions/...` パス
+#[napi(object)]
+#[derive(Clone)]
+pub struct InputActionDefinition {
+    pub name: String,
+    pub actionType: InputActionType,
+    pub hand: Option<InputHand>, // restrict reads to one hand / 読み取りを片手に制限
+}
+
+#[napi(object)]
+pub struct InputActionSetConfig {
+    pub actionSet: String,
+    pub actions: Vec<InputActionDefinition>,
+}
+
+#[napi(object)]
+pub struct DigitalActionState {
+    pub active: bool,
+    pub state: bool,
+    pub changed: bool,
+    pub updateTime: f64, // seconds relative to now, <= 0 / 現在からの相対秒 (0以下)
+}
+
+#[napi(object)]
+pub struct AnalogActionState {
+    pub active: bool,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub deltaX: f64,
+    pub deltaY: f64,
+    pub deltaZ: f64,
+}
+
+#[napi(object)]
+pub struct PoseActionState {
+    pub active: bool,
+    pub poseIsValid: bool,
+    pub trackingResult: TrackingResult,
+    pub matrix: Vec<f64>,
+    pub velocity: Vec<f64>,
+    pub angularVelocity: Vec<f64>,
+}
+
+/// Ray from an aim pose; `origin` and `direction` are in the configured tracking universe
+/// エイムポーズからのレイ。`origin` と `direction` は設定されたトラッキングユニバース座標
+#[napi(object)]
+pub struct AimRay {
+    pub origin: Vec<f64>,
+    pub direction: Vec<f64>,
+    pub trackingResult: TrackingResult,
+}
+
+/// Fingertip poke thresholds in meters / 指先ポークのしきい値 (メートル)
+#[napi(object)]
+pub struct FingertipPokeParams {
+    pub pressDepth: f64,    // behind the surface / 面の奥側
+    pub releaseHeight: f64, // in front of the surface / 面の手前側
+    pub hoverRange: f64,
+}
+
+/// Drumstick mallet settings; lengths in meters / ドラムスティックのマレット設定 (長さはメートル)
+#[napi(object)]
+pub struct DrumstickParams {
+    pub radius: f64,
+    pub headOffset: f64, // ahead of the aim pose / エイムポーズの前方
+    pub pressDepth: f64,
+    pub releaseHeight: f64,
+    pub hoverRange: f64,
+    pub minVelocity: f64, // m/s toward the overlay / オーバーレイへ向かう m/s
+    pub debounceMs: f64,
+}
+
+/// Position over an overlay; `distance` is the height above its surface in meters
+/// オーバーレイ上の位置。`distance` は面からの高さ (メートル)
+#[napi(object)]
+pub struct TouchSample {
+    pub u: f64,
+    pub v: f64,
+    pub distance: f64,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TouchEventType {
+    Hover = 0,
+    Press = 1,
+    Release = 2,
+    HoverEnd = 3,
+}
+
+/// Direct touch on an overlay; `distance` is the height above its surface in meters
+/// オーバーレイへの直接タッチ。`distance` は面からの高さ (メートル)
+#[napi(object)]
+pub struct TouchEvent {
+    pub eventType: TouchEventType,
+    pub hand: InputHand,
+    pub u: f64,
+    pub v: f64,
+    pub distance: f64,
+}
+
+/// Trigger gesture thresholds; travel in overlay UV / トリガージェスチャーのしきい値 (移動量はオーバーレイ UV)
+#[napi(object)]
+pub struct TriggerGestureParams {
+    pub dragThreshold: f64,
+    pub clickCancelThreshold: f64,
+    pub longPressMs: f64,   // 0 disables / 0 で無効
+    pub repeatDelayMs: f64, // 0 disables / 0 で無効
+    pub repeatIntervalMs: f64,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GestureEventType {
+    Press = 0,
+    LongPress = 1,
+    Repeat = 2,
+    DragStart = 3,
+    Scroll = 4,
+    DragEnd = 5,
+    Release = 6,
+}
+
+/// `scrollDelta` is the vertical UV travel since the previous update, only set for Scroll
+/// `scrollDelta` は前回の更新からの縦方向の UV 移動量 (Scroll のみ)
+#[napi(object)]
+pub struct GestureEvent {
+    pub eventType: GestureEventType,
+    pub controllerIndex: u32,
+    pub u: f64,
+    pub v: f64,
+    pub scrollDelta: f64,
+}
+
+/// Point on an overlay in UV / オーバーレイ上の UV 座標
+#[napi(object)]
+pub struct OverlayPoint {
+    pub u: f64,
+    pub v: f64,
+}
+
+/// Events pushed by the native input poller / ネイティブ入力ポーラーが送るイベント
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputEventType {
+    HoverMove = 0,
+    HoverEnd = 1,
+    Press = 2,
+    Release = 3,
+    Scroll = 4,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct InputEvent {
+    pub eventType: InputEventType,
+    pub controllerIndex: u32,
+    pub u: f64, // last known UV for non-hover events / ホバー以外のイベントでは最後の UV
+    pub v: f64,
+    pub scrollDelta: f64, // Scroll only, positive = up / Scroll のみ、正が上
+    pub timestampMs: f64, // Unix epoch milliseconds / Unix エポックからのミリ秒
+}
+
+#[napi(object)]
+pub struct InputPollerOptions {
+    pub overlayHandle: i64,
+    pub rateHz: Option<u32>,
+    pub scrollDeadzone: Option<f64>,
+    pub scrollSpeed: Option<f64>, // scroll units per second at full deflection / 最大倒し時の毎秒スクロール量
+}
+
+/// 1€ filter tuning / 1€ フィルターの調整値
+#[napi(object)]
+pub struct PointerStabilizerParams {
+    pub enabled: bool,
+    pub minCutoff: f64,
+    pub beta: f64,
+    pub dcutoff: f64,
+}
+
+/// Mirrors `EDeviceActivityLevel` / `EDeviceActivityLevel` と同じ値
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeviceActivityLevel {
+    Unknown = -1,
+    Idle = 0,
+    UserInteraction = 1,
+    UserInteractionTimeout = 2,
+    Standby = 3,
+    IdleTimeout = 4,
+}
+
+#[napi(object)]
+pub struct PresenceState {
+    pub userPresent: bool,
+    pub activityLevel: DeviceActivityLevel,
+    pub standby: bool,
+    pub textureUploadsThrottled: bool,
+}
+
+/// Omitted fields keep their current value / 省略したフィールドは現在の値を維持
+#[napi(object)]
+pub struct TextureThrottleOptions {
+    pub enabled: Option<bool>,
+    pub idleIntervalMs: Option<f64>, // upload interval while the user is away / 不在時のアップロード間隔
+    pub skipWhenHidden: Option<bool>,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackingUniverse {
+    Seated = 0,
+    Standing = 1,
+    RawAndUncalibrated = 2,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackingResult {
+    Uninitialized = 1,
+    CalibratingInProgress = 100,
+    CalibratingOutOfRange = 101,
+    RunningOk = 200,
+    RunningOutOfRange = 201,
+    FallbackRotationOnly = 300,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PosePredictionMode {
+    Disabled = 0,
+    Fixed = 1,
+    Auto = 2,
+}
+
+#[napi(object)]
+pub struct DevicePose {
+    pub deviceIndex: u32,
+    pub poseIsValid: bool,
+    pub trackingResult: TrackingResult,
+    pub matrix: Vec<f64>,          // 4x4 flattened / 4x4平坦化済み
+    pub velocity: Vec<f64>,        // m/s
+    pub angularVelocity: Vec<f64>, // rad/s
+}
+
+/// Exactly one of `deviceIndex`, `role` or `deviceClass` must be set.
+/// `ordinal` picks the n-th connected device of `deviceClass` (default: 0).
+/// `deviceIndex` / `role` / `deviceClass` のいずれか1つだけを指定すること。
+/// `ordinal` は `deviceClass` の接続済みデバイスのうち n 番目を選ぶ (既定: 0)。
+#[napi(object)]
+pub struct TrackedDeviceSelector {
+    pub deviceIndex: Option<u32>,
+    pub role: Option<TrackedControllerRole>,
+    pub deviceClass: Option<TrackedDeviceClass>,
+    pub ordinal: Option<u32>,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToggleGestureMode {
+    Click = 0,
+    LongPress = 1,
+    DoublePress = 2,
+    Chord = 3,
+    HoldToShow = 4,
+}
+
+/// How the overlay toggle is recognized. Actions are boolean actions by name (default
+/// `toggle_overlay`), each read from `hand` / `secondaryHand` (default Any);
+/// `durationMs` is for LongPress, `windowMs` for DoublePress and `secondaryAction` for
+/// Chord. A chord may use one action on the left and right hands, e.g. both grips.
+/// オーバーレイ切替の認識方法。アクションは名前で指定するブールアクション (既定は
+/// `toggle_overlay`) で、それぞれ `hand` / `secondaryHand` (既定は Any) から読む。
+/// `durationMs` は LongPress、`windowMs` は DoublePress、`secondaryAction` は Chord で使う。
+/// Chord は左右の手で同じアクションを使える (例: 両手のグリップ)。
+#[napi(object)]
+pub struct ToggleGestureConfig {
+    pub mode: ToggleGestureMode,
+    pub action: Option<String>,
+    pub hand: Option<InputHand>,
+    pub secondaryAction: Option<String>,
+    pub secondaryHand: Option<InputHand>,
+    pub durationMs: Option<f64>,
+    pub windowMs: Option<f64>,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToggleGestureEvent {
+    None = 0,
+    Toggle = 1,
+    Show = 2,
+    Hide = 3,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ManifestIssueKind {
+    InvalidJson = 0,
+    MissingAction = 1,
+    UnknownAction = 2,
+    TypeMismatch = 3,
+    UnknownPath = 4,
+    MissingLocalization = 5,
+    MissingBindingFile = 6,
+    UnknownControllerType = 7,
+}
+
+/// Problem found by `validateActionManifest`; `path` is the action or input path concerned
+/// `validateActionManifest` が見つけた問題。`path` は対象のアクションまたは入力パス
+#[napi(object)]
+pub struct ManifestIssue {
+    pub kind: ManifestIssueKind,
+    pub file: String,
+    pub path: String,
+    pub message: String,
+}
+
+/// SteamVR input file contents; `name` is a file name or path
+/// SteamVR 入力ファイルの内容。`name` はファイル名またはパス
+#[napi(object)]
+pub struct ManifestFile {
+    pub name: String,
+    pub contents: String,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LegacyButtonMapSource {
+    /// No table for this controller type; the fixed fallback layout / この種別のテーブルが無く、固定の代替レイアウト
+    Generic = 0,
+    BuiltIn = 1,
+    /// Loaded by `loadLegacyButtonMaps` / `loadLegacyButtonMaps` で読み込んだもの
+    Config = 2,
+}
+
+/// Where a controller type reports each control in `GetControllerState`. Buttons are
+/// `EVRButtonId` bit positions, axes are `rAxis` indices; absent means no such control.
+/// `GetControllerState` でコントローラー種別が各操作を報告する位置。ボタンは `EVRButtonId` の
+/// ビット位置、軸は `rAxis` のインデックス。省略はその操作が無いことを表す。
+#[napi(object)]
+pub struct LegacyButtonMapInfo {
+    pub name: String,
+    pub source: LegacyButtonMapSource,
+    pub triggerButton: Option<u32>,
+    pub triggerAxis: Option<u32>,
+    pub gripButton: Option<u32>,
+    pub menuButton: Option<u32>,
+    pub touchpadButton: Option<u32>,
+    pub touchpadAxis: Option<u32>,
+    pub joystickButton: Option<u32>,
+    pub joystickAxis: Option<u32>,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SecondaryPointerPolicy {
+    /// Only the primary laser is shown; the other trigger just takes focus
+    /// 主ポインターのレーザーのみ表示し、もう一方のトリガーはフォーカスを取るだけ
+    Suppress = 0,
+    /// Both lasers show and click, for two-handed typing / 両手打ち用に両方のレーザーを表示しクリックも有効
+    TwoHanded = 1,
+}
+
+#[napi(object)]
+pub struct PointerArbitrationPolicy {
+    /// `Left` or `Right` / `Left` または `Right`
+    pub dominantHand: InputHand,
+    pub secondaryPointer: SecondaryPointerPolicy,
+}
+
+/// Raw buttons of one controller for `arbitrateControllers` / `arbitrateControllers` に渡すコントローラー1台分の生のボタン
+#[napi(object)]
+pub struct ControllerButtons {
+    pub controllerIndex: u32,
+    pub triggerPressed: bool,
+    pub gripPressed: bool,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointerRole {
+    Primary = 0,
+    Secondary = 1,
+    Suppressed = 2,
+}
+
+/// Arbitrated state of one controller; feed `triggerPressed` / `gripPressed` into click and
+/// drag handling instead of the raw buttons
+/// コントローラー1台分の調停結果。クリックとドラッグの処理には生のボタンの代わりに
+/// `triggerPressed` / `gripPressed` を使う
+#[napi(object)]
+pub struct PointerArbitration {
+    pub controllerIndex: u32,
+    pub role: PointerRole,
+    pub pointerVisible: bool,
+    pub triggerPressed: bool,
+    pub gripPressed: bool,
+    /// Became the primary pointer on this update / この更新で主ポインターになった
+    pub tookFocus: bool,
+}
+
+/// Key rectangle in overlay UV (0..1) measured from the top-left like the page, so v runs
+/// down, unlike the intersection UV the key lookups take
+/// ページと同じく左上から測ったオーバーレイ UV (0..1) でのキーの矩形。v は下向きで、
+/// キーの検索に渡す交点の UV とは逆になる
+#[napi(object)]
+pub struct KeyBounds {
+    pub id: String,
+    pub row: u32,
+    pub left: f64,
+    pub top: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[napi(object)]
+pub struct GazeDwellParams {
+    pub dwellMs: f64,
+    pub cooldownMs: f64,
+    /// Time on another key before the current dwell is dropped / 現在の注視を捨てるまでに別のキーを見る時間
+    pub switchMs: f64,
+}
+
+/// One head-gaze update; `progress` (0..1) drives the dwell ring and `selectedKey` is set
+/// on the update that completes a dwell
+/// 頭部視線の更新1回分。`progress` (0..1) は注視リングの表示用で、`selectedKey` は注視が
+/// 完了した更新でのみ設定される
+#[napi(object)]
+pub struct GazeDwellState {
+    pub hit: bool,
+    pub u: f64,
+    pub v: f64,
+    pub key: Option<String>,
+    pub progress: f64,
+    pub selectedKey: Option<String>,
+    pub coolingDown: bool,
+}
+
+/// Switch-access scanning: `action` names any boolean action in the set (default
+/// `trigger_click`) that acts as the single switch
+/// スイッチスキャンの設定。`action` はセット内の任意のブールアクションの名前で (既定は
+/// `trigger_click`)、これを唯一のスイッチとして使う
+#[napi(object)]
+pub struct SwitchScanConfig {
+    pub action: Option<String>,
+    pub stepMs: f64,
+    /// Hold on the first item of a row or of the row scan / 行や行スキャンの最初の項目の待ち時間
+    pub firstStepMs: f64,
+    /// Unanswered passes over a row before rows are scanned again / 行のスキャンに戻るまでの無選択の周回数
+    pub keyLoops: u32,
+}
+
+#[napi]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwitchScanEventType {
+    Highlight = 0,
+    Select = 1,
+}
+
+/// `keys` lists the row's key ids; `key` is unset while the whole row is highlighted
+/// `keys` は行のキー ID の一覧。行全体を強調表示している間は `key` は未設定
+#[napi(object)]
+pub struct SwitchScanEvent {
+    pub eventType: SwitchScanEventType,
+    pub row: u32,
+    pub keys: Vec<String>,
+    pub key: Option<String>,
+}
+
+/// D-pad key navigation; the deadzone is in stick units (0..1) and `acceleration`
+/// multiplies the repeat interval after every repeat
+/// D-pad によるキーのナビゲーション。デッドゾーンはスティックの単位 (0..1) で、
+/// `acceleration` はリピートのたびにリピート間隔に掛かる
+#[napi(object)]
+pub struct KeyNavigationParams {
+    pub deadzone: f64,
+    pub repeatDelayMs: f64,
+    pub repeatIntervalMs: f64,
+    pub minRepeatIntervalMs: f64,
+    pub acceleration: f64,
+    pub wrap: bool,
+}
+
+/// One navigation update; `committedKey` is set on the update that commits a key
+/// ナビゲーションの更新1回分。`committedKey` はキーを確定した更新でのみ設定される
+#[napi(object)]
+pub struct KeyNavigationState {
+    pub key: Option<String>,
+    pub moved: bool,
+    pub committedKey: Option<String>,
+}
+
+/// Swipe typing decoder settings; distances in overlay UV
+/// スワイプ入力の解読の設定 (距離はオーバーレイ UV)
+#[napi(object)]
+pub struct SwipeTypingParams {
+    pub samples: u32,
+    /// Shorter paths count as taps / これより短い軌跡はタップとみなす
+    pub minLength: f64,
+    /// How far the path's ends may be from a word's first and last keys
+    /// 軌跡の両端が単語の最初と最後のキーから離れてよい距離
+    pub endpointRadius: f64,
+    pub shapeSigma: f64,
+    pub locationSigma: f64,
+    pub maxCandidates: u32,
+}
+
+/// A decoded word; `score` is its share (0..1) among the returned candidates
+/// 解読した単語。`score` は返した候補の中での割合 (0..1)
+#[napi(object)]
+pub struct SwipeCandidate {
+    pub word: String,
+    pub score: f64,
+}
+
+/// One swipe update; `candidates` is ranked best first and only filled on the update
+/// that ends a swipe
+/// スワイプの更新1回分。`candidates` は良い順で、スワイプが終わった更新でのみ設定される
+#[napi(object)]
+pub struct SwipeTypingState {
+    pub recording: bool,
+    pub pointCount: u32,
+    pub candidates: Vec<SwipeCandidate>,
+}
+
+/// Adaptive key hit-target settings; `reach` is in key sizes
+/// 適応的なキーのヒット判定の設定。`reach` はキーの大きさ単位
+#[napi(object)]
+pub struct TouchModelParams {
+    pub minSamples: u32,
+    pub maxSamples: u32,
+    /// Window after a backspace in which a retype relabels the erased press
+    /// バックスペース後、打ち直しで消した押下を付け替える時間
+    pub correctionMs: f64,
+    pub reach: f64,
+}
+
+/// Learned hit offset of one key, in key widths and heights from its center (v down);
+/// the spread is a standard deviation
+/// 1キー分の学習済みのヒットのずれ。キーの中心からの幅・高さ単位 (v は下向き) で、
+/// spread は標準偏差
+#[napi(object)]
+pub struct TouchKeyOffset {
+    pub id: String,
+    pub samples: f64,
+    pub offsetX: f64,
+    pub offsetY: f64,
+    pub spreadX: f64,
+    pub spreadY: f64,
 }
@@ -0,0 +1,196 @@
+use napi_derive::napi;
+use openvr_sys as vr;
+
+use super::errors::require_fn;
+use super::handles::OverlayHandle;
+use super::manager::OverlayManager;
+use super::math::{invert_hmd_matrix34, mul_hmd_matrix34};
+use super::transform_ops::OverlayPlacement;
+use super::types::TrackingUniverse;
+
+const IDENTITY_MATRIX34: vr::HmdMatrix34_t = vr::HmdMatrix34_t {
+    m: [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+    ],
+};
+
+impl TrackingUniverse {
+    pub(super) fn to_vr(self) -> vr::ETrackingUniverseOrigin {
+        match self {
+            Self::Seated => vr::ETrackingUniverseOrigin_TrackingUniverseSeated,
+            Self::Standing => vr::ETrackingUniverseOrigin_TrackingUniverseStanding,
+            Self::RawAndUncalibrated => {
+                vr::ETrackingUniverseOrigin_TrackingUniverseRawAndUncalibrated
+            }
+        }
+    }
+
+    pub(super) fn from_vr(origin: vr::ETrackingUniverseOrigin) -> Self {
+        match origin {
+            vr::ETrackingUniverseOrigin_TrackingUniverseSeated => Self::Seated,
+            vr::ETrackingUniverseOrigin_TrackingUniverseRawAndUncalibrated => {
+                Self::RawAndUncalibrated
+            }
+            _ => Self::Standing,
+        }
+    }
+}
+
+/// New (origin, transform) for an overlay, `None` to leave it untouched
+/// オーバーレイの新しい (原点, 変換)。変更しない場合は `None`
+type Rebased = napi::Result<Option<(vr::ETrackingUniverseOrigin, vr::HmdMatrix34_t)>>;
+
+fn invert_or_error(m: &vr::HmdMatrix34_t, name: &str) -> napi::Result<vr::HmdMatrix34_t> {
+    invert_hmd_matrix34(m)
+        .ok_or_else(|| napi::Error::from_reason(format!("{name} is not invertible")))
+}
+
+impl OverlayManager {
+    /// Transform from a universe origin to the standing origin
+    /// ユニバース原点からスタンディング原点への変換
+    fn universe_to_standing(
+        &self,
+        origin: vr::ETrackingUniverseOrigin,
+    ) -> napi::Result<vr::HmdMatrix34_t> {
+        let system = self.system()?;
+        match origin {
+            vr::ETrackingUniverseOrigin_TrackingUniverseSeated => {
+                let get_seated_fn = require_fn(
+                    system.GetSeatedZeroPoseToStandingAbsoluteTrackingPose,
+                    "GetSeatedZeroPoseToStandingAbsoluteTrackingPose",
+                )?;
+                Ok(unsafe { get_seated_fn() })
+            }
+            vr::ETrackingUniverseOrigin_TrackingUniverseRawAndUncalibrated => {
+                let get_raw_fn = require_fn(
+                    system.GetRawZeroPoseToStandingAbsoluteTrackingPose,
+                    "GetRawZeroPoseToStandingAbsoluteTrackingPose",
+                )?;
+                Ok(unsafe { get_raw_fn() })
+            }
+            _ => Ok(IDENTITY_MATRIX34),
+        }
+    }
+
+    /// Record the seated zero pose as the baseline if none is recorded yet
+    /// まだ記録されていなければシーテッドのゼロポーズを基準として記録する
+    pub(super) fn capture_seated_zero_pose(&self) {
+        if self.seated_zero_pose().is_some() {
+            return;
+        }
+        if let Ok(pose) =
+            self.universe_to_standing(vr::ETrackingUniverseOrigin_TrackingUniverseSeated)
+        {
+            self.set_seated_zero_pose(pose);
+        }
+    }
+
+    /// Re-express a transform given in `from` in the `to` universe
+    /// `from` ユニバースで表された変換を `to` ユニバースで表し直す
+    pub(super) fn convert_universe_transform(
+        &self,
+        transform: &vr::HmdMatrix34_t,
+        from: vr::ETrackingUniverseOrigin,
+        to: vr::ETrackingUniverseOrigin,
+    ) -> napi::Result<vr::HmdMatrix34_t> {
+        if from == to {
+            return Ok(*transform);
+        }
+        let from_to_standing = self.universe_to_standing(from)?;
+        let standing_to_target =
+            invert_or_error(&self.universe_to_standing(to)?, "universe origin transform")?;
+        Ok(mul_hmd_matrix34(
+            &standing_to_target,
+            &mul_hmd_matrix34(&from_to_standing, transform),
+        ))
+    }
+
+    /// Re-place every owned absolute overlay for which `rebase` returns a new placement
+    /// `rebase` が新しい配置を返した所有中の絶対配置オーバーレイをすべて再配置する
+    fn rebase_owned_overlays(
+        &self,
+        mut rebase: impl FnMut(vr::ETrackingUniverseOrigin, &vr::HmdMatrix34_t) -> Rebased,
+    ) -> napi::Result<u32> {
+        let handles: Vec<u64> = self.borrow_owned_overlays()?.iter().copied().collect();
+        let mut rebased = 0;
+        for raw_handle in handles {
+            let handle = OverlayHandle::from_u64(raw_handle);
+            let OverlayPlacement::Absolute(origin, transform) =
+                self.read_overlay_placement(handle)?
+            else {
+                continue;
+            };
+            if let Some((new_origin, mut new_transform)) = rebase(origin, &transform)? {
+                self.apply_absolute_transform(handle, new_origin, &mut new_transform)?;
+                rebased += 1;
+            }
+        }
+        Ok(rebased)
+    }
+}
+
+#[napi]
+impl OverlayManager {
+    #[napi]
+    pub fn get_tracking_universe(&self) -> TrackingUniverse {
+        TrackingUniverse::from_vr(self.tracking_universe())
+    }
+
+    /// Switch the universe used by poses, intersections and absolute transforms.
+    /// Overlays already placed absolutely are converted so they stay put in the room.
+    /// ポーズ・交差判定・絶対変換で使うユニバースを切り替える。
+    /// 絶対配置済みのオーバーレイは部屋の中で動かないよう変換される。
+    #[napi]
+    pub fn set_tracking_universe(&self, universe: TrackingUniverse) -> napi::Result<()> {
+        let target = universe.to_vr();
+        if target == self.tracking_universe() {
+            return Ok(());
+        }
+        // Settle a pending seated zero pose change first so the conversion below uses
+        // the current seated origin / 下の変換が現在のシーテッド原点を使うよう、先に保留中のゼロポーズ変更を反映
+        self.refresh_seated_zero_pose()?;
+        self.rebase_owned_overlays(|origin, transform| {
+            if origin == target {
+                return Ok(None);
+            }
+            Ok(Some((
+                target,
+                self.convert_universe_transform(transform, origin, target)?,
+            )))
+        })?;
+        self.set_tracking_universe_origin(target);
+        self.update_input_poller(|config| config.universe = target)
+    }
+
+    /// Check the seated zero pose and keep seated overlays in place if it moved
+    /// (e.g. after "Reset seated position"). Returns the number of re-anchored overlays.
+    /// シーテッドのゼロポーズを確認し、移動していればシーテッド配置のオーバーレイを元の位置に保つ
+    /// (例: 「着座位置をリセット」の後)。再配置したオーバーレイ数を返す。
+    #[napi]
+    pub fn refresh_seated_zero_pose(&self) -> napi::Result<u32> {
+        let seated = vr::ETrackingUniverseOrigin_TrackingUniverseSeated;
+        let current = self.universe_to_standing(seated)?;
+        let previous = self.seated_zero_pose();
+        self.set_seated_zero_pose(current);
+
+        let Some(previous) = previous else {
+            return Ok(0);
+        };
+        if previous.m == current.m {
+            return Ok(0);
+        }
+
+        self.invalidate_poses_cache();
+        // old seated -> standing -> new seated / 旧シーテッド -> スタンディング -> 新シーテッド
+        let rebase_matrix =
+            mul_hmd_matrix34(&invert_or_error(&current, "seated zero pose")?, &previous);
+        self.rebase_owned_overlays(|origin, transform| {
+            if origin != seated {
+                return Ok(None);
+            }
+            Ok(Some((seated, mul_hmd_matrix34(&rebase_matrix, transform))))
+        })
+    }
+}
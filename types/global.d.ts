@@ -24,6 +24,28 @@ interface CheckUpdateResult {
   error?: string;
 }
 
+interface SteamVrBindingInfo {
+  devicePath: string;
+  inputPath: string;
+  mode: string;
+  slot: string;
+  sourceType: string;
+  localizedName?: string | null;
+}
+
+interface SteamVrActionOrigin {
+  localizedName: string;
+  trackedDeviceIndex?: number | null;
+  renderModelComponent: string;
+}
+
+interface SteamVrActionBindings {
+  name: string;
+  actionType: number;
+  bindings: SteamVrBindingInfo[];
+  origins: SteamVrActionOrigin[];
+}
+
 interface SteamVrAutoLaunchResult {
   success: boolean;
   enabled?: boolean;
@@ -78,6 +100,7 @@ interface ElectronAPI {
       gripBindings: string[];
       triggerBound: boolean;
       gripBound: boolean;
+      actions?: SteamVrActionBindings[];
     };
     error?: string;
   }>;